@@ -6,11 +6,13 @@
 use core::{
     convert::Infallible,
     net::{Ipv4Addr, SocketAddrV4},
+    ops::Range,
+    sync::atomic::{AtomicU16, Ordering},
 };
 
 use gba::prelude::*;
 use gba_mobile::{
-    Config, Digit, Dns, Driver, Link, Socket, Timer,
+    Config, Digit, Dns, Driver, Link, MobileCell, Socket, Timer,
     config::mobile_system_gb,
     socket::{self, NoSocket},
 };
@@ -99,30 +101,25 @@ impl socket::Buffer for RingBuffer {
     }
 }
 
+type Socket1 = Socket<RingBuffer>;
+type Socket2 = NoSocket;
+type Dns1 = Dns<14>;
+type Config1 = Config<mobile_system_gb::Config>;
+
 #[unsafe(link_section = ".ewram")]
-static mut DRIVER: Driver<Socket<RingBuffer>, NoSocket, Dns<14>, Config<mobile_system_gb::Config>> =
-    Driver::new(
-        Timer::_0,
-        Socket::new(RingBuffer::new()),
-        NoSocket,
-        Dns::new(),
-        Config::new(mobile_system_gb::Config::new()),
-    );
+static DRIVER: MobileCell<Socket1, Socket2, Dns1, Config1> = MobileCell::new(Driver::new(
+    Timer::_0,
+    Socket::new(RingBuffer::new()),
+    NoSocket,
+    Dns::new(),
+    Config::new(mobile_system_gb::Config::new()),
+));
 
-// TODO: This function should probably be unsafe.
-#[allow(static_mut_refs)]
-fn with_driver<T, F>(f: F) -> T
-where
-    F: FnOnce(
-        &mut Driver<Socket<RingBuffer>, NoSocket, Dns<14>, Config<mobile_system_gb::Config>>,
-    ) -> T,
-{
-    let previous_ime = IME.read();
-    IME.write(false);
-    let result = f(unsafe { &mut DRIVER });
-    IME.write(previous_ime);
-    result
-}
+/// Address range of IWRAM, where the `iwram` feature places the driver's serial hot path.
+const IWRAM: Range<usize> = 0x0300_0000..0x0300_8000;
+
+/// The longest serial interrupt observed so far, in CPU cycles.
+static LONGEST_SERIAL: AtomicU16 = AtomicU16::new(0);
 
 #[panic_handler]
 fn panic_handler(info: &core::panic::PanicInfo) -> ! {
@@ -136,13 +133,22 @@ extern "C" fn irq_handler(bits: IrqBits) {
     // To use gba_mobile, you must provide an interrupt handler that calls the library's interrupt
     // handler functions.
     if bits.vblank() {
-        with_driver(Driver::vblank);
+        DRIVER.vblank();
     }
     if bits.timer0() {
-        with_driver(Driver::timer);
+        DRIVER.timer();
     }
     if bits.serial() {
-        with_driver(Driver::serial);
+        // Timer 1 is otherwise unused, so it counts CPU cycles spent in the serial handler.
+        TIMER1_CONTROL.write(TimerControl::new());
+        TIMER1_RELOAD.write(0);
+        TIMER1_CONTROL.write(TimerControl::new().with_enabled(true));
+        DRIVER.serial();
+        let cycles = TIMER1_COUNT.read();
+        TIMER1_CONTROL.write(TimerControl::new());
+        if cycles > LONGEST_SERIAL.load(Ordering::Relaxed) {
+            LONGEST_SERIAL.store(cycles, Ordering::Relaxed);
+        }
     }
 }
 
@@ -150,6 +156,19 @@ extern "C" fn irq_handler(bits: IrqBits) {
 pub fn main() {
     let _ = mgba_log::init();
 
+    // The serial handler runs once per transferred byte, so the `iwram` feature places it in
+    // IWRAM. Check that the linker script honored that.
+    let driver_serial = Driver::<Socket1, Socket2, Dns1, Config1>::serial as usize;
+    let cell_serial = MobileCell::<Socket1, Socket2, Dns1, Config1>::serial as usize;
+    assert!(
+        IWRAM.contains(&driver_serial),
+        "Driver::serial is at {driver_serial:#010x}, outside IWRAM"
+    );
+    assert!(
+        IWRAM.contains(&cell_serial),
+        "MobileCell::serial is at {cell_serial:#010x}, outside IWRAM"
+    );
+
     RUST_IRQ_HANDLER.write(Some(irq_handler));
     DISPSTAT.write(DisplayStatus::new().with_irq_vblank(true));
     IE.write(
@@ -162,12 +181,12 @@ pub fn main() {
 
     VBlankIntrWait();
 
-    let pending_link = with_driver(Link::new);
+    let pending_link = DRIVER.with(Link::new);
 
     let status = loop {
         VBlankIntrWait();
 
-        let status = with_driver(|driver| pending_link.status(driver));
+        let status = DRIVER.with(|driver| pending_link.status(driver));
 
         if let None = status {
             continue;
@@ -180,7 +199,9 @@ pub fn main() {
     if let Some(Ok(link)) = status {
         log::info!(
             "connected to {} adapter",
-            with_driver(|driver| link.adapter(driver)).expect("unable to check adapter")
+            DRIVER
+                .with(|driver| link.adapter(driver))
+                .expect("unable to check adapter")
         );
 
         let write_config = mobile_system_gb::Config {
@@ -193,38 +214,40 @@ pub fn main() {
             pop_server: [0; 19],
             configuration_slots: Default::default(),
         };
-        with_driver(|driver| link.write_config(driver, write_config))
+        DRIVER
+            .with(|driver| link.write_config(driver, write_config))
             .expect("couldn't write config");
 
-        let config = with_driver(|driver| link.config(driver));
+        let config = DRIVER.with(|driver| link.config(driver));
         log::info!("attempted to parse Mobile System GB config: {config:?}");
 
         let pending_ppp = {
             log::info!("logging in!");
-            with_driver(|driver| {
-                link.login(
-                    driver,
-                    // #9677
-                    [
-                        Digit::try_from(b'#').unwrap(),
-                        Digit::try_from(b'9').unwrap(),
-                        Digit::try_from(b'6').unwrap(),
-                        Digit::try_from(b'7').unwrap(),
-                        Digit::try_from(b'7').unwrap(),
-                    ]
-                    .as_slice(),
-                    [],
-                    [],
-                    Ipv4Addr::from_octets([8, 8, 8, 8]),
-                    Ipv4Addr::from_octets([8, 8, 4, 4]),
-                )
-            })
-            .expect("login failed")
+            DRIVER
+                .with(|driver| {
+                    link.login(
+                        driver,
+                        // #9677
+                        [
+                            Digit::try_from(b'#').unwrap(),
+                            Digit::try_from(b'9').unwrap(),
+                            Digit::try_from(b'6').unwrap(),
+                            Digit::try_from(b'7').unwrap(),
+                            Digit::try_from(b'7').unwrap(),
+                        ]
+                        .as_slice(),
+                        [],
+                        [],
+                        Ipv4Addr::from_octets([8, 8, 8, 8]),
+                        Ipv4Addr::from_octets([8, 8, 4, 4]),
+                    )
+                })
+                .expect("login failed")
         };
         let ppp_status = loop {
             VBlankIntrWait();
 
-            let status = with_driver(|driver| pending_ppp.status(driver));
+            let status = DRIVER.with(|driver| pending_ppp.status(driver));
 
             if let None = status {
                 continue;
@@ -234,25 +257,26 @@ pub fn main() {
         log::info!("ppp connection status: {ppp_status:?}");
 
         if let Some(Ok(ppp)) = ppp_status {
-            let pending_dns = with_driver(|driver| ppp.dns(driver, "www.google.com"))
+            let pending_dns = DRIVER
+                .with(|driver| ppp.dns(driver, "www.google.com"))
                 .expect("DNS request failed");
             let dns_result = loop {
                 VBlankIntrWait();
 
-                let status = with_driver(|driver| pending_dns.status(driver));
+                let status = DRIVER.with(|driver| pending_dns.status(driver));
 
                 if let Some(result) = status {
                     break result.expect("DNS failure");
                 }
             };
 
-            let pending_tcp =
-                with_driver(|driver| ppp.socket_1_tcp(driver, SocketAddrV4::new(dns_result, 80)))
-                    .expect("TCP connection attempt failed");
+            let pending_tcp = DRIVER
+                .with(|driver| ppp.socket_1_tcp(driver, SocketAddrV4::new(dns_result, 80)))
+                .expect("TCP connection attempt failed");
             let tcp_status = loop {
                 VBlankIntrWait();
 
-                let status = with_driver(|driver| pending_tcp.status(driver));
+                let status = DRIVER.with(|driver| pending_tcp.status(driver));
 
                 if let None = status {
                     continue;
@@ -267,10 +291,12 @@ pub fn main() {
                 loop {
                     VBlankIntrWait();
                     let amount_written =
-                        with_driver(|driver| tcp.write(driver, &request).expect("write failed"));
+                        DRIVER.with(|driver| tcp.write(driver, &request).expect("write failed"));
                     request = &request[amount_written..];
                     if request.is_empty() {
-                        with_driver(|driver| tcp.flush(driver)).expect("flush failed");
+                        DRIVER
+                            .with(|driver| tcp.flush(driver))
+                            .expect("flush failed");
                         log::info!("write finished");
                         break;
                     }
@@ -280,7 +306,7 @@ pub fn main() {
                     VBlankIntrWait();
                     let mut buffer = [0; 256];
                     let read_amount =
-                        with_driver(|driver| tcp.read(driver, &mut buffer).expect("read failed"));
+                        DRIVER.with(|driver| tcp.read(driver, &mut buffer).expect("read failed"));
                     let s = str::from_utf8(&buffer[..read_amount]).expect("non-utf8 response");
                     if !s.is_empty() {
                         log::debug!("read amount: {read_amount}");
@@ -313,13 +339,15 @@ pub fn main() {
             let keys = gba::mmio::KEYINPUT.read();
             if keys.a() {
                 log::info!("connecting!");
-                let pending_p2p = with_driver(|driver| link.connect(driver, Ipv4Addr::LOCALHOST))
+                let pending_p2p = DRIVER
+                    .with(|driver| link.connect(driver, Ipv4Addr::LOCALHOST))
                     .expect("p2p connection failed");
                 break pending_p2p;
             } else if keys.b() {
                 log::info!("accepting!");
-                let pending_p2p =
-                    with_driver(|driver| link.accept(driver)).expect("p2p connection failed");
+                let pending_p2p = DRIVER
+                    .with(|driver| link.accept(driver))
+                    .expect("p2p connection failed");
                 break pending_p2p;
             }
         };
@@ -327,7 +355,7 @@ pub fn main() {
         let p2p_status = loop {
             VBlankIntrWait();
 
-            let status = with_driver(|driver| pending_p2p.status(driver));
+            let status = DRIVER.with(|driver| pending_p2p.status(driver));
 
             if let None = status {
                 continue;
@@ -338,8 +366,14 @@ pub fn main() {
         log::info!("p2p connection status: {p2p_status:?}");
     }
 
+    let mut logged_serial = 0;
     loop {
         VBlankIntrWait();
+        let longest_serial = LONGEST_SERIAL.load(Ordering::Relaxed);
+        if longest_serial > logged_serial {
+            log::debug!("longest serial interrupt: {longest_serial} cycles");
+            logged_serial = longest_serial;
+        }
     }
 }
 
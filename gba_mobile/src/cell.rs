@@ -0,0 +1,330 @@
+use crate::{Driver, config, dns, mmio::interrupt, socket};
+use core::{
+    cell::{Cell, UnsafeCell},
+    fmt,
+    fmt::{Debug, Formatter},
+};
+
+/// An interrupt event.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Event {
+    VBlank,
+    Serial,
+    Timer,
+}
+
+impl Event {
+    fn handle<Socket1, Socket2, Dns, Config>(
+        self,
+        driver: &mut Driver<Socket1, Socket2, Dns, Config>,
+    ) where
+        Socket1: socket::Slot,
+        Socket2: socket::Slot,
+        Dns: dns::Mode,
+        Config: config::Mode,
+    {
+        match self {
+            Self::VBlank => driver.vblank(),
+            Self::Serial => driver.serial(),
+            Self::Timer => driver.timer(),
+        }
+    }
+}
+
+/// Interrupt events deferred because they were raised while the driver was in use.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+struct Pending {
+    vblank: bool,
+    serial: bool,
+    timer: bool,
+}
+
+impl Pending {
+    fn with(self, event: Event) -> Self {
+        match event {
+            Event::VBlank => Self {
+                vblank: true,
+                ..self
+            },
+            Event::Serial => Self {
+                serial: true,
+                ..self
+            },
+            Event::Timer => Self {
+                timer: true,
+                ..self
+            },
+        }
+    }
+
+    /// The deferred events, in the order they are handled.
+    fn events(self) -> impl Iterator<Item = Event> {
+        [
+            (self.vblank, Event::VBlank),
+            (self.serial, Event::Serial),
+            (self.timer, Event::Timer),
+        ]
+        .into_iter()
+        .filter_map(|(pending, event)| pending.then_some(event))
+    }
+}
+
+/// A [`Driver`] that can be shared between the game's main loop and its interrupt handlers.
+///
+/// Every access made through [`MobileCell::with()`] disables interrupts for its duration, so an
+/// interrupt handler can never observe the driver partway through an update. The previous value
+/// of the interrupt master enable is restored afterward, so accesses can be made from within
+/// interrupt handlers as well. Interrupts raised in the meantime are held by the hardware until the
+/// access ends.
+///
+/// The interrupt entry points never wait on an access in progress, and never panic. If one is
+/// called while the driver is in use, such as from within [`MobileCell::with()`], the event is
+/// deferred and handled when the access ends. Deferred events are handled in a fixed order rather
+/// than the order they were raised in: a vblank first, then a serial event, then a timer event.
+///
+/// This is intended to be stored in a `static`, with the interrupt handler calling
+/// [`MobileCell::vblank()`], [`MobileCell::serial()`], and [`MobileCell::timer()`] directly.
+pub struct MobileCell<Socket1, Socket2, Dns, Config>
+where
+    Socket1: socket::Slot,
+    Socket2: socket::Slot,
+    Dns: dns::Mode,
+    Config: config::Mode,
+{
+    driver: UnsafeCell<Driver<Socket1, Socket2, Dns, Config>>,
+    borrowed: Cell<bool>,
+    pending: Cell<Pending>,
+}
+
+// SAFETY: The GBA has a single core, and the driver is only ever accessed with interrupts disabled
+// and with reentrant accesses rejected or deferred, so no two references to it can exist at once.
+unsafe impl<Socket1, Socket2, Dns, Config> Sync for MobileCell<Socket1, Socket2, Dns, Config>
+where
+    Socket1: socket::Slot,
+    Socket2: socket::Slot,
+    Dns: dns::Mode,
+    Config: config::Mode,
+{
+}
+
+impl<Socket1, Socket2, Dns, Config> MobileCell<Socket1, Socket2, Dns, Config>
+where
+    Socket1: socket::Slot,
+    Socket2: socket::Slot,
+    Dns: dns::Mode,
+    Config: config::Mode,
+{
+    pub const fn new(driver: Driver<Socket1, Socket2, Dns, Config>) -> Self {
+        Self {
+            driver: UnsafeCell::new(driver),
+            borrowed: Cell::new(false),
+            pending: Cell::new(Pending {
+                vblank: false,
+                serial: false,
+                timer: false,
+            }),
+        }
+    }
+
+    /// Runs `f` with exclusive access to the driver, with interrupts disabled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called again from within `f`.
+    #[cfg_attr(feature = "iwram", inline(never), unsafe(link_section = ".iwram"))]
+    pub fn with<T, F>(&self, f: F) -> T
+    where
+        F: FnOnce(&mut Driver<Socket1, Socket2, Dns, Config>) -> T,
+    {
+        self.access(|driver| driver.map(f))
+            .unwrap_or_else(|| panic!("the driver is already in use"))
+    }
+
+    /// Runs `f` with interrupts disabled, passing it the driver unless it is already in use.
+    ///
+    /// Any events deferred while the driver was in use are handled before interrupts are enabled
+    /// again.
+    #[cfg_attr(feature = "iwram", inline(never), unsafe(link_section = ".iwram"))]
+    fn access<T, F>(&self, f: F) -> T
+    where
+        F: FnOnce(Option<&mut Driver<Socket1, Socket2, Dns, Config>>) -> T,
+    {
+        let master_enable = unsafe { interrupt::MASTER_ENABLE.read_volatile() };
+        unsafe { interrupt::MASTER_ENABLE.write_volatile(0) };
+        let result = if self.borrowed.get() {
+            f(None)
+        } else {
+            self.borrowed.set(true);
+            // SAFETY: Interrupts are disabled and `borrowed` was not set, so this is the only
+            // reference to the driver.
+            let driver = unsafe { &mut *self.driver.get() };
+            let result = f(Some(driver));
+            for event in self.pending.take().events() {
+                event.handle(driver);
+            }
+            self.borrowed.set(false);
+            result
+        };
+        unsafe { interrupt::MASTER_ENABLE.write_volatile(master_enable) };
+        result
+    }
+
+    /// Handles `event` now, or defers it until the current access ends.
+    #[cfg_attr(feature = "iwram", inline(never), unsafe(link_section = ".iwram"))]
+    fn handle(&self, event: Event) {
+        self.access(|driver| match driver {
+            Some(driver) => event.handle(driver),
+            None => self.pending.set(self.pending.get().with(event)),
+        })
+    }
+
+    /// Handles a vblank interrupt.
+    ///
+    /// See [`Driver::vblank()`].
+    pub fn vblank(&self) {
+        self.handle(Event::VBlank)
+    }
+
+    /// Handles a serial interrupt.
+    ///
+    /// See [`Driver::serial()`].
+    #[cfg_attr(feature = "iwram", inline(never), unsafe(link_section = ".iwram"))]
+    pub fn serial(&self) {
+        self.handle(Event::Serial)
+    }
+
+    /// Handles a timer interrupt.
+    ///
+    /// See [`Driver::timer()`].
+    #[cfg_attr(feature = "iwram", inline(never), unsafe(link_section = ".iwram"))]
+    pub fn timer(&self) {
+        self.handle(Event::Timer)
+    }
+
+    /// Handles any serial or timer events that have occurred since the last poll.
+    ///
+    /// See [`Driver::poll()`].
+    pub fn poll(&self) {
+        self.with(Driver::poll)
+    }
+}
+
+impl<Socket1, Socket2, Dns, Config> Debug for MobileCell<Socket1, Socket2, Dns, Config>
+where
+    Socket1: socket::Slot,
+    Socket2: socket::Slot,
+    Dns: dns::Mode,
+    Config: config::Mode,
+{
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        // The driver itself can't be shown without taking exclusive access to it.
+        formatter.debug_struct("MobileCell").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Event, MobileCell, Pending};
+    use crate::{Driver, Timer, config::NoConfig, dns::NoDns, mmio::interrupt, socket::NoSocket};
+    use alloc::vec::Vec;
+    use gba_test::test;
+
+    type TestCell = MobileCell<NoSocket, NoSocket, NoDns, NoConfig>;
+
+    fn cell() -> TestCell {
+        MobileCell::new(Driver::new(Timer::_0, NoSocket, NoSocket, NoDns, NoConfig))
+    }
+
+    /// Runs `f` with the interrupt master enable set to `master_enable`, restoring it afterward.
+    fn with_master_enable(master_enable: u16, f: impl FnOnce()) -> u16 {
+        let previous = unsafe { interrupt::MASTER_ENABLE.read_volatile() };
+        unsafe { interrupt::MASTER_ENABLE.write_volatile(master_enable) };
+        f();
+        let after = unsafe { interrupt::MASTER_ENABLE.read_volatile() };
+        unsafe { interrupt::MASTER_ENABLE.write_volatile(previous) };
+        after
+    }
+
+    #[test]
+    fn with_disables_interrupts() {
+        let cell = cell();
+        let after = with_master_enable(1, || {
+            cell.with(|_| {
+                assert_eq!(unsafe { interrupt::MASTER_ENABLE.read_volatile() }, 0);
+            })
+        });
+
+        assert_eq!(after, 1);
+    }
+
+    #[test]
+    fn with_keeps_interrupts_disabled() {
+        let cell = cell();
+        let after = with_master_enable(0, || cell.with(|_| {}));
+
+        assert_eq!(after, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn with_reentrant() {
+        let cell = cell();
+        cell.with(|_| cell.with(|_| {}));
+    }
+
+    #[test]
+    fn handler_deferred_while_in_use() {
+        let cell = cell();
+        cell.with(|_| {
+            cell.serial();
+            cell.timer();
+            cell.vblank();
+
+            assert_eq!(
+                cell.pending.get().events().collect::<Vec<_>>(),
+                [Event::VBlank, Event::Serial, Event::Timer]
+            );
+        });
+
+        assert_eq!(cell.pending.get(), Pending::default());
+    }
+
+    #[test]
+    fn handler_deferred_vblank_before_serial() {
+        let cell = cell();
+        cell.with(|_| {
+            cell.serial();
+            cell.vblank();
+
+            assert_eq!(
+                cell.pending.get().events().collect::<Vec<_>>(),
+                [Event::VBlank, Event::Serial]
+            );
+        });
+
+        assert_eq!(cell.pending.get(), Pending::default());
+    }
+
+    #[test]
+    fn handler_deferred_while_in_use_from_interrupt() {
+        let cell = cell();
+        // A vblank handler is partway through an access when the serial interrupt arrives.
+        cell.borrowed.set(true);
+        let after = with_master_enable(1, || cell.serial());
+        cell.borrowed.set(false);
+
+        assert_eq!(after, 1);
+        assert_eq!(
+            cell.pending.get().events().collect::<Vec<_>>(),
+            [Event::Serial]
+        );
+    }
+
+    #[test]
+    fn handler_not_deferred() {
+        let cell = cell();
+        cell.serial();
+
+        assert_eq!(cell.pending.get(), Pending::default());
+    }
+}
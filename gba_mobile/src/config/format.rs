@@ -1,11 +1,52 @@
+use core::{
+    fmt,
+    fmt::{Display, Formatter},
+};
 use deranged::RangedU8;
 
+/// The size of the adapter's configuration block, in bytes.
+pub const BLOCK_LEN: usize = 0xc0;
+
 #[derive(Clone, Copy, Debug)]
 pub struct Location {
     pub offset: u8,
     pub length: RangedU8<0, 128>,
 }
 
+impl Location {
+    /// Checks that this location lies entirely within the configuration block.
+    pub fn validate(self) -> Result<Self, LocationError> {
+        if self.offset as usize + self.length.get() as usize > BLOCK_LEN {
+            Err(LocationError {
+                offset: self.offset,
+                length: self.length.get(),
+            })
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+/// A [`Location`] extending past the end of the configuration block.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LocationError {
+    offset: u8,
+    length: u8,
+}
+
+impl Display for LocationError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "configuration write of {} bytes at offset {:#04x} extends past the end of the \
+             {BLOCK_LEN}-byte configuration block",
+            self.length, self.offset
+        )
+    }
+}
+
+impl core::error::Error for LocationError {}
+
 pub trait Format: Sized + Clone {
     const WRITES: usize;
 
@@ -14,6 +55,15 @@ pub trait Format: Sized + Clone {
 
     fn segments() -> Self::Segments;
     fn write(&self, request: usize, bytes: &mut [u8; 128]) -> Location;
+
+    /// Whether a read error indicates the adapter's configuration was never initialized.
+    ///
+    /// Brand new adapters return a blank configuration block. Formats that can recognize this
+    /// allow the driver to fail fast on operations that depend on the configuration.
+    fn uninitialized(error: &Self::Error) -> bool {
+        let _ = error;
+        false
+    }
 }
 
 pub enum ReadResult<Format, Segments> {
@@ -30,3 +80,71 @@ pub trait Segments: Sized {
         bytes: &[u8],
     ) -> Result<ReadResult<Self::Format, Self>, <Self::Format as Format>::Error>;
 }
+
+/// Checks that every write of `format` lies within the configuration block.
+///
+/// All public APIs that write a configuration go through here, ensuring that no write outside of
+/// the block is ever scheduled.
+pub(crate) fn validate_writes<Format>(format: &Format) -> Result<(), LocationError>
+where
+    Format: self::Format,
+{
+    let mut bytes = [0; 128];
+    for request in 0..Format::WRITES {
+        format.write(request, &mut bytes).validate()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Location, validate_writes};
+    use crate::config::mobile_system_gb;
+    use alloc::format;
+    use claims::{assert_err, assert_ok};
+    use core::net::Ipv4Addr;
+    use deranged::RangedU8;
+    use gba_test::test;
+
+    #[test]
+    fn location_end_of_block() {
+        assert_ok!(
+            Location {
+                offset: 0x80,
+                length: RangedU8::new_static::<0x40>(),
+            }
+            .validate()
+        );
+    }
+
+    #[test]
+    fn location_past_end_of_block() {
+        let error = assert_err!(
+            Location {
+                offset: 0x81,
+                length: RangedU8::new_static::<0x40>(),
+            }
+            .validate()
+        );
+
+        assert_eq!(
+            format!("{error}"),
+            "configuration write of 64 bytes at offset 0x81 extends past the end of the 192-byte \
+             configuration block"
+        );
+    }
+
+    #[test]
+    fn mobile_system_gb_writes_within_block() {
+        assert_ok!(validate_writes(&mobile_system_gb::Config {
+            registration: mobile_system_gb::Registration::Complete,
+            primary_dns: Ipv4Addr::UNSPECIFIED,
+            secondary_dns: Ipv4Addr::UNSPECIFIED,
+            login_id: [0; 10],
+            email: [0; 24],
+            smtp_server: [0; 20],
+            pop_server: [0; 19],
+            configuration_slots: Default::default(),
+        }));
+    }
+}
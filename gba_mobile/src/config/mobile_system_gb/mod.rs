@@ -1,13 +1,13 @@
+pub mod portable;
 pub mod registration;
 pub mod segments;
 
 mod error;
-mod phone_number;
 mod slot;
 
+pub use crate::digit::PhoneNumber;
 use deranged::RangedU8;
 pub use error::Error;
-pub use phone_number::PhoneNumber;
 pub use registration::Registration;
 pub use slot::Slot;
 
@@ -204,4 +204,86 @@ impl super::Format for Config {
             _ => unreachable!(),
         }
     }
+
+    fn uninitialized(error: &Self::Error) -> bool {
+        // A configuration that was never written will not contain the "MA" header.
+        matches!(error, Error::HeaderM(_) | Error::HeaderA(_))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Config, Error, Registration, Slot};
+    use crate::config::{self, Format, Sealed, format::Segments};
+    use core::net::Ipv4Addr;
+    use gba_test::test;
+
+    #[test]
+    fn uninitialized_blank_block() {
+        let error = match Config::segments().read(&[0xff; 0x71]) {
+            Err(error) => error,
+            Ok(_) => panic!("blank configuration block was read successfully"),
+        };
+
+        assert!(Config::uninitialized(&error));
+    }
+
+    #[test]
+    fn uninitialized_missing_a() {
+        assert!(Config::uninitialized(&Error::HeaderA(0x00)));
+    }
+
+    #[test]
+    fn not_uninitialized_checksum() {
+        assert!(!Config::uninitialized(&Error::Checksum {
+            calculated: 0x1234,
+            received: 0x4321,
+        }));
+    }
+
+    #[test]
+    fn not_uninitialized_registration() {
+        let mut bytes = [0; 0x71];
+        bytes[0] = b'M';
+        bytes[1] = b'A';
+        bytes[2] = 0x42;
+        let error = match Config::segments().read(&bytes) {
+            Err(error) => error,
+            Ok(_) => panic!("invalid registration byte was read successfully"),
+        };
+
+        assert!(matches!(error, Error::Registration(_)));
+        assert!(!Config::uninitialized(&error));
+    }
+
+    #[test]
+    fn needs_initialization_after_blank_read() {
+        let mut config = crate::Config::<Config>::new(Config::new());
+        assert!(!config.needs_initialization());
+
+        config.read(Err(Error::HeaderM(0xff)));
+        assert!(config.needs_initialization());
+    }
+
+    #[test]
+    fn initialized_after_write() {
+        let mut config = crate::Config::<Config>::new(Config::new());
+        config.read(Err(Error::HeaderM(0xff)));
+
+        config.data = config::Data::Config(Config {
+            registration: Registration::Complete,
+            primary_dns: Ipv4Addr::UNSPECIFIED,
+            secondary_dns: Ipv4Addr::UNSPECIFIED,
+            login_id: [0; 10],
+            email: [0; 24],
+            smtp_server: [0; 20],
+            pop_server: [0; 19],
+            configuration_slots: [Slot::default(), Slot::default(), Slot::default()],
+        });
+        // The new value has only been requested, not yet accepted by the adapter.
+        assert!(config.needs_initialization());
+
+        config.written();
+        assert!(!config.needs_initialization());
+    }
 }
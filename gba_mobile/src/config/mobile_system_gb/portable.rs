@@ -0,0 +1,470 @@
+//! A stable serialized form of the configuration, for storing in game save files.
+//!
+//! Unlike the adapter's own configuration layout, this only covers the fields a game may let a
+//! player edit, and it is kept stable across versions of this crate.
+//!
+//! # Format
+//!
+//! The first byte is the format version, which is currently `1`. It is followed by any number of
+//! fields, each made up of a tag byte, a length byte, and then `length` bytes of value:
+//!
+//! | Tag            | Field                                     | Value                  |
+//! | -------------- | ----------------------------------------- | ---------------------- |
+//! | `0x01`         | Primary DNS server                        | 4 octets               |
+//! | `0x02`         | Secondary DNS server                      | 4 octets               |
+//! | `0x03`         | Login ID                                  | Up to 10 bytes         |
+//! | `0x04`         | Email address                             | Up to 24 bytes         |
+//! | `0x05`         | SMTP server                               | Up to 20 bytes         |
+//! | `0x06`         | POP server                                | Up to 19 bytes         |
+//! | `0x10`..`0x12` | Phone number of configuration slot 0 to 2 | Up to 16 ASCII digits  |
+//! | `0x20`..`0x22` | ID of configuration slot 0 to 2           | Up to 16 bytes         |
+//!
+//! Byte string values have their trailing zero padding removed. Fields that are missing are left
+//! empty when reading, and fields with unknown tags are skipped, so that data written by a later
+//! version of this format can still be read.
+
+use super::{Config, PhoneNumber, Registration, Slot};
+use crate::Digit;
+use core::{
+    fmt,
+    fmt::{Debug, Display, Formatter},
+    net::Ipv4Addr,
+};
+
+const VERSION: u8 = 1;
+
+const PRIMARY_DNS: u8 = 0x01;
+const SECONDARY_DNS: u8 = 0x02;
+const LOGIN_ID: u8 = 0x03;
+const EMAIL: u8 = 0x04;
+const SMTP_SERVER: u8 = 0x05;
+const POP_SERVER: u8 = 0x06;
+const SLOT_PHONE_NUMBERS: [u8; 3] = [0x10, 0x11, 0x12];
+const SLOT_IDS: [u8; 3] = [0x20, 0x21, 0x22];
+
+/// The byte string with its trailing zero padding removed.
+fn trimmed(bytes: &[u8]) -> &[u8] {
+    let len = bytes
+        .iter()
+        .rposition(|&byte| byte != 0)
+        .map_or(0, |index| index + 1);
+    &bytes[..len]
+}
+
+struct Writer<'a> {
+    bytes: &'a mut [u8],
+    len: usize,
+}
+
+impl Writer<'_> {
+    fn push(&mut self, byte: u8) -> Result<(), Error> {
+        let slot = self.bytes.get_mut(self.len).ok_or(Error {
+            kind: Kind::BufferTooSmall,
+        })?;
+        *slot = byte;
+        self.len += 1;
+        Ok(())
+    }
+
+    fn field<Value>(&mut self, tag: u8, value: Value) -> Result<(), Error>
+    where
+        Value: IntoIterator<Item = u8>,
+    {
+        self.push(tag)?;
+        let length_index = self.len;
+        self.push(0)?;
+        for byte in value {
+            self.push(byte)?;
+        }
+        // Every field is shorter than 256 bytes, so the length always fits.
+        self.bytes[length_index] = (self.len - length_index - 1) as u8;
+        Ok(())
+    }
+}
+
+fn read_string<const N: usize>(tag: u8, value: &[u8]) -> Result<[u8; N], Error> {
+    if value.len() > N {
+        return Err(Error {
+            kind: Kind::FieldTooLong {
+                tag,
+                length: value.len() as u8,
+            },
+        });
+    }
+    let mut string = [0; N];
+    string[..value.len()].copy_from_slice(value);
+    Ok(string)
+}
+
+fn read_ip(tag: u8, value: &[u8]) -> Result<Ipv4Addr, Error> {
+    <[u8; 4]>::try_from(value)
+        .map(Ipv4Addr::from)
+        .map_err(|_| Error {
+            kind: Kind::FieldLength {
+                tag,
+                length: value.len() as u8,
+            },
+        })
+}
+
+fn read_phone_number(tag: u8, value: &[u8]) -> Result<PhoneNumber, Error> {
+    if value.len() > 16 {
+        return Err(Error {
+            kind: Kind::FieldTooLong {
+                tag,
+                length: value.len() as u8,
+            },
+        });
+    }
+    if let Some(&byte) = value.iter().find(|&&byte| Digit::try_from(byte).is_err()) {
+        return Err(Error {
+            kind: Kind::InvalidDigit(byte),
+        });
+    }
+    PhoneNumber::from_digits(value.iter().filter_map(|&byte| Digit::try_from(byte).ok())).map_err(
+        |_| Error {
+            kind: Kind::FieldTooLong {
+                tag,
+                length: value.len() as u8,
+            },
+        },
+    )
+}
+
+impl Config {
+    /// The most bytes [`to_portable()`](Self::to_portable) will write.
+    pub const MAX_PORTABLE_LEN: usize = 202;
+
+    /// Writes the game-editable fields of this configuration to `bytes` in the [portable
+    /// format](self).
+    ///
+    /// Returns the number of bytes written, which is never more than
+    /// [`MAX_PORTABLE_LEN`](Self::MAX_PORTABLE_LEN).
+    pub fn to_portable(&self, bytes: &mut [u8]) -> Result<usize, Error> {
+        let mut writer = Writer { bytes, len: 0 };
+        writer.push(VERSION)?;
+
+        writer.field(PRIMARY_DNS, self.primary_dns.octets())?;
+        writer.field(SECONDARY_DNS, self.secondary_dns.octets())?;
+        writer.field(LOGIN_ID, trimmed(&self.login_id).iter().copied())?;
+        writer.field(EMAIL, trimmed(&self.email).iter().copied())?;
+        writer.field(SMTP_SERVER, trimmed(&self.smtp_server).iter().copied())?;
+        writer.field(POP_SERVER, trimmed(&self.pop_server).iter().copied())?;
+        for (slot, (phone_number_tag, id_tag)) in self
+            .configuration_slots
+            .iter()
+            .zip(SLOT_PHONE_NUMBERS.into_iter().zip(SLOT_IDS))
+        {
+            writer.field(
+                phone_number_tag,
+                slot.phone_number
+                    .into_iter()
+                    .map(|digit| char::from(digit) as u8),
+            )?;
+            writer.field(id_tag, trimmed(&slot.id).iter().copied())?;
+        }
+
+        Ok(writer.len)
+    }
+
+    /// Reads a configuration from bytes written by [`to_portable()`](Self::to_portable).
+    ///
+    /// The portable format does not store the registration status, since it is not the game's to
+    /// edit. The returned configuration is marked as registered, as it is only meaningful to write
+    /// a configuration that the player has already set up.
+    pub fn from_portable(bytes: &[u8]) -> Result<Self, Error> {
+        let (&version, mut fields) = bytes.split_first().ok_or(Error { kind: Kind::Empty })?;
+        if version != VERSION {
+            return Err(Error {
+                kind: Kind::UnsupportedVersion(version),
+            });
+        }
+
+        let mut config = Self {
+            registration: Registration::Complete,
+            primary_dns: Ipv4Addr::UNSPECIFIED,
+            secondary_dns: Ipv4Addr::UNSPECIFIED,
+            login_id: [0; 10],
+            email: [0; 24],
+            smtp_server: [0; 20],
+            pop_server: [0; 19],
+            configuration_slots: [Slot::default(), Slot::default(), Slot::default()],
+        };
+        while let [tag, length, rest @ ..] = fields {
+            let (tag, length) = (*tag, *length as usize);
+            if rest.len() < length {
+                return Err(Error {
+                    kind: Kind::Truncated,
+                });
+            }
+            let (value, rest) = rest.split_at(length);
+            match tag {
+                PRIMARY_DNS => config.primary_dns = read_ip(tag, value)?,
+                SECONDARY_DNS => config.secondary_dns = read_ip(tag, value)?,
+                LOGIN_ID => config.login_id = read_string(tag, value)?,
+                EMAIL => config.email = read_string(tag, value)?,
+                SMTP_SERVER => config.smtp_server = read_string(tag, value)?,
+                POP_SERVER => config.pop_server = read_string(tag, value)?,
+                0x10..=0x12 => {
+                    config.configuration_slots[(tag - 0x10) as usize].phone_number =
+                        read_phone_number(tag, value)?
+                }
+                0x20..=0x22 => {
+                    config.configuration_slots[(tag - 0x20) as usize].id = read_string(tag, value)?
+                }
+                // Fields added by later versions of the format are skipped.
+                _ => {}
+            }
+            fields = rest;
+        }
+        if !fields.is_empty() {
+            // A lone tag without its length.
+            return Err(Error {
+                kind: Kind::Truncated,
+            });
+        }
+
+        Ok(config)
+    }
+}
+
+/// An error writing or reading the portable configuration format.
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct Error {
+    kind: Kind,
+}
+
+impl Debug for Error {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        Debug::fmt(&self.kind, formatter)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        Display::fmt(&self.kind, formatter)
+    }
+}
+
+impl core::error::Error for Error {}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Kind {
+    BufferTooSmall,
+    Empty,
+    UnsupportedVersion(u8),
+    Truncated,
+    FieldLength { tag: u8, length: u8 },
+    FieldTooLong { tag: u8, length: u8 },
+    InvalidDigit(u8),
+}
+
+impl Display for Kind {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::BufferTooSmall => formatter.write_str("buffer is too small for configuration"),
+            Self::Empty => formatter.write_str("configuration data is empty"),
+            Self::UnsupportedVersion(version) => {
+                write!(
+                    formatter,
+                    "unsupported configuration format version {version}"
+                )
+            }
+            Self::Truncated => formatter.write_str("configuration data is truncated"),
+            Self::FieldLength { tag, length } => write!(
+                formatter,
+                "field {tag:#04x} has invalid length of {length} bytes"
+            ),
+            Self::FieldTooLong { tag, length } => write!(
+                formatter,
+                "field {tag:#04x} is {length} bytes long, which is too long"
+            ),
+            Self::InvalidDigit(byte) => {
+                write!(formatter, "phone number contains invalid digit {byte:#04x}")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Config, PhoneNumber, Registration, Slot};
+    use crate::Digit;
+    use alloc::format;
+    use claims::{assert_err, assert_ok};
+    use core::net::Ipv4Addr;
+    use gba_test::test;
+
+    /// The portable form of `config()`, as written by version 1 of the format.
+    ///
+    /// This must never change, since it stands in for data already stored in save files.
+    const FIXTURE: [u8; 69] = [
+        0x01, // Version.
+        0x01, 0x04, 192, 168, 0, 1, // Primary DNS.
+        0x02, 0x04, 8, 8, 4, 4, // Secondary DNS.
+        0x03, 0x04, b'g', b'b', b'a', b'1', // Login ID.
+        0x04, 0x0b, b'm', b'e', b'@', b'e', b'x', b'a', b'm', b'p', b'l', b'e',
+        b'.', // Email.
+        0x05, 0x04, b's', b'm', b't', b'p', // SMTP server.
+        0x06, 0x03, b'p', b'o', b'p', // POP server.
+        0x10, 0x07, b'8', b'6', b'7', b'5', b'3', b'0', b'9', // Slot 0 phone number.
+        0x20, 0x04, b'h', b'o', b'm', b'e', // Slot 0 ID.
+        0x11, 0x00, // Slot 1 phone number.
+        0x21, 0x00, // Slot 1 ID.
+        0x12, 0x03, b'#', b'1', b'*', // Slot 2 phone number.
+        0x22, 0x00, // Slot 2 ID.
+    ];
+
+    fn string<const N: usize>(value: &[u8]) -> [u8; N] {
+        let mut string = [0; N];
+        string[..value.len()].copy_from_slice(value);
+        string
+    }
+
+    fn phone_number(digits: &str) -> PhoneNumber {
+        assert_ok!(PhoneNumber::from_digits(
+            digits.chars().map(|c| assert_ok!(Digit::try_from(c)))
+        ))
+    }
+
+    fn config() -> Config {
+        Config {
+            registration: Registration::Complete,
+            primary_dns: Ipv4Addr::new(192, 168, 0, 1),
+            secondary_dns: Ipv4Addr::new(8, 8, 4, 4),
+            login_id: string(b"gba1"),
+            email: string(b"me@example."),
+            smtp_server: string(b"smtp"),
+            pop_server: string(b"pop"),
+            configuration_slots: [
+                Slot {
+                    phone_number: phone_number("8675309"),
+                    id: string(b"home"),
+                },
+                Slot::default(),
+                Slot {
+                    phone_number: phone_number("#1*"),
+                    id: [0; 16],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut bytes = [0; Config::MAX_PORTABLE_LEN];
+        let len = assert_ok!(config().to_portable(&mut bytes));
+
+        assert_eq!(
+            format!("{:?}", assert_ok!(Config::from_portable(&bytes[..len]))),
+            format!("{:?}", config())
+        );
+    }
+
+    #[test]
+    fn maximum_size() {
+        let full = Config {
+            registration: Registration::Complete,
+            primary_dns: Ipv4Addr::BROADCAST,
+            secondary_dns: Ipv4Addr::BROADCAST,
+            login_id: [b'a'; 10],
+            email: [b'a'; 24],
+            smtp_server: [b'a'; 20],
+            pop_server: [b'a'; 19],
+            configuration_slots: [0, 1, 2].map(|_| Slot {
+                phone_number: phone_number("0123456789012345"),
+                id: [b'a'; 16],
+            }),
+        };
+        let mut bytes = [0; Config::MAX_PORTABLE_LEN];
+
+        assert_eq!(
+            assert_ok!(full.to_portable(&mut bytes)),
+            Config::MAX_PORTABLE_LEN
+        );
+    }
+
+    #[test]
+    fn writes_fixture() {
+        let mut bytes = [0; Config::MAX_PORTABLE_LEN];
+        let len = assert_ok!(config().to_portable(&mut bytes));
+
+        assert_eq!(&bytes[..len], FIXTURE.as_slice());
+    }
+
+    #[test]
+    fn reads_fixture() {
+        assert_eq!(
+            format!("{:?}", assert_ok!(Config::from_portable(&FIXTURE))),
+            format!("{:?}", config())
+        );
+    }
+
+    #[test]
+    fn buffer_too_small() {
+        let mut bytes = [0; 16];
+
+        let error = assert_err!(config().to_portable(&mut bytes));
+        assert_eq!(format!("{error:?}"), "BufferTooSmall");
+    }
+
+    #[test]
+    fn unknown_tag_skipped() {
+        let bytes = [
+            0x01, // Version.
+            0x7f, 0x02, 0xaa, 0xbb, // Unknown.
+            0x03, 0x02, b'i', b'd', // Login ID.
+        ];
+
+        let config = assert_ok!(Config::from_portable(&bytes));
+        assert_eq!(config.login_id, string(b"id"));
+    }
+
+    #[test]
+    fn unsupported_version() {
+        let error = assert_err!(Config::from_portable(&[0x02]));
+
+        assert_eq!(format!("{error:?}"), "UnsupportedVersion(2)");
+        assert_eq!(
+            format!("{error}"),
+            "unsupported configuration format version 2"
+        );
+    }
+
+    #[test]
+    fn empty() {
+        let error = assert_err!(Config::from_portable(&[]));
+
+        assert_eq!(format!("{error:?}"), "Empty");
+    }
+
+    #[test]
+    fn truncated() {
+        let error = assert_err!(Config::from_portable(&[0x01, 0x03, 0x04, b'a']));
+
+        assert_eq!(format!("{error:?}"), "Truncated");
+    }
+
+    #[test]
+    fn oversized_string() {
+        let mut bytes = [b'a'; 14];
+        bytes[..3].copy_from_slice(&[0x01, 0x03, 0x0b]);
+
+        let error = assert_err!(Config::from_portable(&bytes));
+        assert_eq!(format!("{error:?}"), "FieldTooLong { tag: 3, length: 11 }");
+    }
+
+    #[test]
+    fn invalid_dns_length() {
+        let error = assert_err!(Config::from_portable(&[0x01, 0x01, 0x03, 1, 2, 3]));
+
+        assert_eq!(format!("{error:?}"), "FieldLength { tag: 1, length: 3 }");
+    }
+
+    #[test]
+    fn invalid_digit() {
+        let error = assert_err!(Config::from_portable(&[0x01, 0x10, 0x01, b'a']));
+
+        assert_eq!(format!("{error:?}"), "InvalidDigit(97)");
+    }
+}
@@ -1,7 +1,68 @@
 use super::PhoneNumber;
+use crate::digit::phone_number;
 
+/// One of the adapter's configuration slots.
 #[derive(Clone, Debug, Default)]
 pub struct Slot {
-    pub phone_number: PhoneNumber,
-    pub id: [u8; 16],
+    pub(super) phone_number: PhoneNumber,
+    pub(super) id: [u8; 16],
+}
+
+impl Slot {
+    /// The most digits of a phone number the adapter has room to store in a slot.
+    pub const MAX_DIGITS: usize = 16;
+
+    /// Creates a slot holding `phone_number` and `id`.
+    ///
+    /// Returns an error if `phone_number` has more than [`MAX_DIGITS`](Self::MAX_DIGITS) digits,
+    /// since the adapter would only store part of it.
+    pub fn new(phone_number: PhoneNumber, id: [u8; 16]) -> Result<Self, phone_number::Error> {
+        let length = phone_number.len();
+        if length > Self::MAX_DIGITS {
+            Err(phone_number::Error::too_long(length, Self::MAX_DIGITS))
+        } else {
+            Ok(Self { phone_number, id })
+        }
+    }
+
+    /// The phone number stored in this slot.
+    pub fn phone_number(&self) -> &PhoneNumber {
+        &self.phone_number
+    }
+
+    /// The ID stored in this slot.
+    pub fn id(&self) -> &[u8; 16] {
+        &self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PhoneNumber, Slot};
+    #[cfg(not(feature = "phone-digits-16"))]
+    use alloc::format;
+    #[cfg(not(feature = "phone-digits-16"))]
+    use claims::assert_err;
+    use claims::assert_ok;
+    use gba_test::test;
+
+    #[test]
+    fn new_max_digits() {
+        let slot = assert_ok!(Slot::new(PhoneNumber::new("0123456789#*0123"), [0; 16]));
+
+        assert_eq!(slot.phone_number(), &PhoneNumber::new("0123456789#*0123"));
+    }
+
+    // With `phone-digits-16`, no phone number is too long for a slot.
+    #[cfg(not(feature = "phone-digits-16"))]
+    #[test]
+    fn new_too_long() {
+        let error = assert_err!(Slot::new(PhoneNumber::new("0123456789#*01234"), [0; 16]));
+
+        assert_eq!(format!("{error:?}"), "TooLong { length: 17, max: 16 }");
+        assert_eq!(
+            format!("{error}"),
+            "phone number has 17 digits, but at most 16 are supported"
+        );
+    }
 }
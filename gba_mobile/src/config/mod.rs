@@ -23,6 +23,8 @@ pub(crate) trait Sealed: Sized {
         Socket2: socket::Slot,
         Dns: dns::Sealed;
     type Flow: ConfigSubFlow<Self>;
+
+    fn needs_initialization(&self) -> bool;
 }
 
 #[allow(private_bounds)]
@@ -39,6 +41,10 @@ impl Sealed for NoConfig {
         Socket2: socket::Slot,
         Dns: dns::Sealed;
     type Flow = flow::Empty;
+
+    fn needs_initialization(&self) -> bool {
+        false
+    }
 }
 
 impl Mode for NoConfig {}
@@ -71,6 +77,12 @@ where
     Format: self::Format,
 {
     pub(crate) data: Data<Format>,
+    /// Whether the adapter's configuration was read as uninitialized and has not yet been
+    /// overwritten.
+    ///
+    /// This is tracked separately from `data`, since `data` holds a requested write as soon as it
+    /// is made, before the adapter has accepted it.
+    pub(crate) uninitialized: bool,
 }
 
 impl<Format> Config<Format>
@@ -81,8 +93,28 @@ where
         Self {
             // This value will be initialized upon linking with the adapter.
             data: Data::Segments(segments),
+            uninitialized: false,
+        }
+    }
+
+    /// Stores the result of reading the adapter's configuration.
+    pub(crate) fn read(&mut self, result: Result<Format, Format::Error>) {
+        match result {
+            Ok(format) => {
+                self.uninitialized = false;
+                self.data = Data::Config(format);
+            }
+            Err(error) => {
+                self.uninitialized = Format::uninitialized(&error);
+                self.data = Data::Error(error);
+            }
         }
     }
+
+    /// Records that the adapter has accepted a full configuration write.
+    pub(crate) fn written(&mut self) {
+        self.uninitialized = false;
+    }
 }
 
 impl<Format> Debug for Config<Format>
@@ -94,6 +126,7 @@ where
         formatter
             .debug_struct("Config")
             .field("data", &self.data)
+            .field("uninitialized", &self.uninitialized)
             .finish()
     }
 }
@@ -109,6 +142,10 @@ where
         Socket2: socket::Slot,
         Dns: dns::Sealed;
     type Flow = ConfigFlow<Format>;
+
+    fn needs_initialization(&self) -> bool {
+        self.uninitialized
+    }
 }
 
 impl<Format> Mode for Config<Format> where Format: self::Format {}
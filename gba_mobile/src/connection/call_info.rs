@@ -0,0 +1,61 @@
+use crate::{ArrayVec, Digit, digit};
+
+/// The telephone network a call is carried over.
+///
+/// This is determined by the adapter that made or accepted the call.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub enum Bearer {
+    /// Personal Digital Cellular, used by the Blue adapter.
+    Pdc = 0,
+    /// Personal Handy-phone System, used by the Green and Red adapters.
+    Phs = 1,
+    /// cdmaOne, used by the Yellow adapter.
+    CdmaOne = 2,
+}
+
+/// Information about an established peer-to-peer call.
+#[derive(Clone, Debug)]
+pub struct CallInfo {
+    remote_number: Option<digit::NumberBuf>,
+    bearer: Bearer,
+    inbound: bool,
+}
+
+impl CallInfo {
+    /// A call that was established by dialing `remote_number`.
+    pub(crate) fn outbound(remote_number: digit::NumberBuf, bearer: Bearer) -> Self {
+        Self {
+            remote_number: Some(remote_number),
+            bearer,
+            inbound: false,
+        }
+    }
+
+    /// A call that was established by accepting an incoming call.
+    pub(crate) fn inbound(bearer: Bearer) -> Self {
+        Self {
+            remote_number: None,
+            bearer,
+            inbound: true,
+        }
+    }
+
+    /// Whether the call was established by accepting an incoming call.
+    pub fn is_inbound(&self) -> bool {
+        self.inbound
+    }
+
+    /// The telephone network the call is carried over.
+    pub fn bearer(&self) -> Bearer {
+        self.bearer
+    }
+
+    /// The number of the remote device, if known.
+    ///
+    /// The adapter does not report the caller's number when accepting a call, so this is only
+    /// available for calls that were dialed.
+    pub fn remote_number(&self) -> Option<&[Digit]> {
+        self.remote_number.as_ref().map(ArrayVec::as_slice)
+    }
+}
@@ -0,0 +1,14 @@
+/// How the most recent call hung up by this side ended.
+///
+/// This is returned by [`Link::disconnect_reason()`](crate::Link::disconnect_reason) once the
+/// hang-up has completed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DisconnectReason {
+    /// The adapter hung up the call.
+    Local,
+    /// The call had already ended by the time it was hung up, because the other side hung up
+    /// first or at the same time.
+    ///
+    /// This is still a successful hang-up.
+    MutualOrRemoteFirst,
+}
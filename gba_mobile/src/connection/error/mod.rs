@@ -16,6 +16,37 @@ where
     internal: driver::error::connection::Error<Socket1, Socket2, Dns, Config>,
 }
 
+impl<Socket1, Socket2, Dns, Config> P2p<Socket1, Socket2, Dns, Config>
+where
+    Socket1: socket::Slot,
+    Socket2: socket::Slot,
+    Dns: dns::Mode,
+    Config: config::Mode,
+{
+    /// Whether the call failed because the adapter stopped responding in time.
+    pub fn is_timeout(&self) -> bool {
+        self.internal.is_timeout()
+    }
+
+    /// Whether the call failed because the adapter itself reported an error.
+    ///
+    /// This includes the adapter responding to a request with an error, as well as it refusing a
+    /// packet every time it was sent.
+    pub fn is_adapter_error(&self) -> bool {
+        self.internal.is_adapter_error()
+    }
+
+    /// Whether the call has already ended, or the link it was made through has.
+    pub fn is_closed(&self) -> bool {
+        self.internal.is_closed()
+    }
+
+    /// Whether the call was replaced by a newer connection, or its link by a newer link.
+    pub fn is_superseded(&self) -> bool {
+        self.internal.is_superseded()
+    }
+}
+
 impl<Socket1, Socket2, Dns, Config> Debug for P2p<Socket1, Socket2, Dns, Config>
 where
     Socket1: socket::Slot,
@@ -91,6 +122,38 @@ where
     internal: driver::error::socket::Error<Socket1, Socket2, Dns, Config>,
 }
 
+impl<Socket1, Socket2, Dns, Config> Socket<Socket1, Socket2, Dns, Config>
+where
+    Socket1: socket::Slot,
+    Socket2: socket::Slot,
+    Dns: dns::Mode,
+    Config: config::Mode,
+{
+    /// Whether the socket failed because the adapter stopped responding in time.
+    pub fn is_timeout(&self) -> bool {
+        self.internal.is_timeout()
+    }
+
+    /// Whether the socket failed because the adapter itself reported an error.
+    ///
+    /// This includes the adapter responding to a request with an error, as well as it refusing a
+    /// packet every time it was sent.
+    pub fn is_adapter_error(&self) -> bool {
+        self.internal.is_adapter_error()
+    }
+
+    /// Whether the socket has already been closed, by either side, or the connection it was opened
+    /// over has ended.
+    pub fn is_closed(&self) -> bool {
+        self.internal.is_closed()
+    }
+
+    /// Whether the socket, or the connection it was opened over, was replaced by a newer one.
+    pub fn is_superseded(&self) -> bool {
+        self.internal.is_superseded()
+    }
+}
+
 impl<Socket1, Socket2, Dns, Config> Debug for Socket<Socket1, Socket2, Dns, Config>
 where
     Socket1: socket::Slot,
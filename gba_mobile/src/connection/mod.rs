@@ -1,10 +1,21 @@
 pub mod error;
 
+mod call_info;
+mod disconnect_reason;
 mod pending;
 
+pub use call_info::{Bearer, CallInfo};
+pub use disconnect_reason::DisconnectReason;
+
 use crate::{Driver, Generation, Socket, config, dns, socket};
 use core::marker::PhantomData;
 
+/// A peer-to-peer call with another Mobile Adapter.
+///
+/// Bytes written to a peer-to-peer connection are delivered to the other side exactly as written;
+/// this crate does not add any framing of its own. This keeps calls with peers that are not using
+/// this crate possible, but it also means that any versioning of the data exchanged must be handled
+/// by the game's own protocol.
 #[derive(Clone, Copy, Debug)]
 pub struct P2p;
 
@@ -41,6 +52,14 @@ where
             .map_err(Into::into)
     }
 
+    /// Queues data to be sent to the peer.
+    ///
+    /// Written data is collected until the write buffer fills, the connection is flushed, or the
+    /// next periodic transfer occurs, and is then sent in a single packet. Many small writes
+    /// therefore do not each pay the cost of a separate packet.
+    ///
+    /// Returns the number of bytes accepted, which may be less than `buf.len()` if the write buffer
+    /// is full.
     pub fn write(
         &mut self,
         driver: &mut Driver<Socket<Buffer>, Socket2, Dns, Config>,
@@ -52,6 +71,8 @@ where
             .map_err(Into::into)
     }
 
+    /// Sends all written data with the next packet, rather than waiting for the next periodic
+    /// transfer.
     pub fn flush(
         &mut self,
         driver: &mut Driver<Socket<Buffer>, Socket2, Dns, Config>,
@@ -71,6 +92,49 @@ where
             .disconnect(self.connection_generation)
             .map_err(Into::into)
     }
+
+    /// Returns information about the established call.
+    pub fn info<'a>(
+        &self,
+        driver: &'a Driver<Socket<Buffer>, Socket2, Dns, Config>,
+    ) -> Result<&'a CallInfo, error::P2p<Socket<Buffer>, Socket2, Dns, Config>> {
+        driver
+            .as_active(self.link_generation)?
+            .connection_info(self.connection_generation)
+            .map_err(Into::into)
+    }
+
+    /// Returns the number of frames since data was last received from the peer.
+    ///
+    /// The adapters keep the call up on their own, so a call can stay connected even after the
+    /// game on the other end has stopped responding. Only data the peer actually writes resets
+    /// this count, allowing a game to decide for itself when the peer should be considered hung.
+    ///
+    /// Data is only received while reading from or polling the connection, which happens at least
+    /// once per second.
+    pub fn frames_since_peer_data(
+        &self,
+        driver: &Driver<Socket<Buffer>, Socket2, Dns, Config>,
+    ) -> Result<u16, error::P2p<Socket<Buffer>, Socket2, Dns, Config>> {
+        driver
+            .as_active(self.link_generation)?
+            .peer_quiet_frames(self.connection_generation)
+            .map_err(Into::into)
+    }
+
+    /// Returns the number of frames since the call was established.
+    ///
+    /// Games can use this to display how long a call has lasted. It is counted on every vblank,
+    /// regardless of whether any data is being exchanged.
+    pub fn call_frames(
+        &self,
+        driver: &Driver<Socket<Buffer>, Socket2, Dns, Config>,
+    ) -> Result<u32, error::P2p<Socket<Buffer>, Socket2, Dns, Config>> {
+        driver
+            .as_active(self.link_generation)?
+            .call_frames(self.connection_generation)
+            .map_err(Into::into)
+    }
 }
 
 impl<Buffer, Socket2, Dns, Config> Connection<Driver<Socket<Buffer>, Socket2, Dns, Config>, Socket1>
@@ -1,16 +1,34 @@
 mod into_digits;
 mod pair;
+pub mod phone_number;
 
 pub use into_digits::IntoDigits;
+pub use phone_number::PhoneNumber;
 
 pub(crate) use pair::Pair;
 
+use crate::ArrayVec;
 use core::{
     fmt::{self, Debug, Display, Formatter, Write},
     hint::unreachable_unchecked,
 };
 use deranged::RangedU8;
 
+/// The maximum number of digits in a phone number that can be dialed.
+///
+/// This is 32 by default. Enabling the `phone-digits-16` feature lowers it to 16, which is enough
+/// for most real numbers and reduces the size of the [`Driver`](crate::Driver).
+#[cfg(not(feature = "phone-digits-16"))]
+pub const MAX_DIGITS: usize = 32;
+/// The maximum number of digits in a phone number that can be dialed.
+///
+/// This is 16, as selected by the `phone-digits-16` feature.
+#[cfg(feature = "phone-digits-16")]
+pub const MAX_DIGITS: usize = 16;
+
+/// Storage for the digits of a phone number to be dialed.
+pub(crate) type NumberBuf = ArrayVec<Digit, MAX_DIGITS>;
+
 #[derive(Clone, Copy, Debug)]
 pub struct Invalid<T>(pub T);
 
@@ -39,6 +57,20 @@ impl Digit {
         Self(value)
     }
 
+    /// Maps an ASCII digit, `#`, or `*` to its digit.
+    const fn from_ascii(byte: u8) -> Option<Self> {
+        let value = match byte {
+            b'0'..=b'9' => byte - b'0',
+            b'#' => 0xa,
+            b'*' => 0xb,
+            _ => return None,
+        };
+        match RangedU8::new(value) {
+            Some(value) => Some(Self(value)),
+            None => None,
+        }
+    }
+
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
         formatter.write_char((*self).into())
     }
@@ -60,21 +92,10 @@ impl TryFrom<char> for Digit {
     type Error = Invalid<char>;
 
     fn try_from(c: char) -> Result<Self, Self::Error> {
-        match c {
-            '0' => Ok(Self(RangedU8::new_static::<0x0>())),
-            '1' => Ok(Self(RangedU8::new_static::<0x1>())),
-            '2' => Ok(Self(RangedU8::new_static::<0x2>())),
-            '3' => Ok(Self(RangedU8::new_static::<0x3>())),
-            '4' => Ok(Self(RangedU8::new_static::<0x4>())),
-            '5' => Ok(Self(RangedU8::new_static::<0x5>())),
-            '6' => Ok(Self(RangedU8::new_static::<0x6>())),
-            '7' => Ok(Self(RangedU8::new_static::<0x7>())),
-            '8' => Ok(Self(RangedU8::new_static::<0x8>())),
-            '9' => Ok(Self(RangedU8::new_static::<0x9>())),
-            '#' => Ok(Self(RangedU8::new_static::<0xa>())),
-            '*' => Ok(Self(RangedU8::new_static::<0xb>())),
-            invalid => Err(Invalid(invalid)),
-        }
+        u8::try_from(c)
+            .ok()
+            .and_then(Self::from_ascii)
+            .ok_or(Invalid(c))
     }
 }
 
@@ -82,21 +103,7 @@ impl TryFrom<u8> for Digit {
     type Error = Invalid<u8>;
 
     fn try_from(b: u8) -> Result<Self, Self::Error> {
-        match b {
-            b'0' => Ok(Self(RangedU8::new_static::<0x0>())),
-            b'1' => Ok(Self(RangedU8::new_static::<0x1>())),
-            b'2' => Ok(Self(RangedU8::new_static::<0x2>())),
-            b'3' => Ok(Self(RangedU8::new_static::<0x3>())),
-            b'4' => Ok(Self(RangedU8::new_static::<0x4>())),
-            b'5' => Ok(Self(RangedU8::new_static::<0x5>())),
-            b'6' => Ok(Self(RangedU8::new_static::<0x6>())),
-            b'7' => Ok(Self(RangedU8::new_static::<0x7>())),
-            b'8' => Ok(Self(RangedU8::new_static::<0x8>())),
-            b'9' => Ok(Self(RangedU8::new_static::<0x9>())),
-            b'#' => Ok(Self(RangedU8::new_static::<0xa>())),
-            b'*' => Ok(Self(RangedU8::new_static::<0xb>())),
-            invalid => Err(Invalid(invalid)),
-        }
+        Self::from_ascii(b).ok_or(Invalid(b))
     }
 }
 
@@ -144,7 +151,7 @@ impl From<Digit> for u8 {
 mod tests {
     use super::Digit;
     use alloc::format;
-    use claims::assert_ok;
+    use claims::{assert_err, assert_ok};
     use gba_test::test;
 
     #[test]
@@ -178,4 +185,21 @@ mod tests {
         assert_eq!(format!("{:?}", assert_ok!(Digit::try_from('#'))), "#");
         assert_eq!(format!("{:?}", assert_ok!(Digit::try_from('*'))), "*");
     }
+
+    #[test]
+    fn digit_invalid_char() {
+        assert_eq!(
+            format!("{}", assert_err!(Digit::try_from('-'))),
+            "- is not a valid digit"
+        );
+        assert_err!(Digit::try_from('٣'));
+    }
+
+    #[test]
+    fn digit_invalid_byte() {
+        assert_eq!(
+            format!("{}", assert_err!(Digit::try_from(b'a'))),
+            "97 is not a valid digit"
+        );
+    }
 }
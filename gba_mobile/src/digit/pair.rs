@@ -19,7 +19,7 @@ impl Pair {
         Self(0xff)
     }
 
-    pub(crate) fn from_raw(byte: u8) -> Self {
+    pub(crate) const fn from_raw(byte: u8) -> Self {
         Self(byte)
     }
 
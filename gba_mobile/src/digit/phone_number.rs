@@ -0,0 +1,544 @@
+//! Phone numbers that can be dialed or stored in a configuration.
+
+use super::{Digit, IntoDigits, MAX_DIGITS, NumberBuf, Pair};
+use core::{
+    fmt,
+    fmt::{Debug, Display, Formatter},
+    iter, slice,
+};
+
+/// A phone number of at most [`MAX_DIGITS`] digits.
+///
+/// This is built from a string, either at runtime using [`TryFrom`] or at compile time using
+/// [`PhoneNumber::new`], which allows a fixed number, such as that of a relay server, to be
+/// declared as a `const` without any validation at runtime.
+///
+/// The digits are packed two to a byte, in the same format the adapter uses to store the phone
+/// numbers of its configuration slots. An unused slot holds an [`empty`](PhoneNumber::empty)
+/// number, which can't be dialed.
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct PhoneNumber {
+    pairs: [Pair; MAX_DIGITS / 2],
+}
+
+impl PhoneNumber {
+    /// Creates a phone number from a string of ASCII digits, `#`, and `*`.
+    ///
+    /// # Panics
+    /// Panics if the number is empty, longer than [`MAX_DIGITS`], or contains any other
+    /// character. When used to initialize a `const`, this is reported as a compile error instead.
+    pub const fn new(number: &str) -> Self {
+        match Self::parse(number.as_bytes()) {
+            Ok(phone_number) => phone_number,
+            Err(Error { kind: Kind::Empty }) => panic!("phone number is empty"),
+            Err(Error {
+                kind: Kind::TooLong { .. },
+            }) => panic!("phone number is too long"),
+            Err(Error {
+                kind: Kind::InvalidCharacter { .. },
+            }) => panic!("phone number contains an invalid character"),
+        }
+    }
+
+    /// Creates a phone number with no digits.
+    pub const fn empty() -> Self {
+        Self {
+            pairs: [Pair::new(); MAX_DIGITS / 2],
+        }
+    }
+
+    const fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.is_empty() {
+            return Err(Error { kind: Kind::Empty });
+        }
+        if bytes.len() > MAX_DIGITS {
+            return Err(Error::too_long(bytes.len(), MAX_DIGITS));
+        }
+
+        let mut raw = [0xff; MAX_DIGITS / 2];
+        let mut index = 0;
+        while index < bytes.len() {
+            let byte = bytes[index];
+            let value = match Digit::from_ascii(byte) {
+                Some(digit) => digit.0.get(),
+                None => {
+                    return Err(Error {
+                        kind: Kind::InvalidCharacter { index, byte },
+                    });
+                }
+            };
+            // The first digit of a pair is stored in the low halfbyte.
+            raw[index / 2] = if index % 2 == 0 {
+                (raw[index / 2] & 0xf0) | value
+            } else {
+                (raw[index / 2] & 0x0f) | (value << 4)
+            };
+            index += 1;
+        }
+
+        let mut phone_number = Self::empty();
+        let mut index = 0;
+        while index < raw.len() {
+            phone_number.pairs[index] = Pair::from_raw(raw[index]);
+            index += 1;
+        }
+        Ok(phone_number)
+    }
+
+    /// Collects `digits` into a phone number.
+    ///
+    /// No digits results in an [`empty`](PhoneNumber::empty) number. Returns an error if there are
+    /// more than [`MAX_DIGITS`] digits.
+    pub fn from_digits<Digits>(digits: Digits) -> Result<Self, Error>
+    where
+        Digits: IntoIterator<Item = Digit>,
+    {
+        let mut iter = digits.into_iter();
+        let mut phone_number = Self::empty();
+
+        for pair in &mut phone_number.pairs {
+            let first = iter.next();
+            // Only get a second digit if the first digit was `Some`.
+            let second = first.and_then(|_| iter.next());
+
+            *pair = Pair::from_digits([first, second]);
+
+            if second.is_none() {
+                // The number ended within this pair.
+                return Ok(phone_number);
+            }
+        }
+
+        match iter.count() {
+            0 => Ok(phone_number),
+            extra => Err(Error::too_long(MAX_DIGITS + extra, MAX_DIGITS)),
+        }
+    }
+
+    /// Reads a phone number stored in a configuration slot, which has room for 16 digits.
+    pub(crate) fn from_raw_bytes(bytes: [u8; 8]) -> Self {
+        let mut phone_number = Self::empty();
+        for (pair, byte) in phone_number.pairs.iter_mut().zip(bytes) {
+            *pair = Pair::from_raw(byte);
+        }
+        phone_number
+    }
+
+    /// The first 16 digits of this phone number, as stored in a configuration slot.
+    ///
+    /// [`Slot`](crate::config::mobile_system_gb::Slot) only accepts phone numbers that fit, so no
+    /// digits of a slot's phone number are ever left out.
+    pub(crate) fn as_raw_bytes(&self) -> &[u8; 8] {
+        // SAFETY: `Pair` is a transparent wrapper around a `u8`, and there are always at least 8
+        // pairs.
+        unsafe { &*self.pairs.as_ptr().cast::<[u8; 8]>() }
+    }
+
+    /// Returns the number of digits in this phone number.
+    pub fn len(&self) -> usize {
+        self.into_iter().count()
+    }
+
+    /// Returns whether this phone number has no digits.
+    pub fn is_empty(&self) -> bool {
+        self.into_iter().next().is_none()
+    }
+
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        for digit in self {
+            write!(formatter, "{digit}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Debug for PhoneNumber {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        self.fmt(formatter)
+    }
+}
+
+impl Display for PhoneNumber {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        self.fmt(formatter)
+    }
+}
+
+impl Default for PhoneNumber {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl TryFrom<&[u8]> for PhoneNumber {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Self::parse(bytes)
+    }
+}
+
+impl TryFrom<&str> for PhoneNumber {
+    type Error = Error;
+
+    fn try_from(number: &str) -> Result<Self, Self::Error> {
+        Self::parse(number.as_bytes())
+    }
+}
+
+impl<'a> IntoIterator for &'a PhoneNumber {
+    type Item = Digit;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Iter::new(self.pairs.as_slice())
+    }
+}
+
+impl<'a> IntoDigits for &'a PhoneNumber {
+    type Digits = Iter<'a>;
+
+    fn into_digits(self) -> Self::Digits {
+        self.into_iter()
+    }
+}
+
+/// An iterator over the digits of a [`PhoneNumber`].
+pub struct Iter<'a> {
+    digit_pairs: iter::Copied<slice::Iter<'a, Pair>>,
+    next_digit: Option<Option<Digit>>,
+    terminated: bool,
+}
+
+impl<'a> Iter<'a> {
+    fn new(digit_pairs: &'a [Pair]) -> Self {
+        Self {
+            digit_pairs: digit_pairs.iter().copied(),
+            next_digit: None,
+            terminated: false,
+        }
+    }
+}
+
+impl Iterator for Iter<'_> {
+    type Item = Digit;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.terminated {
+            None
+        } else if let Some(digit) = self.next_digit.take() {
+            if digit.is_none() {
+                self.terminated = true;
+            }
+            digit
+        } else if let Some(pair) = self.digit_pairs.next() {
+            let [first, second] = pair.to_digits();
+            if first.is_none() {
+                self.terminated = true;
+            } else {
+                self.next_digit = Some(second);
+            }
+            first
+        } else {
+            self.terminated = true;
+            None
+        }
+    }
+}
+
+/// Collects the digits of a phone number to be dialed.
+///
+/// All public APIs that dial a number go through here, ensuring that no request to dial an
+/// invalid number is ever scheduled.
+pub(crate) fn collect<Number>(phone_number: Number) -> Result<NumberBuf, Error>
+where
+    Number: IntoDigits,
+{
+    let mut digits = NumberBuf::new();
+    let mut iter = phone_number.into_digits();
+    for digit in iter.by_ref() {
+        if digits.try_push(digit).is_err() {
+            return Err(Error::too_long(MAX_DIGITS + 1 + iter.count(), MAX_DIGITS));
+        }
+    }
+    if digits.is_empty() {
+        return Err(Error { kind: Kind::Empty });
+    }
+    Ok(digits)
+}
+
+/// An error encountered while creating, dialing, or storing a [`PhoneNumber`].
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct Error {
+    kind: Kind,
+}
+
+impl Error {
+    /// A phone number of `length` digits where at most `max` are supported.
+    pub(crate) const fn too_long(length: usize, max: usize) -> Self {
+        Self {
+            kind: Kind::TooLong { length, max },
+        }
+    }
+}
+
+impl Debug for Error {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        Debug::fmt(&self.kind, formatter)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        Display::fmt(&self.kind, formatter)
+    }
+}
+
+impl core::error::Error for Error {}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Kind {
+    Empty,
+    TooLong { length: usize, max: usize },
+    InvalidCharacter { index: usize, byte: u8 },
+}
+
+impl Display for Kind {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Empty => formatter.write_str("phone number is empty"),
+            Self::TooLong { length, max } => write!(
+                formatter,
+                "phone number has {length} digits, but at most {max} are supported"
+            ),
+            Self::InvalidCharacter { index, byte } => write!(
+                formatter,
+                "phone number has invalid character '{}' at index {index}",
+                byte.escape_ascii()
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::NumberBuf, MAX_DIGITS, PhoneNumber, collect};
+    use crate::Digit;
+    use alloc::{format, vec::Vec};
+    use claims::{assert_err, assert_none, assert_ok, assert_some_eq};
+    use core::mem::size_of;
+    use gba_test::test;
+
+    fn digits<const N: usize>() -> [Digit; N] {
+        [assert_ok!(Digit::try_from('7')); N]
+    }
+
+    #[test]
+    fn collect_valid() {
+        let number = digits::<10>();
+
+        assert_eq!(
+            assert_ok!(collect(number.as_slice())).as_slice(),
+            number.as_slice()
+        );
+    }
+
+    #[test]
+    fn collect_max_length() {
+        assert_eq!(
+            assert_ok!(collect(digits::<MAX_DIGITS>().as_slice())).len() as usize,
+            MAX_DIGITS
+        );
+    }
+
+    #[test]
+    fn collect_empty() {
+        let error = assert_err!(collect(digits::<0>().as_slice()));
+
+        assert_eq!(format!("{error:?}"), "Empty");
+        assert_eq!(format!("{error}"), "phone number is empty");
+    }
+
+    #[test]
+    fn collect_too_long() {
+        let error = assert_err!(collect(digits::<{ MAX_DIGITS + 1 }>().as_slice()));
+
+        assert_eq!(
+            format!("{error:?}"),
+            format!(
+                "TooLong {{ length: {}, max: {MAX_DIGITS} }}",
+                MAX_DIGITS + 1
+            )
+        );
+    }
+
+    #[test]
+    fn number_buf_size() {
+        assert_eq!(size_of::<NumberBuf>(), MAX_DIGITS + 1);
+    }
+
+    #[cfg(feature = "phone-digits-16")]
+    #[test]
+    fn collect_too_long_for_16() {
+        assert_err!(collect(digits::<17>().as_slice()));
+    }
+
+    #[test]
+    fn parse_special_digits() {
+        let number = assert_ok!(PhoneNumber::try_from("#31*0"));
+
+        assert_eq!(format!("{number}"), "#31*0");
+        assert_eq!(
+            assert_ok!(collect(&number)).as_slice(),
+            number.into_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn parse_bytes() {
+        let number = assert_ok!(PhoneNumber::try_from(b"0755".as_slice()));
+
+        assert_eq!(format!("{number:?}"), "0755");
+    }
+
+    #[test]
+    fn parse_const() {
+        const NUMBER: PhoneNumber = PhoneNumber::new("0077487751");
+
+        assert_eq!(format!("{NUMBER}"), "0077487751");
+    }
+
+    #[test]
+    fn parse_empty() {
+        let error = assert_err!(PhoneNumber::try_from(""));
+
+        assert_eq!(format!("{error:?}"), "Empty");
+        assert_eq!(format!("{error}"), "phone number is empty");
+    }
+
+    #[test]
+    fn parse_max_length() {
+        let number = [b'1'; MAX_DIGITS];
+
+        assert_eq!(
+            assert_ok!(PhoneNumber::try_from(number.as_slice()))
+                .into_iter()
+                .count(),
+            MAX_DIGITS
+        );
+    }
+
+    #[test]
+    fn parse_too_long() {
+        let number = [b'1'; MAX_DIGITS + 1];
+        let error = assert_err!(PhoneNumber::try_from(number.as_slice()));
+
+        assert_eq!(
+            format!("{error:?}"),
+            format!(
+                "TooLong {{ length: {}, max: {MAX_DIGITS} }}",
+                MAX_DIGITS + 1
+            )
+        );
+        assert_eq!(
+            format!("{error}"),
+            format!(
+                "phone number has {} digits, but at most {MAX_DIGITS} are supported",
+                MAX_DIGITS + 1
+            )
+        );
+    }
+
+    #[test]
+    fn parse_invalid_character() {
+        let error = assert_err!(PhoneNumber::try_from("075-5"));
+
+        assert_eq!(
+            format!("{error:?}"),
+            "InvalidCharacter { index: 3, byte: 45 }"
+        );
+        assert_eq!(
+            format!("{error}"),
+            "phone number has invalid character '-' at index 3"
+        );
+    }
+
+    #[test]
+    fn parse_odd_length() {
+        let number = assert_ok!(PhoneNumber::try_from("123"));
+
+        assert_eq!(
+            number.as_raw_bytes(),
+            &[0x21, 0xf3, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]
+        );
+    }
+
+    #[test]
+    fn len() {
+        assert_eq!(PhoneNumber::new("#31*0").len(), 5);
+        assert_eq!(PhoneNumber::empty().len(), 0);
+    }
+
+    #[test]
+    fn empty() {
+        let number = PhoneNumber::empty();
+
+        assert!(number.is_empty());
+        assert_eq!(format!("{number}"), "");
+        assert_err!(collect(&number));
+    }
+
+    #[test]
+    fn raw_bytes_round_trip() {
+        let number = assert_ok!(PhoneNumber::try_from("0123456789#*0123"));
+
+        assert_eq!(PhoneNumber::from_raw_bytes(*number.as_raw_bytes()), number);
+    }
+
+    #[test]
+    fn from_digits() {
+        let number = assert_ok!(PhoneNumber::from_digits(
+            "8675309".chars().map(|c| assert_ok!(Digit::try_from(c)))
+        ));
+
+        assert_eq!(number, PhoneNumber::new("8675309"));
+    }
+
+    #[test]
+    fn from_digits_empty() {
+        assert!(assert_ok!(PhoneNumber::from_digits(digits::<0>())).is_empty());
+    }
+
+    #[test]
+    fn from_digits_max_length() {
+        assert_eq!(
+            assert_ok!(PhoneNumber::from_digits(digits::<MAX_DIGITS>())).len(),
+            MAX_DIGITS
+        );
+    }
+
+    #[test]
+    fn from_digits_too_long() {
+        let error = assert_err!(PhoneNumber::from_digits(digits::<{ MAX_DIGITS + 2 }>()));
+
+        assert_eq!(
+            format!("{error:?}"),
+            format!(
+                "TooLong {{ length: {}, max: {MAX_DIGITS} }}",
+                MAX_DIGITS + 2
+            )
+        );
+    }
+
+    #[test]
+    fn iter() {
+        let number = PhoneNumber::new("1234");
+        let mut iter = number.into_iter();
+
+        assert_some_eq!(iter.next(), assert_ok!(Digit::try_from('1')));
+        assert_some_eq!(iter.next(), assert_ok!(Digit::try_from('2')));
+        assert_some_eq!(iter.next(), assert_ok!(Digit::try_from('3')));
+        assert_some_eq!(iter.next(), assert_ok!(Digit::try_from('4')));
+        assert_none!(iter.next());
+    }
+}
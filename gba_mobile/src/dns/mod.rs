@@ -1,3 +1,5 @@
+pub mod name;
+
 mod error;
 mod pending;
 mod to_name;
@@ -0,0 +1,112 @@
+//! Domain names that can be looked up.
+
+use crate::ArrayVec;
+use core::{
+    fmt,
+    fmt::{Debug, Display, Formatter},
+};
+
+/// Collects the bytes of a domain name to be looked up.
+///
+/// All public APIs that look up a name go through here, ensuring that no request to look up an
+/// invalid name is ever scheduled. The capacity is never more than the 255 bytes a single packet
+/// holds.
+pub(crate) fn collect<const CAPACITY: usize>(name: &[u8]) -> Result<ArrayVec<u8, CAPACITY>, Error> {
+    if name.is_empty() {
+        return Err(Error { kind: Kind::Empty });
+    }
+    if name.len() > CAPACITY {
+        return Err(Error {
+            kind: Kind::TooLong {
+                length: name.len(),
+                max: CAPACITY,
+            },
+        });
+    }
+    // SAFETY: The name was just checked to fit within `CAPACITY`.
+    Ok(unsafe { ArrayVec::try_from_iter(name.iter().copied()).unwrap_unchecked() })
+}
+
+/// An error encountered while looking up a domain name.
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct Error {
+    kind: Kind,
+}
+
+impl Debug for Error {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        Debug::fmt(&self.kind, formatter)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        Display::fmt(&self.kind, formatter)
+    }
+}
+
+impl core::error::Error for Error {}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Kind {
+    Empty,
+    TooLong { length: usize, max: usize },
+}
+
+impl Display for Kind {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Empty => formatter.write_str("domain name is empty"),
+            Self::TooLong { length, max } => write!(
+                formatter,
+                "domain name has {length} bytes, but at most {max} can be looked up"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::collect;
+    use alloc::format;
+    use claims::{assert_err, assert_ok};
+    use gba_test::test;
+
+    #[test]
+    fn collect_valid() {
+        assert_eq!(
+            assert_ok!(collect::<16>(b"example.com")).as_slice(),
+            b"example.com"
+        );
+    }
+
+    #[test]
+    fn collect_capacity() {
+        assert_eq!(assert_ok!(collect::<4>(b"a.jp")).len(), 4);
+    }
+
+    #[test]
+    fn collect_empty() {
+        let error = assert_err!(collect::<16>(b""));
+
+        assert_eq!(format!("{error:?}"), "Empty");
+        assert_eq!(format!("{error}"), "domain name is empty");
+    }
+
+    #[test]
+    fn collect_beyond_capacity() {
+        let error = assert_err!(collect::<4>(b"a.com"));
+
+        assert_eq!(format!("{error:?}"), "TooLong { length: 5, max: 4 }");
+    }
+
+    #[test]
+    fn collect_beyond_packet() {
+        let error = assert_err!(collect::<255>(&[b'a'; 256]));
+
+        assert_eq!(
+            format!("{error}"),
+            "domain name has 256 bytes, but at most 255 can be looked up"
+        );
+    }
+}
@@ -2,7 +2,9 @@ use super::{
     super::{ConnectionRequest, Phase},
     request::{Packet, packet, packet::payload},
 };
-use crate::{Socket, Timer, driver::Adapter, mmio::serial::TransferLength, socket};
+use crate::{
+    Socket, Timer, connection::CallInfo, driver::Adapter, mmio::serial::TransferLength, socket,
+};
 use either::Either;
 
 #[derive(Debug)]
@@ -61,7 +63,12 @@ impl Accept {
                                         //
                                         // It is possible to have the phase change during execution of the
                                         // flow, in which case we should not update the phase.
-                                        *phase = Phase::Connected(0);
+                                        *phase = Phase::Connected {
+                                            frame: 0,
+                                            call: CallInfo::inbound(response.adapter.bearer()),
+                                            quiet_frames: 0,
+                                            call_frames: 0,
+                                        };
                                         socket.id = socket::Id::P2P;
                                         socket.frame = 0;
                                     }
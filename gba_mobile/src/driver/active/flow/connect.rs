@@ -3,8 +3,8 @@ use super::{
     request::{Packet, packet, packet::payload},
 };
 use crate::{
-    ArrayVec, Digit, Generation, Socket, Timer, driver::Adapter, mmio::serial::TransferLength,
-    socket,
+    Generation, Socket, Timer, connection::CallInfo, digit, driver::Adapter,
+    mmio::serial::TransferLength, socket,
 };
 use either::Either;
 
@@ -20,7 +20,7 @@ impl Connect {
         timer: Timer,
         packet_data: &mut packet::Data,
         adapter: Adapter,
-        digits: &ArrayVec<Digit, 32>,
+        digits: &digit::NumberBuf,
         connection_generation: Generation,
     ) -> Self {
         Self {
@@ -59,7 +59,7 @@ impl Connect {
                 }),
                 Either::Right(response) => {
                     *adapter = response.adapter;
-                    if matches!(phase, Phase::Connecting(ConnectionRequest::Connect { .. }))
+                    if let Phase::Connecting(ConnectionRequest::Connect { digits }) = phase
                         && connection_generation == self.connection_generation
                     {
                         // Only update the phase if we are currently in the phase of connecting for
@@ -69,7 +69,15 @@ impl Connect {
                         // which case we should not update the phase.
                         match response.payload {
                             payload::connect::Response::Connected => {
-                                *phase = Phase::Connected(0);
+                                *phase = Phase::Connected {
+                                    frame: 0,
+                                    call: CallInfo::outbound(
+                                        digits.clone(),
+                                        response.adapter.bearer(),
+                                    ),
+                                    quiet_frames: 0,
+                                    call_frames: 0,
+                                };
                                 socket.id = socket::Id::P2P;
                                 socket.frame = 0;
                             }
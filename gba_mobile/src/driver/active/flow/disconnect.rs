@@ -1,5 +1,5 @@
 use super::request::{Packet, packet, packet::payload};
-use crate::{Timer, driver::Adapter, mmio::serial::TransferLength};
+use crate::{Timer, connection::DisconnectReason, driver::Adapter, mmio::serial::TransferLength};
 use either::Either;
 
 #[derive(Debug)]
@@ -35,6 +35,7 @@ impl Disconnect {
         timer: Timer,
         adapter: &mut Adapter,
         packet_data: &mut packet::Data,
+        disconnect_reason: &mut Option<DisconnectReason>,
     ) -> Result<Option<Self>, packet::Error<payload::Disconnect>> {
         self.packet
             .serial(timer, packet_data)
@@ -42,6 +43,7 @@ impl Disconnect {
                 Either::Left(packet) => Some(Self { packet }),
                 Either::Right(response) => {
                     *adapter = response.adapter;
+                    *disconnect_reason = Some(response.payload);
                     None
                 }
             })
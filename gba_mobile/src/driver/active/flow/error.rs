@@ -5,12 +5,16 @@ use super::{
 };
 use crate::{
     config,
-    driver::active::flow::{ConfigSubFlow, DnsSubFlow},
+    driver::{
+        active::flow::{ConfigSubFlow, DnsSubFlow},
+        command,
+    },
     socket,
 };
 use core::{
     fmt,
     fmt::{Debug, Display, Formatter},
+    iter,
 };
 
 #[derive(Clone, Debug)]
@@ -130,6 +134,7 @@ where
     Reset(reset::Error),
     Login(login::Error),
     Connection(<Socket1::ConnectionFlow as SocketSubFlow<Socket1>>::Error),
+    Logout(packet::Error<payload::Logout>),
     Disconnect(packet::Error<payload::Disconnect>),
     Socket1(<Socket1::SocketFlow<0> as SocketSubFlow<Socket1>>::Error),
     Socket2(<Socket2::SocketFlow<1> as SocketSubFlow<Socket2>>::Error),
@@ -139,6 +144,26 @@ where
     Idle(idle::Error),
 }
 
+impl<Socket1, Socket2, Dns, Config> Error<Socket1, Socket2, Dns, Config>
+where
+    Socket1: socket::Slot,
+    Socket2: socket::Slot,
+    Dns: crate::dns::Mode,
+    Config: config::Mode,
+{
+    /// Whether the adapter itself reported this error, either by refusing a packet on every
+    /// attempt or by responding to a request with a command error, even one that isn't known.
+    pub(in crate::driver) fn is_adapter_error(&self) -> bool {
+        iter::successors(core::error::Error::source(self), |error| error.source()).any(|error| {
+            error.is::<command::Error>()
+                || error.is::<command::error::Unknown>()
+                || error
+                    .downcast_ref::<packet::error::Send>()
+                    .is_some_and(packet::error::Send::is_adapter_error)
+        })
+    }
+}
+
 impl<Socket1, Socket2, Dns, Config> Clone for Error<Socket1, Socket2, Dns, Config>
 where
     Socket1: socket::Slot,
@@ -153,6 +178,7 @@ where
             Self::Reset(error) => Self::Reset(error.clone()),
             Self::Login(error) => Self::Login(error.clone()),
             Self::Connection(error) => Self::Connection(error.clone()),
+            Self::Logout(error) => Self::Logout(error.clone()),
             Self::Disconnect(error) => Self::Disconnect(error.clone()),
             Self::Socket1(error) => Self::Socket1(error.clone()),
             Self::Socket2(error) => Self::Socket2(error.clone()),
@@ -178,6 +204,7 @@ where
             Self::Reset(error) => formatter.debug_tuple("Reset").field(error).finish(),
             Self::Login(error) => formatter.debug_tuple("Login").field(error).finish(),
             Self::Connection(error) => formatter.debug_tuple("Connection").field(error).finish(),
+            Self::Logout(error) => formatter.debug_tuple("Logout").field(error).finish(),
             Self::Disconnect(error) => formatter.debug_tuple("Disconnect").field(error).finish(),
             Self::Socket1(error) => formatter.debug_tuple("Socket1").field(error).finish(),
             Self::Socket2(error) => formatter.debug_tuple("Socket2").field(error).finish(),
@@ -203,6 +230,7 @@ where
             Self::Reset(_) => formatter.write_str("error during reset"),
             Self::Login(_) => formatter.write_str("error during login"),
             Self::Connection(_) => formatter.write_str("error during connection flow"),
+            Self::Logout(_) => formatter.write_str("error during logout"),
             Self::Disconnect(_) => formatter.write_str("error during disconnect"),
             Self::Socket1(_) => formatter.write_str("error during socket 1 flow"),
             Self::Socket2(_) => formatter.write_str("error during socket 2 flow"),
@@ -228,6 +256,7 @@ where
             Self::Reset(error) => Some(error),
             Self::Login(error) => Some(error),
             Self::Connection(error) => Some(error),
+            Self::Logout(error) => Some(error),
             Self::Disconnect(error) => Some(error),
             Self::Socket1(error) => Some(error),
             Self::Socket2(error) => Some(error),
@@ -8,7 +8,7 @@ use super::{
     super::{ConnectionFailure, ConnectionRequest, Phase},
     request::{Packet, packet, packet::payload},
 };
-use crate::{Adapter, ArrayVec, Digit, Generation, Timer, mmio::serial::TransferLength, socket};
+use crate::{Adapter, Generation, Timer, digit, mmio::serial::TransferLength, socket};
 use either::Either;
 
 #[derive(Debug)]
@@ -29,7 +29,7 @@ impl Login {
         timer: Timer,
         packet_data: &mut packet::Data,
         adapter: Adapter,
-        digits: &ArrayVec<Digit, 32>,
+        digits: &digit::NumberBuf,
         connection_generation: Generation,
     ) -> Self {
         Self::Connect {
@@ -12,6 +12,7 @@ mod end;
 mod error;
 mod idle;
 mod login;
+mod logout;
 mod open_tcp;
 mod open_udp;
 mod read_config;
@@ -27,8 +28,9 @@ pub(in crate::driver) use timeout::Timeout;
 
 use super::{Phase, Queue, State};
 use crate::{
-    ArrayVec, Config, Digit, Generation, Socket, Timer,
+    ArrayVec, Config, Generation, Socket, Timer,
     config::{self, NoConfig},
+    digit,
     dns::NoDns,
     driver::Adapter,
     mmio::serial::TransferLength,
@@ -50,6 +52,7 @@ use either::Either;
 use end::End;
 use idle::Idle;
 use login::Login;
+use logout::Logout;
 use open_tcp::OpenTcp;
 use open_udp::OpenUdp;
 use read_config::ReadConfig;
@@ -463,6 +466,7 @@ where
     Login(Login),
 
     Connection(Socket1::ConnectionFlow),
+    Logout(Logout),
     Disconnect(Disconnect),
 
     Socket1(Socket1::SocketFlow<0>),
@@ -513,7 +517,7 @@ where
         timer: Timer,
         packet_data: &mut packet::Data,
         adapter: Adapter,
-        digits: &ArrayVec<Digit, 32>,
+        digits: &digit::NumberBuf,
         connection_generation: Generation,
     ) -> Self {
         Self::Login(Login::new(
@@ -526,6 +530,14 @@ where
         ))
     }
 
+    pub(super) fn logout(
+        transfer_length: TransferLength,
+        timer: Timer,
+        packet_data: &mut packet::Data,
+    ) -> Self {
+        Self::Logout(Logout::new(transfer_length, timer, packet_data))
+    }
+
     pub(super) fn disconnect(
         transfer_length: TransferLength,
         timer: Timer,
@@ -546,6 +558,25 @@ where
         Self::Idle(Idle::new(transfer_length, timer))
     }
 
+    #[cfg(feature = "debug-overlay")]
+    pub(super) fn name(&self) -> &'static str {
+        match self {
+            Self::Start(_) => "start",
+            Self::End(_) => "end",
+            Self::Reset(_) => "reset",
+            Self::Login(_) => "login",
+            Self::Connection(_) => "connection",
+            Self::Logout(_) => "logout",
+            Self::Disconnect(_) => "disconnect",
+            Self::Socket1(_) => "socket 1",
+            Self::Socket2(_) => "socket 2",
+            Self::Dns(_) => "dns",
+            Self::Config(_) => "config",
+            Self::Status(_) => "status",
+            Self::Idle(_) => "idle",
+        }
+    }
+
     /// Only returns `false` if the active session is being ended.
     pub(super) fn vblank(&mut self) -> Result<bool, Timeout> {
         match self {
@@ -554,6 +585,7 @@ where
             Self::Reset(reset) => reset.vblank().map(|_| true).map_err(Timeout::Reset),
             Self::Login(login) => login.vblank().map(|_| true).map_err(Timeout::Login),
             Self::Connection(connection) => connection.vblank().map(|_| true),
+            Self::Logout(logout) => logout.vblank().map(|_| true).map_err(Timeout::Logout),
             Self::Disconnect(disconnect) => disconnect
                 .vblank()
                 .map(|_| true)
@@ -574,6 +606,7 @@ where
             Self::Reset(reset) => reset.timer(&state.packet_data),
             Self::Login(login) => login.timer(&state.packet_data),
             Self::Connection(connection) => connection.timer(state),
+            Self::Logout(logout) => logout.timer(&state.packet_data),
             Self::Disconnect(disconnect) => disconnect.timer(&state.packet_data),
             Self::Socket1(socket_1) => socket_1.timer(state),
             Self::Socket2(socket_2) => socket_2.timer(state),
@@ -699,8 +732,17 @@ where
                 .serial(state, timer, socket_1)
                 .map(|flow| flow.map(Self::Connection))
                 .map_err(Error::Connection),
-            Self::Disconnect(disconnect) => disconnect
+            Self::Logout(logout) => logout
                 .serial(timer, &mut state.adapter, &mut state.packet_data)
+                .map(|flow| flow.map(Self::Logout))
+                .map_err(Error::Logout),
+            Self::Disconnect(disconnect) => disconnect
+                .serial(
+                    timer,
+                    &mut state.adapter,
+                    &mut state.packet_data,
+                    &mut state.disconnect_reason,
+                )
                 .map(|flow| flow.map(Self::Disconnect))
                 .map_err(Error::Disconnect),
             Self::Socket1(socket) => socket
@@ -725,6 +767,8 @@ where
                     &mut state.adapter,
                     &mut state.packet_data,
                     &mut state.phase,
+                    queue,
+                    &mut state.disconnect_reason,
                 )
                 .map(|flow| flow.map(Self::Status))
                 .map_err(Error::Status),
@@ -760,7 +804,7 @@ where
         timer: Timer,
         packet_data: &mut packet::Data,
         adapter: Adapter,
-        digits: &ArrayVec<Digit, 32>,
+        digits: &digit::NumberBuf,
         connection_generation: Generation,
     ) -> Self {
         Self::Connection(ConnectionFlow::Connect(Connect::new(
@@ -92,12 +92,12 @@ where
                             }
                             Ok(config::format::ReadResult::Success(format)) => {
                                 // Store the data and finish reading.
-                                config.data = config::Data::Config(format);
+                                config.read(Ok(format));
                                 None
                             }
                             Err(error) => {
                                 // Store the error and finish reading.
-                                config.data = config::Data::Error(error);
+                                config.read(Err(error));
                                 None
                             }
                         };
@@ -4,7 +4,7 @@ mod timeout;
 pub(in crate::driver) use error::Error;
 pub(in crate::driver) use timeout::Timeout;
 
-use super::{communication, schedule_serial, schedule_timer};
+use super::{communication, protocol_violation, schedule_serial, schedule_timer};
 use crate::{
     Timer,
     driver::frames,
@@ -40,6 +40,7 @@ impl Idle {
         }
     }
 
+    #[cfg_attr(feature = "iwram", inline(never), unsafe(link_section = ".iwram"))]
     pub(in crate::driver::active::flow) fn timer(&mut self) {
         if matches!(self.communication_state, communication::State::Send) {
             match self.transfer_length {
@@ -53,6 +54,7 @@ impl Idle {
         }
     }
 
+    #[cfg_attr(feature = "iwram", inline(never), unsafe(link_section = ".iwram"))]
     pub(in crate::driver::active::flow) fn serial(
         self,
         timer: Timer,
@@ -68,7 +70,7 @@ impl Idle {
                     if byte == 0xd2 {
                         Ok(None)
                     } else {
-                        Err(Error::Sio8(byte))
+                        Err(Error::Sio8(byte)).inspect_err(protocol_violation)
                     }
                 }
                 TransferLength::_32Bit => {
@@ -76,10 +78,60 @@ impl Idle {
                     if bytes == 0xd2_d2_d2_d2 {
                         Ok(None)
                     } else {
-                        Err(Error::Sio32(bytes))
+                        Err(Error::Sio32(bytes)).inspect_err(protocol_violation)
                     }
                 }
             },
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Idle;
+    use crate::{
+        Timer,
+        mmio::serial::{SIODATA8, SIODATA32, TransferLength},
+    };
+    use alloc::format;
+    use claims::{assert_err, assert_none, assert_ok};
+    use gba_test::test;
+
+    /// Exchanges one idle transfer, with the adapter answering `response`.
+    fn exchange(
+        transfer_length: TransferLength,
+        response: u32,
+    ) -> Result<Option<Idle>, super::Error> {
+        let mut idle = Idle::new(transfer_length, Timer::_0);
+        idle.timer();
+        match transfer_length {
+            TransferLength::_8Bit => unsafe { SIODATA8.write_volatile(response as u8) },
+            TransferLength::_32Bit => unsafe { SIODATA32.write_volatile(response) },
+        }
+        idle.serial(Timer::_0)
+    }
+
+    #[test]
+    fn sio8_idle() {
+        assert_none!(assert_ok!(exchange(TransferLength::_8Bit, 0xd2)));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "protocol-assertions", should_panic)]
+    fn sio8_not_idle() {
+        let error = assert_err!(exchange(TransferLength::_8Bit, 0x4b));
+        assert_eq!(format!("{error:?}"), "Sio8(75)");
+    }
+
+    #[test]
+    fn sio32_idle() {
+        assert_none!(assert_ok!(exchange(TransferLength::_32Bit, 0xd2_d2_d2_d2)));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "protocol-assertions", should_panic)]
+    fn sio32_not_idle() {
+        let error = assert_err!(exchange(TransferLength::_32Bit, 0xd2_d2_d2_00));
+        assert_eq!(format!("{error:?}"), "Sio32(3537031680)");
+    }
+}
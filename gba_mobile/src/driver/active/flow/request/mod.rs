@@ -18,7 +18,10 @@ use crate::{
         timer::{self, TM0CNT, TM0VAL, TM1CNT, TM1VAL, TM2CNT, TM2VAL, TM3CNT, TM3VAL},
     },
 };
+#[cfg(feature = "protocol-assertions")]
+use core::fmt::Display;
 
+#[cfg_attr(feature = "iwram", inline(never), unsafe(link_section = ".iwram"))]
 fn schedule_serial(transfer_length: TransferLength) {
     unsafe {
         SIOCNT.write_volatile(
@@ -31,11 +34,28 @@ fn schedule_serial(transfer_length: TransferLength) {
     }
 }
 
+#[cfg_attr(feature = "iwram", inline(never), unsafe(link_section = ".iwram"))]
 fn schedule_timer(timer: Timer, transfer_length: TransferLength) {
-    let value = match transfer_length {
-        TransferLength::_8Bit => timers::MICROSECONDS_200,
-        TransferLength::_32Bit => timers::MICROSECONDS_400,
-    };
+    schedule_timer_value(
+        timer,
+        match transfer_length {
+            TransferLength::_8Bit => timers::MICROSECONDS_200,
+            TransferLength::_32Bit => timers::MICROSECONDS_400,
+        },
+    )
+}
+
+/// Schedules the next transfer of a long drain after a receive error.
+///
+/// The packet has already failed, so the adapter gains nothing from it being drained quickly.
+/// Pacing these transfers further apart spreads the interrupts they cost over more frames.
+#[cfg_attr(feature = "iwram", inline(never), unsafe(link_section = ".iwram"))]
+fn schedule_drain_timer(timer: Timer) {
+    schedule_timer_value(timer, timers::MICROSECONDS_800)
+}
+
+#[cfg_attr(feature = "iwram", inline(never), unsafe(link_section = ".iwram"))]
+fn schedule_timer_value(timer: Timer, value: u16) {
     let control = timer::Control::new()
         .frequency(timer::Frequency::_1024)
         .interrupts(true)
@@ -61,3 +81,19 @@ fn schedule_timer(timer: Timer, transfer_length: TransferLength) {
         }
     }
 }
+
+/// Reports a deviation from the protocol by the adapter.
+///
+/// With the `protocol-assertions` feature this panics, so that conformance tests against a scripted
+/// adapter stop exactly where the deviation happened instead of going on to retry. Otherwise it
+/// does nothing, and the deviation is handled as usual.
+#[cfg(feature = "protocol-assertions")]
+fn protocol_violation<Violation>(violation: &Violation)
+where
+    Violation: Display,
+{
+    panic!("protocol violation: {violation}");
+}
+
+#[cfg(not(feature = "protocol-assertions"))]
+fn protocol_violation<Violation>(_violation: &Violation) {}
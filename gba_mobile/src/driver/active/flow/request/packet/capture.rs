@@ -0,0 +1,125 @@
+use core::{
+    fmt,
+    fmt::{Debug, Formatter},
+};
+
+/// The number of received bytes kept in a capture.
+const LEN: usize = 16;
+
+/// The part of a packet a captured byte was received in.
+///
+/// In 32-bit mode, every byte of a transfer is labeled with the step the whole transfer was
+/// received in.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(in crate::driver) enum Step {
+    Magic,
+    Header,
+    Data,
+    Checksum,
+    Footer,
+}
+
+/// The most recently received bytes of the current packet.
+///
+/// This is kept so that a receive error can report the bytes leading up to it, which is often
+/// needed to tell what an adapter was actually sending.
+#[derive(Clone, Eq, PartialEq)]
+pub(in crate::driver) struct Capture {
+    bytes: [u8; LEN],
+    steps: [Step; LEN],
+    /// The total number of bytes received, saturating once the capture is full.
+    len: u8,
+    /// The position the next byte will be written to.
+    next: u8,
+}
+
+impl Capture {
+    pub(in crate::driver) const fn new() -> Self {
+        Self {
+            bytes: [0; LEN],
+            steps: [Step::Magic; LEN],
+            len: 0,
+            next: 0,
+        }
+    }
+
+    pub(in crate::driver) fn push(&mut self, step: Step, byte: u8) {
+        // This is called for every byte received, so the index is reduced modulo the capture's
+        // length to let the bounds checks be compiled out.
+        let index = self.next as usize % LEN;
+        self.bytes[index] = byte;
+        self.steps[index] = step;
+        self.next = (self.next + 1) % LEN as u8;
+        if (self.len as usize) < LEN {
+            self.len += 1;
+        }
+    }
+
+    pub(in crate::driver) fn push_word(&mut self, step: Step, bytes: [u8; 4]) {
+        for byte in bytes {
+            self.push(step, byte);
+        }
+    }
+
+    /// Iterates over the captured bytes, oldest first.
+    pub(in crate::driver) fn iter(&self) -> impl Iterator<Item = (Step, u8)> + '_ {
+        let start = if (self.len as usize) < LEN {
+            0
+        } else {
+            self.next as usize
+        };
+        (0..self.len as usize).map(move |offset| {
+            let index = (start + offset) % LEN;
+            (self.steps[index], self.bytes[index])
+        })
+    }
+}
+
+impl Debug for Capture {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Capture, LEN, Step};
+    use alloc::format;
+    use gba_test::test;
+
+    #[test]
+    fn empty() {
+        assert_eq!(Capture::new().iter().count(), 0);
+    }
+
+    #[test]
+    fn partial() {
+        let mut capture = Capture::new();
+        capture.push(Step::Magic, 0x66);
+        capture.push(Step::Header, 0x10);
+
+        assert_eq!(format!("{capture:?}"), "[(Magic, 102), (Header, 16)]");
+    }
+
+    #[test]
+    fn keeps_most_recent() {
+        let mut capture = Capture::new();
+        for byte in 0..(LEN as u8 + 3) {
+            capture.push(Step::Data, byte);
+        }
+
+        assert!(capture.iter().map(|(_, byte)| byte).eq(3..(LEN as u8 + 3)));
+    }
+
+    #[test]
+    fn push_word() {
+        let mut capture = Capture::new();
+        capture.push_word(Step::Checksum, [0x00, 0x00, 0x01, 0x10]);
+
+        assert!(
+            capture
+                .iter()
+                .eq([0x00, 0x00, 0x01, 0x10].map(|byte| (Step::Checksum, byte)))
+        );
+    }
+}
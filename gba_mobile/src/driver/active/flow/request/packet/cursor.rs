@@ -0,0 +1,113 @@
+use super::Data;
+
+/// A read position within the data of a packet being sent.
+///
+/// Send steps pull data bytes through a cursor rather than indexing into the packet data
+/// themselves. This makes exhaustion of the data explicit, ensuring no step can read past the end
+/// of the payload regardless of how many bytes it packs into a single transfer.
+#[derive(Debug)]
+pub(super) struct Cursor {
+    position: u8,
+}
+
+impl Cursor {
+    pub(super) fn new() -> Self {
+        Self { position: 0 }
+    }
+
+    /// Pulls the next data byte, returning `None` if all data bytes have been pulled.
+    pub(super) fn next_byte(&mut self, data: &Data) -> Option<u8> {
+        let byte = data.data.get(self.position).copied()?;
+        // The data never holds more than `u8::MAX` bytes, so this can't overflow.
+        self.position += 1;
+        Some(byte)
+    }
+
+    /// Returns whether all data bytes have been pulled.
+    pub(super) fn is_exhausted(&self, data: &Data) -> bool {
+        debug_assert!(self.position <= data.data.len());
+        self.position >= data.data.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cursor, Data};
+    use claims::{assert_none, assert_some_eq};
+    use gba_test::test;
+
+    fn data(length: u8) -> Data {
+        let mut data = Data::new();
+        for byte in 0..length {
+            data.data.try_push(byte).unwrap();
+        }
+        data
+    }
+
+    #[test]
+    fn empty() {
+        let data = data(0);
+        let mut cursor = Cursor::new();
+
+        assert!(cursor.is_exhausted(&data));
+        assert_none!(cursor.next_byte(&data));
+    }
+
+    #[test]
+    fn in_order() {
+        let data = data(3);
+        let mut cursor = Cursor::new();
+
+        assert_some_eq!(cursor.next_byte(&data), 0);
+        assert_some_eq!(cursor.next_byte(&data), 1);
+        assert!(!cursor.is_exhausted(&data));
+        assert_some_eq!(cursor.next_byte(&data), 2);
+        assert!(cursor.is_exhausted(&data));
+        assert_none!(cursor.next_byte(&data));
+    }
+
+    #[test]
+    fn all_lengths_single_bytes() {
+        // Pulling one byte at a time, as the SIO8 send path does.
+        for length in 0..=u8::MAX {
+            let data = data(length);
+            let mut cursor = Cursor::new();
+
+            let mut pulled = 0usize;
+            while !cursor.is_exhausted(&data) {
+                assert_some_eq!(cursor.next_byte(&data), pulled as u8);
+                pulled += 1;
+            }
+
+            assert_eq!(pulled, length as usize);
+            assert_none!(cursor.next_byte(&data));
+        }
+    }
+
+    #[test]
+    fn all_lengths_words() {
+        // Pulling two bytes alongside the header and then four bytes at a time, as the SIO32 send
+        // path does.
+        for length in 0..=u8::MAX {
+            let data = data(length);
+            let mut cursor = Cursor::new();
+
+            let mut pulled = 0usize;
+            for _ in 0..2 {
+                if cursor.next_byte(&data).is_some() {
+                    pulled += 1;
+                }
+            }
+            while !cursor.is_exhausted(&data) {
+                for _ in 0..4 {
+                    if cursor.next_byte(&data).is_some() {
+                        pulled += 1;
+                    }
+                }
+            }
+
+            assert_eq!(pulled, length as usize);
+            assert_none!(cursor.next_byte(&data));
+        }
+    }
+}
@@ -1,9 +1,24 @@
-use crate::{ArrayVec, driver::Command};
+use super::Capture;
+use crate::{ArrayVec, driver::Command, stats::LinkStats};
+use core::num::NonZeroU8;
 
 #[derive(Debug)]
 pub(in crate::driver::active) struct Data {
-    pub(super) command: Command,
+    pub(in crate::driver::active) command: Command,
     pub(in crate::driver::active::flow) data: ArrayVec<u8, 255>,
+    /// The bytes most recently received from the adapter, kept for reporting receive errors.
+    pub(in crate::driver::active) capture: Capture,
+    /// The second acknowledgement byte of the most recently received packet, if it was nonzero.
+    pub(in crate::driver::active) extended_acknowledgement: Option<NonZeroU8>,
+    /// How many times the packet in flight has been sent again, or, once it was acknowledged, how
+    /// many times its response has been asked for again.
+    pub(in crate::driver::active) attempt: u8,
+    /// Counters for every packet exchanged during the current link session.
+    pub(in crate::driver::active) stats: LinkStats,
+    /// Whether fields the adapter is only expected to leave empty are verified.
+    pub(in crate::driver::active) strict: bool,
+    /// Whether a nonzero second acknowledgement byte is recorded rather than treated as an error.
+    pub(in crate::driver::active) extended_ack: bool,
 }
 
 impl Data {
@@ -11,6 +26,26 @@ impl Data {
         Self {
             command: Command::Empty,
             data: ArrayVec::new(),
+            capture: Capture::new(),
+            extended_acknowledgement: None,
+            attempt: 0,
+            stats: LinkStats::default(),
+            strict: false,
+            extended_ack: false,
         }
     }
+
+    /// Clears everything kept about the previous packet before a response is received.
+    ///
+    /// The command is kept, since the response's command is checked against it.
+    pub(in crate::driver::active) fn begin_response(&mut self) {
+        *self = Self {
+            command: self.command,
+            attempt: self.attempt,
+            stats: self.stats,
+            strict: self.strict,
+            extended_ack: self.extended_ack,
+            ..Self::new()
+        };
+    }
 }
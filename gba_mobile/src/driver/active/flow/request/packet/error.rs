@@ -1,15 +1,41 @@
-use super::Payload;
+use super::{Capture, Data, Payload, protocol_violation};
 use crate::driver::{Command, adapter, command};
-use core::{
-    fmt::{self, Display, Formatter},
-    num::NonZeroU8,
-};
+use core::fmt::{self, Display, Formatter};
+use core::num::NonZeroU8;
 
 #[derive(Clone, Debug)]
 pub(in crate::driver) enum Send {
     UnsupportedCommand(Command),
     Malformed,
     AdapterInternalError,
+    BadAcknowledgement {
+        received: u8,
+    },
+    /// The acknowledgement echoed a different command than the one sent.
+    ///
+    /// Only distinguished from [`Send::BadAcknowledgement`] in strict mode.
+    AckCommandMismatch {
+        expected: Command,
+        received: Command,
+    },
+    /// A byte following the command in an SIO32 acknowledgement was not zero.
+    ///
+    /// The position is the byte's index within the acknowledgement. Only checked in strict mode.
+    NonZeroPadding {
+        position: u8,
+        value: u8,
+    },
+}
+
+impl Send {
+    /// Whether the adapter itself refused the packet, rather than its acknowledgement being
+    /// garbled.
+    pub(in crate::driver) fn is_adapter_error(&self) -> bool {
+        matches!(
+            self,
+            Self::UnsupportedCommand(_) | Self::Malformed | Self::AdapterInternalError
+        )
+    }
 }
 
 impl Display for Send {
@@ -24,6 +50,18 @@ impl Display for Send {
             Self::AdapterInternalError => {
                 formatter.write_str("adapter indicated it encountered an internal error")
             }
+            Self::BadAcknowledgement { received } => write!(
+                formatter,
+                "adapter acknowledged packet with {received:#04x} instead of echoing its command"
+            ),
+            Self::AckCommandMismatch { expected, received } => write!(
+                formatter,
+                "adapter acknowledged packet with command {received}, but expected {expected}"
+            ),
+            Self::NonZeroPadding { position, value } => write!(
+                formatter,
+                "adapter acknowledged packet with {value:#04x} at position {position}, but expected 0x00"
+            ),
         }
     }
 }
@@ -35,9 +73,24 @@ pub(in crate::driver) enum Receive {
     MagicValue2(u8),
 
     UnknownCommand(command::Unknown),
-    LengthTooLarge(u16),
+    MismatchedCommand {
+        sent: Command,
+        received: Command,
+    },
+    UnexpectedLength(u16),
+    CommandErrorLength(u16),
+    /// The empty byte of the packet's header was not zero.
+    ///
+    /// The position is the byte's index within the packet. Only checked in strict mode.
+    NonZeroPadding {
+        position: u8,
+        value: u8,
+    },
 
-    Checksum { calculated: u16, received: u16 },
+    Checksum {
+        calculated: u16,
+        received: u16,
+    },
     UnsupportedDevice(adapter::Unknown),
     NonZeroFooterCommand(NonZeroU8),
 }
@@ -48,13 +101,27 @@ impl Receive {
             Self::MagicValue2(_) => Command::MalformedError,
 
             Self::UnknownCommand(_) => Command::NotSupportedError,
-            Self::LengthTooLarge(_) => Command::MalformedError,
+            Self::MismatchedCommand { .. } => Command::MalformedError,
+            Self::UnexpectedLength(_) => Command::MalformedError,
+            Self::CommandErrorLength(_) => Command::MalformedError,
+            Self::NonZeroPadding { .. } => Command::MalformedError,
 
             Self::Checksum { .. } => Command::MalformedError,
             Self::UnsupportedDevice(_) => Command::MalformedError,
             Self::NonZeroFooterCommand(_) => Command::MalformedError,
         }
     }
+
+    /// Whether the packet should be requested again after this error.
+    ///
+    /// An invalid length means the rest of the packet could not be drained reliably, so it is not
+    /// worth asking for again.
+    pub(super) fn is_retryable(&self) -> bool {
+        !matches!(
+            self,
+            Self::UnexpectedLength(_) | Self::CommandErrorLength(_)
+        )
+    }
 }
 
 impl Display for Receive {
@@ -68,10 +135,22 @@ impl Display for Receive {
             Self::UnknownCommand(_) => {
                 formatter.write_str("received an invalid command in packet header")
             }
-            Self::LengthTooLarge(length) => write!(
+            Self::MismatchedCommand { sent, received } => write!(
+                formatter,
+                "received response with command {received}, which does not answer {sent}"
+            ),
+            Self::UnexpectedLength(length) => write!(
                 formatter,
                 "received response packet length of {length}, but maximum supported length is 255"
             ),
+            Self::CommandErrorLength(length) => write!(
+                formatter,
+                "received command error response with length of {length}, but expected length of 2"
+            ),
+            Self::NonZeroPadding { position, value } => write!(
+                formatter,
+                "received packet with {value:#04x} at position {position}, but expected 0x00"
+            ),
 
             Self::Checksum {
                 calculated,
@@ -97,7 +176,10 @@ impl core::error::Error for Receive {
             Self::MagicValue2(_) => None,
 
             Self::UnknownCommand(unknown) => Some(unknown),
-            Self::LengthTooLarge(_) => None,
+            Self::MismatchedCommand { .. } => None,
+            Self::UnexpectedLength(_) => None,
+            Self::CommandErrorLength(_) => None,
+            Self::NonZeroPadding { .. } => None,
 
             Self::Checksum { .. } => None,
             Self::UnsupportedDevice(unknown) => Some(unknown),
@@ -112,10 +194,28 @@ where
     Payload: self::Payload,
 {
     Send(Send),
-    Receive(Receive),
+    Receive {
+        error: Receive,
+        /// The bytes received leading up to the error.
+        context: Capture,
+    },
     Payload(Payload::Error),
 }
 
+impl<Payload> Error<Payload>
+where
+    Payload: self::Payload,
+{
+    /// A receive error, along with the bytes received leading up to it.
+    pub(super) fn receive(error: Receive, data: &Data) -> Self {
+        protocol_violation(&error);
+        Self::Receive {
+            error,
+            context: data.capture.clone(),
+        }
+    }
+}
+
 impl<Payload> Clone for Error<Payload>
 where
     Payload: self::Payload,
@@ -123,7 +223,10 @@ where
     fn clone(&self) -> Self {
         match self {
             Self::Send(error) => Self::Send(error.clone()),
-            Self::Receive(error) => Self::Receive(error.clone()),
+            Self::Receive { error, context } => Self::Receive {
+                error: error.clone(),
+                context: context.clone(),
+            },
             Self::Payload(error) => Self::Payload(error.clone()),
         }
     }
@@ -136,7 +239,7 @@ where
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
         match self {
             Self::Send(_) => formatter.write_str("error while sending packet"),
-            Self::Receive(_) => formatter.write_str("error while receiving packet"),
+            Self::Receive { .. } => formatter.write_str("error while receiving packet"),
             Self::Payload(_) => formatter.write_str("error interpreting payload"),
         }
     }
@@ -149,7 +252,7 @@ where
     fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
         match self {
             Self::Send(error) => Some(error),
-            Self::Receive(error) => Some(error),
+            Self::Receive { error, .. } => Some(error),
             Self::Payload(error) => Some(error),
         }
     }
@@ -163,12 +266,3 @@ where
         Self::Send(error)
     }
 }
-
-impl<Payload> From<Receive> for Error<Payload>
-where
-    Payload: self::Payload,
-{
-    fn from(error: Receive) -> Self {
-        Self::Receive(error)
-    }
-}
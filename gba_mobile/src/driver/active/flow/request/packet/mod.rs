@@ -2,7 +2,12 @@ pub(in crate::driver) mod error;
 
 pub(in crate::driver::active::flow) mod payload;
 
+mod capture;
+mod cursor;
 mod data;
+mod response_window;
+#[cfg(test)]
+pub(in crate::driver) mod script;
 mod sio32;
 mod sio8;
 mod timeout;
@@ -10,21 +15,148 @@ mod timeout;
 pub(in crate::driver) use error::Error;
 pub(in crate::driver) use timeout::Timeout;
 
+pub(in crate::driver) use capture::Capture;
 pub(in crate::driver::active) use data::Data;
 
 pub(in crate::driver::active::flow) use payload::Payload;
 
-use super::{communication, schedule_serial, schedule_timer};
-use crate::{Timer, driver::Adapter, mmio::serial::TransferLength};
+use super::{
+    communication, protocol_violation, schedule_drain_timer, schedule_serial, schedule_timer,
+};
+use crate::{
+    Timer,
+    driver::{Adapter, Command},
+    mmio::serial::TransferLength,
+};
+use core::num::{NonZeroU8, NonZeroU16};
+use cursor::Cursor;
 use either::Either;
+use response_window::ResponseWindow;
 use sio8::Sio8;
 use sio32::Sio32;
 
-const MAX_RETRIES: u8 = 5;
+pub(in crate::driver::active) const MAX_RETRIES: u8 = 5;
+/// The largest payload a packet may declare.
+const MAX_PAYLOAD_LENGTH: NonZeroU16 = NonZeroU16::new(255).unwrap();
+
+/// The number of payload bytes to skip over after an error while receiving a packet.
+///
+/// Lengths beyond the protocol's maximum are capped, since draining a declared length of `0xffff`
+/// would take tens of seconds. The adapter has lost track of the protocol by then, so the packet
+/// is not retried anyway.
+fn drain_length(declared: NonZeroU16) -> NonZeroU16 {
+    declared.min(MAX_PAYLOAD_LENGTH)
+}
+
+/// Validates the payload length declared in the header of a received packet.
+///
+/// A command error response always carries exactly two bytes, so one declaring any other length
+/// is rejected as soon as its header is received instead of after its whole payload.
+fn check_length(command: Command, length: u16) -> Result<(), error::Receive> {
+    if length > MAX_PAYLOAD_LENGTH.get() {
+        Err(error::Receive::UnexpectedLength(length))
+    } else if matches!(command, Command::CommandError) && length != 2 {
+        Err(error::Receive::CommandErrorLength(length))
+    } else {
+        Ok(())
+    }
+}
+
+/// Validates the command in the header of a received packet.
+///
+/// A command that is valid but doesn't answer the request that was sent indicates a corrupted
+/// command byte, so it is handled like any other receive error instead of being handed to the
+/// payload.
+fn check_command(
+    sent: Command,
+    received: Command,
+    responses: &[Command],
+) -> Result<(), error::Receive> {
+    if responses.contains(&received) {
+        Ok(())
+    } else {
+        Err(error::Receive::MismatchedCommand { sent, received })
+    }
+}
+
+/// Verifies that the adapter acknowledged a sent packet by echoing its command.
+///
+/// Anything else, whether another command or an undecodable byte, means the adapter may not have
+/// accepted the packet. In strict mode, an echo of another command reports both commands.
+fn verify_acknowledgement(data: &Data, byte: u8) -> Result<(), error::Send> {
+    if byte ^ 0x80 == data.command as u8 {
+        Ok(())
+    } else {
+        let error = match Command::try_from(byte ^ 0x80) {
+            Ok(received) if data.strict => error::Send::AckCommandMismatch {
+                expected: data.command,
+                received,
+            },
+            _ => error::Send::BadAcknowledgement { received: byte },
+        };
+        protocol_violation(&error);
+        Err(error)
+    }
+}
+
+/// Verifies that the bytes following the command in an SIO32 acknowledgement are zero.
+///
+/// Adapters are not known to send anything else, so these bytes are only checked in strict mode.
+fn verify_acknowledgement_padding(data: &Data, bytes: [u8; 4]) -> Result<(), error::Send> {
+    match (data.strict, bytes[2], bytes[3]) {
+        (false, _, _) | (true, 0x00, 0x00) => Ok(()),
+        (true, 0x00, value) => Err(error::Send::NonZeroPadding { position: 3, value }),
+        (true, value, _) => Err(error::Send::NonZeroPadding { position: 2, value }),
+    }
+    .inspect_err(protocol_violation)
+}
+
+/// Verifies that the empty byte in the header of a received packet is zero.
+///
+/// This byte is otherwise only added to the checksum, so it is only checked in strict mode.
+fn check_empty_byte(data: &Data, byte: u8) -> Result<(), error::Receive> {
+    if data.strict && byte != 0x00 {
+        Err(error::Receive::NonZeroPadding {
+            position: 3,
+            value: byte,
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// The command byte sent in the acknowledgement signal for a received packet.
+///
+/// This is shared between both transfer lengths, which differ only in how the byte is packed into
+/// a transfer.
+fn acknowledgement_command(command: Command, command_xor: bool) -> u8 {
+    if command_xor {
+        command as u8 | 0x80
+    } else {
+        command as u8
+    }
+}
+
+/// Checks the command byte of the adapter's acknowledgement signal, which is expected to be `0x00`.
+///
+/// Adapters implementing the extended acknowledgement use this byte to report their status, so
+/// when it is enabled, the byte is recorded instead.
+fn acknowledgement_footer(data: &mut Data, byte: u8) -> Result<(), error::Receive> {
+    match NonZeroU8::new(byte) {
+        Some(nonzero) if !data.extended_ack => Err(error::Receive::NonZeroFooterCommand(nonzero)),
+        status => {
+            data.extended_acknowledgement = status;
+            Ok(())
+        }
+    }
+}
 
 trait Send: Sized {
     type WaitForReceive;
 
+    /// The number of times the packet has been sent again.
+    fn attempt(&self) -> u8;
+
     fn vblank(&mut self) -> Result<(), Timeout>;
 
     fn timer(&mut self, data: &Data);
@@ -36,9 +168,16 @@ trait WaitForReceive: Sized {
     type Receive;
     type ReceiveError;
 
+    /// The number of times the adapter has been asked to send the response again.
+    fn attempt(&self) -> u8;
+
     fn vblank(&mut self) -> Result<(), Timeout>;
 
-    fn serial(self, data: &mut Data) -> Result<Either<Self, Self::Receive>, Self::ReceiveError>;
+    fn serial(
+        self,
+        data: &mut Data,
+        responses: &[Command],
+    ) -> Result<Either<Self, Self::Receive>, Self::ReceiveError>;
 }
 
 trait Receive: Sized {
@@ -51,6 +190,7 @@ trait Receive: Sized {
     fn serial(
         self,
         data: &mut Data,
+        responses: &[Command],
     ) -> Result<Either<Result<Self, Self::ReceiveError>, Adapter>, error::Receive>;
 }
 
@@ -61,6 +201,12 @@ trait ReceiveError: Sized {
 
     fn timer(&mut self);
 
+    /// Whether the packet was rejected because of a checksum mismatch.
+    fn is_checksum(&self) -> bool;
+
+    /// Whether a long payload is being drained, which is paced more slowly.
+    fn is_long_drain(&self) -> bool;
+
     fn serial(self) -> Result<Either<Self, Self::WaitForReceive>, error::Receive>;
 }
 
@@ -97,6 +243,10 @@ where
         }
     }
 
+    fn is_waiting_for_receive(&self) -> bool {
+        matches!(self, Self::WaitForReceive(_))
+    }
+
     fn timer(&mut self, data: &Data) {
         match self {
             Self::Send(send) => send.timer(data),
@@ -117,32 +267,60 @@ where
         match self {
             Self::Send(send) => Ok(Either::Left(
                 send.serial(data)?
-                    .map_left(Self::Send)
-                    .map_right(Self::WaitForReceive)
-                    .into_inner(),
-            )),
-            Self::WaitForReceive(wait_for_receive) => Ok(Either::Left(
-                Either::from(wait_for_receive.serial(data))
-                    .map_right(|right| {
-                        right
-                            .map_left(Self::WaitForReceive)
-                            .map_right(Self::Receive)
-                            .into_inner()
+                    .map_left(|send| {
+                        data.attempt = send.attempt();
+                        Self::Send(send)
+                    })
+                    .map_right(|wait_for_receive| {
+                        data.stats.record_sent();
+                        data.attempt = wait_for_receive.attempt();
+                        Self::WaitForReceive(wait_for_receive)
                     })
-                    .map_left(Self::ReceiveError)
                     .into_inner(),
             )),
-            Self::Receive(receive) => Ok(receive.serial(data)?.map_left(|left| {
-                Either::from(left)
-                    .map_right(Self::Receive)
-                    .map_left(Self::ReceiveError)
-                    .into_inner()
-            })),
+            Self::WaitForReceive(wait_for_receive) => {
+                let result = wait_for_receive.serial(data, Payload::RESPONSES);
+                Ok(Either::Left(
+                    Either::from(result)
+                        .map_right(|right| {
+                            right
+                                .map_left(Self::WaitForReceive)
+                                .map_right(Self::Receive)
+                                .into_inner()
+                        })
+                        .map_left(|receive_error| {
+                            data.stats.record_receive_error(receive_error.is_checksum());
+                            Self::ReceiveError(receive_error)
+                        })
+                        .into_inner(),
+                ))
+            }
+            Self::Receive(receive) => Ok(receive
+                .serial(data, Payload::RESPONSES)
+                .map_err(|error| Error::receive(error, data))?
+                .map_left(|left| {
+                    Either::from(left)
+                        .map_right(Self::Receive)
+                        .map_left(|receive_error| {
+                            data.stats.record_receive_error(receive_error.is_checksum());
+                            Self::ReceiveError(receive_error)
+                        })
+                        .into_inner()
+                })
+                .map_right(|adapter| {
+                    data.stats.record_received();
+                    adapter
+                })),
             Self::ReceiveError(receive_error) => Ok(Either::Left(
                 receive_error
-                    .serial()?
+                    .serial()
+                    .map_err(|error| Error::receive(error, data))?
                     .map_left(Self::ReceiveError)
-                    .map_right(Self::WaitForReceive)
+                    .map_right(|wait_for_receive| {
+                        data.stats.record_retry(wait_for_receive.attempt());
+                        data.attempt = wait_for_receive.attempt();
+                        Self::WaitForReceive(wait_for_receive)
+                    })
                     .into_inner(),
             )),
         }
@@ -161,7 +339,13 @@ where
             Self::Send(_) => schedule_timer(timer, Sio::TRANSFER_LENGTH),
             Self::WaitForReceive(_) => {}
             Self::Receive(_) => schedule_timer(timer, Sio::TRANSFER_LENGTH),
-            Self::ReceiveError(_) => schedule_timer(timer, Sio::TRANSFER_LENGTH),
+            Self::ReceiveError(receive_error) => {
+                if receive_error.is_long_drain() {
+                    schedule_drain_timer(timer)
+                } else {
+                    schedule_timer(timer, Sio::TRANSFER_LENGTH)
+                }
+            }
         }
     }
 }
@@ -188,6 +372,21 @@ impl State {
         }
     }
 
+    fn is_waiting_for_receive(&self) -> bool {
+        match self {
+            Self::Packet8(packet) => packet.is_waiting_for_receive(),
+            Self::Packet32(packet) => packet.is_waiting_for_receive(),
+        }
+    }
+
+    #[cfg(test)]
+    fn transfer_length(&self) -> TransferLength {
+        match self {
+            Self::Packet8(_) => TransferLength::_8Bit,
+            Self::Packet32(_) => TransferLength::_32Bit,
+        }
+    }
+
     fn timer(&mut self, data: &Data) {
         match self {
             Self::Packet8(packet) => packet.timer(data),
@@ -218,6 +417,9 @@ impl State {
 pub(in crate::driver::active::flow) struct Packet<Payload> {
     state: State,
     payload: Payload,
+    response_window: ResponseWindow,
+    /// Frames since the last serial interrupt, not counting time spent waiting for a response.
+    serial_gap: u8,
 }
 
 impl<Payload> Packet<Payload>
@@ -232,11 +434,19 @@ where
         Self {
             state: State::new(transfer_length, timer),
             payload,
+            response_window: ResponseWindow::new(),
+            serial_gap: 0,
         }
     }
 
     pub(in crate::driver::active::flow) fn vblank(&mut self) -> Result<(), Timeout> {
-        self.state.vblank()
+        self.state.vblank()?;
+        if self.state.is_waiting_for_receive() {
+            self.response_window.vblank()?;
+        } else {
+            self.serial_gap = self.serial_gap.saturating_add(1);
+        }
+        Ok(())
     }
 
     pub(in crate::driver::active::flow) fn timer(&mut self, data: &Data) {
@@ -251,12 +461,15 @@ where
     where
         'a: 'b,
     {
+        data.stats.record_serial_gap(self.serial_gap);
         self.state
             .serial(timer, data)
             .and_then(|either| match either {
                 Either::Left(state) => Ok(Either::Left(Self {
                     state,
                     payload: self.payload,
+                    response_window: self.response_window,
+                    serial_gap: 0,
                 })),
                 Either::Right(adapter) => self
                     .payload
@@ -279,3 +492,731 @@ where
     pub(in crate::driver::active::flow) payload: Payload::Response<'a>,
     pub(in crate::driver::active::flow) adapter: Adapter,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Data, Error, MAX_PAYLOAD_LENGTH, Operation, Packet, Payload, State,
+        acknowledgement_command, acknowledgement_footer, capture, check_command, check_empty_byte,
+        check_length, drain_length, error, payload, script, verify_acknowledgement,
+        verify_acknowledgement_padding,
+    };
+    use crate::{
+        ArrayVec, Timer,
+        driver::{Adapter, Command},
+        guardrails,
+        mmio::serial::TransferLength,
+        socket,
+        stats::LinkStats,
+    };
+    use alloc::{format, vec::Vec};
+    use claims::{assert_err, assert_ok, assert_ok_eq, assert_some};
+    use core::{iter, num::NonZeroU16};
+    use either::Either;
+    use gba_test::test;
+
+    #[test]
+    fn acknowledgement_matches() {
+        let mut data = Data::new();
+        data.command = Command::BeginSession;
+
+        assert_ok!(verify_acknowledgement(&data, 0x90));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "protocol-assertions", should_panic)]
+    fn acknowledgement_mismatch() {
+        let mut data = Data::new();
+        data.command = Command::BeginSession;
+
+        let error = assert_err!(verify_acknowledgement(&data, 0x91));
+        assert_eq!(format!("{error:?}"), "BadAcknowledgement { received: 145 }");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "protocol-assertions", should_panic)]
+    fn acknowledgement_mismatch_strict() {
+        let mut data = Data::new();
+        data.command = Command::BeginSession;
+        data.strict = true;
+
+        let error = assert_err!(verify_acknowledgement(&data, 0x91));
+        assert_eq!(
+            format!("{error:?}"),
+            "AckCommandMismatch { expected: BeginSession, received: EndSession }"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "protocol-assertions", should_panic)]
+    fn acknowledgement_undecodable_strict() {
+        let mut data = Data::new();
+        data.command = Command::BeginSession;
+        data.strict = true;
+
+        let error = assert_err!(verify_acknowledgement(&data, 0xff));
+        assert_eq!(format!("{error:?}"), "BadAcknowledgement { received: 255 }");
+    }
+
+    #[test]
+    fn acknowledgement_padding_zero_strict() {
+        let mut data = Data::new();
+        data.strict = true;
+
+        assert_ok!(verify_acknowledgement_padding(
+            &data,
+            [0x88, 0x90, 0x00, 0x00]
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "protocol-assertions", should_panic)]
+    fn acknowledgement_padding_nonzero_only_rejected_when_strict() {
+        let mut data = Data::new();
+
+        assert_ok!(verify_acknowledgement_padding(
+            &data,
+            [0x88, 0x90, 0x00, 0x42]
+        ));
+
+        data.strict = true;
+        let error = assert_err!(verify_acknowledgement_padding(
+            &data,
+            [0x88, 0x90, 0x00, 0x42]
+        ));
+        assert_eq!(
+            format!("{error:?}"),
+            "NonZeroPadding { position: 3, value: 66 }"
+        );
+    }
+
+    #[test]
+    fn empty_byte_zero_strict() {
+        let mut data = Data::new();
+        data.strict = true;
+
+        assert_ok!(check_empty_byte(&data, 0x00));
+    }
+
+    #[test]
+    fn empty_byte_nonzero_only_rejected_when_strict() {
+        let mut data = Data::new();
+
+        assert_ok!(check_empty_byte(&data, 0x42));
+
+        data.strict = true;
+        let error = assert_err!(check_empty_byte(&data, 0x42));
+        assert_eq!(
+            format!("{error:?}"),
+            "NonZeroPadding { position: 3, value: 66 }"
+        );
+        assert!(error.is_retryable());
+    }
+
+    #[test]
+    fn drain_length_within_maximum() {
+        let length = assert_some!(NonZeroU16::new(0x00fe));
+
+        assert_eq!(drain_length(length), length);
+    }
+
+    #[test]
+    fn drain_length_capped() {
+        assert_eq!(
+            drain_length(assert_some!(NonZeroU16::new(0xffff))),
+            MAX_PAYLOAD_LENGTH
+        );
+    }
+
+    #[test]
+    fn check_length_maximum() {
+        assert_ok!(check_length(Command::TransferData, 0x00ff));
+    }
+
+    #[test]
+    fn check_length_too_large() {
+        let error = assert_err!(check_length(Command::TransferData, 0x0100));
+
+        assert_eq!(format!("{error:?}"), "UnexpectedLength(256)");
+    }
+
+    #[test]
+    fn check_length_command_error() {
+        assert_ok!(check_length(Command::CommandError, 2));
+    }
+
+    #[test]
+    fn check_length_command_error_too_long() {
+        let error = assert_err!(check_length(Command::CommandError, 3));
+
+        assert_eq!(format!("{error:?}"), "CommandErrorLength(3)");
+        assert!(!error.is_retryable());
+    }
+
+    #[test]
+    fn check_length_command_error_too_short() {
+        for length in [0, 1] {
+            let error = assert_err!(check_length(Command::CommandError, length));
+
+            assert_eq!(
+                format!("{error:?}"),
+                format!("CommandErrorLength({length})")
+            );
+            assert!(!error.is_retryable());
+        }
+    }
+
+    /// Asserts that a command error response carrying `bytes` fails the packet without it being
+    /// requested again.
+    fn assert_short_command_error_fails(transfer_length: TransferLength, bytes: &[u8]) {
+        let mut data = Data::new();
+        let packet = Packet::new(
+            payload::EndSession::new(&mut data),
+            transfer_length,
+            Timer::_0,
+        );
+        let adapter = script::acknowledge(transfer_length, Command::EndSession, 0).chain(
+            script::response(transfer_length, Command::CommandError, bytes),
+        );
+
+        let (_, result) = script::exchange(packet, &mut data, adapter);
+
+        let error = assert_err!(result);
+        assert!(
+            matches!(
+                error,
+                Error::Receive {
+                    error: error::Receive::CommandErrorLength(length),
+                    ..
+                } if length as usize == bytes.len()
+            ),
+            "{error:?}"
+        );
+        assert_eq!(data.stats.retries, 0);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "protocol-assertions", should_panic)]
+    fn empty_command_error_fails_sio8() {
+        assert_short_command_error_fails(TransferLength::_8Bit, &[]);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "protocol-assertions", should_panic)]
+    fn short_command_error_fails_sio8() {
+        assert_short_command_error_fails(TransferLength::_8Bit, &[0x12]);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "protocol-assertions", should_panic)]
+    fn empty_command_error_fails_sio32() {
+        assert_short_command_error_fails(TransferLength::_32Bit, &[]);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "protocol-assertions", should_panic)]
+    fn short_command_error_fails_sio32() {
+        assert_short_command_error_fails(TransferLength::_32Bit, &[0x12]);
+    }
+
+    #[test]
+    fn check_command_response() {
+        assert_ok!(check_command(
+            Command::BeginSession,
+            Command::BeginSession,
+            payload::BeginSession::RESPONSES
+        ));
+    }
+
+    #[test]
+    fn check_command_command_error() {
+        assert_ok!(check_command(
+            Command::BeginSession,
+            Command::CommandError,
+            payload::BeginSession::RESPONSES
+        ));
+    }
+
+    #[test]
+    fn check_command_mismatch() {
+        let error = assert_err!(check_command(
+            Command::BeginSession,
+            Command::TelephoneStatus,
+            payload::BeginSession::RESPONSES
+        ));
+
+        assert_eq!(
+            format!("{error:?}"),
+            "MismatchedCommand { sent: BeginSession, received: TelephoneStatus }"
+        );
+        assert_eq!(
+            format!("{error}"),
+            "received response with command Telephone Status (0x17), which does not answer Begin Session (0x10)"
+        );
+        assert!(error.is_retryable());
+    }
+
+    /// Asserts that a response to Begin Session with a Telephone Status header is retried.
+    ///
+    /// The header alone rejects the response, so the packet must go through its receive error
+    /// step and be received again instead of finishing with the wrong response.
+    fn assert_mismatched_command_retried(transfer_length: TransferLength) {
+        let mut data = Data::new();
+        let mut packet = Packet::new(
+            payload::BeginSession::new(&mut data),
+            transfer_length,
+            Timer::_0,
+        );
+        let mut adapter = script::acknowledge(transfer_length, Command::BeginSession, 8)
+            .chain(script::response(
+                transfer_length,
+                Command::TelephoneStatus,
+                &[0x00, 0x4d, 0x00],
+            ))
+            .chain(script::response(
+                transfer_length,
+                Command::BeginSession,
+                b"NINTENDO",
+            ));
+        let mut sent = Vec::new();
+        let mut receive_errors = 0;
+
+        let adapter = loop {
+            if packet.state.is_waiting_for_receive() {
+                assert_ok!(packet.vblank());
+            } else {
+                packet.timer(&data);
+            }
+            assert!(script::transfer(transfer_length, &mut adapter, &mut sent));
+            match assert_ok!(packet.serial(Timer::_0, &mut data)) {
+                Either::Left(next) => packet = next,
+                Either::Right(response) => break response.adapter,
+            }
+            if matches!(
+                packet.state,
+                State::Packet8(Operation::ReceiveError(_))
+                    | State::Packet32(Operation::ReceiveError(_))
+            ) {
+                assert!(format!("{:?}", packet.state).contains(
+                    "MismatchedCommand { sent: BeginSession, received: TelephoneStatus }"
+                ));
+                receive_errors += 1;
+            }
+        };
+
+        assert_eq!(adapter, Adapter::Blue);
+        assert!(receive_errors > 0);
+        assert_eq!(data.stats.receive_errors, 1);
+        assert_eq!(data.stats.retries, 1);
+        assert_eq!(data.stats.packets_received, 1);
+        assert_eq!(data.command, Command::BeginSession);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "protocol-assertions", should_panic)]
+    fn mismatched_command_retried_sio8() {
+        assert_mismatched_command_retried(TransferLength::_8Bit);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "protocol-assertions", should_panic)]
+    fn mismatched_command_retried_sio32() {
+        assert_mismatched_command_retried(TransferLength::_32Bit);
+    }
+
+    /// Exchanges a Begin Session packet whose response has `0x42` as its header's empty byte,
+    /// returning the number of receive errors.
+    ///
+    /// A correct response is scripted after it, in case the first one is rejected.
+    fn exchange_nonzero_empty_byte(transfer_length: TransferLength, strict: bool) -> u16 {
+        let mut data = Data::new();
+        data.strict = strict;
+        let packet = Packet::new(
+            payload::BeginSession::new(&mut data),
+            transfer_length,
+            Timer::_0,
+        );
+        let mut response = script::response(transfer_length, Command::BeginSession, b"NINTENDO");
+        response[3] = 0x42;
+        let checksum = response.len()
+            - match transfer_length {
+                TransferLength::_8Bit => 4,
+                TransferLength::_32Bit => 6,
+            };
+        let corrected = u16::from_be_bytes([response[checksum], response[checksum + 1]]) + 0x42;
+        response[checksum..checksum + 2].copy_from_slice(&corrected.to_be_bytes());
+        let adapter = script::acknowledge(transfer_length, Command::BeginSession, 8)
+            .chain(response)
+            .chain(script::response(
+                transfer_length,
+                Command::BeginSession,
+                b"NINTENDO",
+            ));
+
+        let (_, result) = script::exchange(packet, &mut data, adapter);
+
+        assert_ok!(result);
+        data.stats.receive_errors
+    }
+
+    #[test]
+    #[cfg_attr(feature = "protocol-assertions", should_panic)]
+    fn nonzero_empty_byte_only_retried_when_strict_sio8() {
+        assert_eq!(exchange_nonzero_empty_byte(TransferLength::_8Bit, false), 0);
+        assert_eq!(exchange_nonzero_empty_byte(TransferLength::_8Bit, true), 1);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "protocol-assertions", should_panic)]
+    fn nonzero_empty_byte_only_retried_when_strict_sio32() {
+        assert_eq!(
+            exchange_nonzero_empty_byte(TransferLength::_32Bit, false),
+            0
+        );
+        assert_eq!(exchange_nonzero_empty_byte(TransferLength::_32Bit, true), 1);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "protocol-assertions", should_panic)]
+    fn nonzero_acknowledgement_padding_only_resent_when_strict() {
+        let new_packet = |data: &mut Data| {
+            Packet::new(
+                payload::BeginSession::new(data),
+                TransferLength::_32Bit,
+                Timer::_0,
+            )
+        };
+        let padded_acknowledgement = || {
+            script::acknowledge(TransferLength::_32Bit, Command::BeginSession, 8)
+                .take(19)
+                .chain([0x42])
+        };
+        let response = script::response(TransferLength::_32Bit, Command::BeginSession, b"NINTENDO");
+
+        let mut data = Data::new();
+        let packet = new_packet(&mut data);
+        let (permissive, result) = script::exchange(
+            packet,
+            &mut data,
+            padded_acknowledgement().chain(response.iter().copied()),
+        );
+        assert_ok!(result);
+
+        let mut data = Data::new();
+        data.strict = true;
+        let packet = new_packet(&mut data);
+        let (strict, result) = script::exchange(
+            packet,
+            &mut data,
+            padded_acknowledgement()
+                .chain(script::acknowledge(
+                    TransferLength::_32Bit,
+                    Command::BeginSession,
+                    8,
+                ))
+                .chain(response.iter().copied()),
+        );
+        assert_ok!(result);
+
+        // The packet was sent a second time.
+        assert_eq!(strict.len(), permissive.len() + 20);
+        assert_eq!(strict[..20], strict[20..40]);
+    }
+
+    #[test]
+    fn unexpected_length_not_retryable() {
+        assert!(!error::Receive::UnexpectedLength(0xffff).is_retryable());
+    }
+
+    /// Asserts that a response declaring a length of `0xffff` fails the packet once the capped
+    /// drain is done, instead of draining all 65535 bytes or being requested again.
+    fn assert_maximum_length_fails(transfer_length: TransferLength) {
+        let mut data = Data::new();
+        let packet = Packet::new(
+            payload::EndSession::new(&mut data),
+            transfer_length,
+            Timer::_0,
+        );
+        let adapter = script::acknowledge(transfer_length, Command::EndSession, 0)
+            .chain([
+                0x99,
+                0x66,
+                Command::EndSession as u8 | 0x80,
+                0x00,
+                0xff,
+                0xff,
+            ])
+            .chain(iter::repeat(0x00));
+
+        let (sent, result) = script::exchange(packet, &mut data, adapter);
+
+        let error = assert_err!(result);
+        assert!(
+            matches!(
+                error,
+                Error::Receive {
+                    error: error::Receive::UnexpectedLength(0xffff),
+                    ..
+                }
+            ),
+            "{error:?}"
+        );
+        // The request, the response's header, at most 255 drained bytes, and the checksum and
+        // acknowledgement signal.
+        assert!(sent.len() <= 12 + 8 + 255 + 8, "{} bytes sent", sent.len());
+        assert_eq!(data.stats.retries, 0);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "protocol-assertions", should_panic)]
+    fn maximum_length_fails_sio8() {
+        assert_maximum_length_fails(TransferLength::_8Bit);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "protocol-assertions", should_panic)]
+    fn maximum_length_fails_sio32() {
+        assert_maximum_length_fails(TransferLength::_32Bit);
+    }
+
+    #[test]
+    fn checksum_retryable() {
+        assert!(
+            error::Receive::Checksum {
+                calculated: 0x0010,
+                received: 0x0011,
+            }
+            .is_retryable()
+        );
+    }
+
+    #[test]
+    fn acknowledgement_command_xor() {
+        assert_eq!(acknowledgement_command(Command::BeginSession, true), 0x90);
+    }
+
+    #[test]
+    fn acknowledgement_command_no_xor() {
+        assert_eq!(acknowledgement_command(Command::BeginSession, false), 0x10);
+    }
+
+    #[test]
+    fn acknowledgement_footer_zero() {
+        let mut data = Data::new();
+
+        assert_ok!(acknowledgement_footer(&mut data, 0x00));
+    }
+
+    #[test]
+    fn acknowledgement_footer_extended_disabled() {
+        let mut data = Data::new();
+
+        let error = assert_err!(acknowledgement_footer(&mut data, 0x42));
+        assert_eq!(format!("{error:?}"), "NonZeroFooterCommand(66)");
+        assert_eq!(data.extended_acknowledgement, None);
+    }
+
+    #[test]
+    fn acknowledgement_footer_extended() {
+        let mut data = Data::new();
+        data.extended_ack = true;
+
+        assert_ok!(acknowledgement_footer(&mut data, 0x42));
+        assert_eq!(format!("{:?}", data.extended_acknowledgement), "Some(66)");
+    }
+
+    #[test]
+    fn acknowledgement_footer_extended_cleared() {
+        let mut data = Data::new();
+        data.extended_ack = true;
+
+        assert_ok!(acknowledgement_footer(&mut data, 0x42));
+        assert_ok!(acknowledgement_footer(&mut data, 0x00));
+        assert_eq!(data.extended_acknowledgement, None);
+    }
+
+    /// Exchanges a Begin Session packet whose response is acknowledged with a status of `0x42`.
+    fn exchange_extended_acknowledgement(
+        transfer_length: TransferLength,
+        extended_ack: bool,
+    ) -> (Data, Result<Adapter, Error<payload::BeginSession>>) {
+        let mut data = Data::new();
+        data.extended_ack = extended_ack;
+        let packet = Packet::new(
+            payload::BeginSession::new(&mut data),
+            transfer_length,
+            Timer::_0,
+        );
+        let mut response = script::response(transfer_length, Command::BeginSession, b"NINTENDO");
+        let footer = match transfer_length {
+            TransferLength::_8Bit => response.len() - 2,
+            TransferLength::_32Bit => response.len() - 4,
+        };
+        response[footer + 1] = 0x42;
+        let adapter =
+            script::acknowledge(transfer_length, Command::BeginSession, 8).chain(response);
+
+        let (_, result) = script::exchange(packet, &mut data, adapter);
+        (data, result)
+    }
+
+    fn assert_extended_acknowledgement_rejected(transfer_length: TransferLength) {
+        let (data, result) = exchange_extended_acknowledgement(transfer_length, false);
+
+        let error = assert_err!(result);
+        assert!(
+            matches!(
+                error,
+                Error::Receive {
+                    error: error::Receive::NonZeroFooterCommand(_),
+                    ..
+                }
+            ),
+            "{error:?}"
+        );
+        assert_eq!(data.extended_acknowledgement, None);
+    }
+
+    fn assert_extended_acknowledgement_recorded(transfer_length: TransferLength) {
+        let (data, result) = exchange_extended_acknowledgement(transfer_length, true);
+
+        assert_ok_eq!(result, Adapter::Blue);
+        assert_eq!(format!("{:?}", data.extended_acknowledgement), "Some(66)");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "protocol-assertions", should_panic)]
+    fn extended_acknowledgement_rejected_sio8() {
+        assert_extended_acknowledgement_rejected(TransferLength::_8Bit);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "protocol-assertions", should_panic)]
+    fn extended_acknowledgement_rejected_sio32() {
+        assert_extended_acknowledgement_rejected(TransferLength::_32Bit);
+    }
+
+    #[test]
+    fn extended_acknowledgement_recorded_sio8() {
+        assert_extended_acknowledgement_recorded(TransferLength::_8Bit);
+    }
+
+    #[test]
+    fn extended_acknowledgement_recorded_sio32() {
+        assert_extended_acknowledgement_recorded(TransferLength::_32Bit);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "protocol-assertions", should_panic)]
+    fn receive_error_freezes_capture() {
+        let mut data = Data::new();
+        data.capture.push(capture::Step::Magic, 0x99);
+        data.capture.push(capture::Step::Magic, 0x66);
+        data.capture.push(capture::Step::Checksum, 0x00);
+        data.capture.push(capture::Step::Checksum, 0x11);
+
+        let error = Error::<payload::EndSession>::receive(
+            error::Receive::Checksum {
+                calculated: 0x0010,
+                received: 0x0011,
+            },
+            &data,
+        );
+        data.capture.push(capture::Step::Footer, 0x88);
+
+        assert_eq!(
+            format!("{error:?}"),
+            "Receive { error: Checksum { calculated: 16, received: 17 }, context: [(Magic, 153), (Magic, 102), (Checksum, 0), (Checksum, 17)] }"
+        );
+    }
+
+    fn end_session(data: &mut Data) -> Packet<payload::EndSession> {
+        Packet::new(
+            payload::EndSession::new(data),
+            TransferLength::_8Bit,
+            Timer::_0,
+        )
+    }
+
+    #[test]
+    #[cfg_attr(feature = "protocol-assertions", should_panic)]
+    fn link_stats_checksum_errors() {
+        let mut data = Data::new();
+        let packet = end_session(&mut data);
+        let adapter = script::acknowledge(TransferLength::_8Bit, Command::EndSession, 0)
+            .chain(script::corrupted_response(
+                TransferLength::_8Bit,
+                Command::EndSession,
+                &[],
+            ))
+            .chain(script::corrupted_response(
+                TransferLength::_8Bit,
+                Command::EndSession,
+                &[],
+            ))
+            .chain(script::response(
+                TransferLength::_8Bit,
+                Command::EndSession,
+                &[],
+            ));
+
+        let (_, result) = script::exchange(packet, &mut data, adapter);
+
+        assert_ok_eq!(result, Adapter::Blue);
+        assert_eq!(
+            data.stats,
+            LinkStats {
+                packets_sent: 1,
+                packets_received: 1,
+                receive_errors: 2,
+                checksum_errors: 2,
+                retries: 2,
+                serial_timeouts_recovered: 0,
+                max_serial_gap: 0,
+                max_retry_streak: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn link_stats_serial_gap() {
+        let mut data = Data::new();
+        let mut packet = end_session(&mut data);
+        // Two frames pass before the first transfer completes.
+        assert_ok!(packet.vblank());
+        assert_ok!(packet.vblank());
+        let adapter = script::acknowledge(TransferLength::_8Bit, Command::EndSession, 0).chain(
+            script::response(TransferLength::_8Bit, Command::EndSession, &[]),
+        );
+
+        let (_, result) = script::exchange(packet, &mut data, adapter);
+
+        assert_ok_eq!(result, Adapter::Blue);
+        assert_eq!(data.stats.serial_timeouts_recovered, 1);
+        assert_eq!(data.stats.max_serial_gap, 2);
+    }
+
+    #[test]
+    fn serial_cycles_sio32() {
+        let mut data = Data::new();
+        let mut send_data = ArrayVec::new();
+        send_data.write(&[0xaa; 254]);
+        // The longest packets possible in both directions, so that every data path is taken.
+        let packet = Packet::new(
+            payload::TransferData::new(&mut data, socket::Id::P2P, &mut send_data),
+            TransferLength::_32Bit,
+            Timer::_0,
+        );
+        let response = [0xff; 255];
+        let adapter =
+            script::acknowledge(TransferLength::_32Bit, Command::TransferData, 255).chain(
+                script::response(TransferLength::_32Bit, Command::TransferData, &response),
+            );
+
+        let (_, result, cycles) = script::exchange_timed(packet, &mut data, adapter);
+
+        assert_ok_eq!(result, Adapter::Blue);
+        assert_eq!(data.data.as_slice(), response.as_slice());
+        guardrails::assert_serial_cycles(cycles);
+    }
+}
@@ -30,6 +30,7 @@ pub(in crate::driver::active::flow) enum Response {
 impl Payload for AcceptConnection {
     type Response<'a> = Response;
     type Error = Error;
+    const RESPONSES: &'static [Command] = &[Command::WaitForTelephoneCall, Command::CommandError];
 
     fn parse<'a>(self, data: &'a Data) -> Result<Self::Response<'a>, Self::Error> {
         match data.command {
@@ -55,7 +56,7 @@ impl Payload for AcceptConnection {
             }
             unexpected => Err(Error::UnsupportedCommand {
                 received: unexpected,
-                expected: &[Command::WaitForTelephoneCall, Command::CommandError],
+                expected: Self::RESPONSES,
             }),
         }
     }
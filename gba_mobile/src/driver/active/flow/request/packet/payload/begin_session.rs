@@ -44,7 +44,7 @@ impl Display for Error {
         match self {
             Self::Handshake { byte, index } => write!(
                 formatter,
-                "unexpected byte {byte:#04x} at index {index}; expected {:04x}",
+                "unexpected byte {byte:#04x} at index {index}; expected {:#04x}",
                 HANDSHAKE[*index],
             ),
             Self::Payload(_) => formatter.write_str("payload error"),
@@ -70,6 +70,7 @@ impl From<super::Error> for Error {
 impl Payload for BeginSession {
     type Response<'a> = Response;
     type Error = Error;
+    const RESPONSES: &'static [Command] = &[Command::BeginSession, Command::CommandError];
 
     fn parse<'a>(self, data: &'a Data) -> Result<Self::Response<'a>, Self::Error> {
         match data.command {
@@ -110,7 +111,7 @@ impl Payload for BeginSession {
             }
             unexpected => Err(super::Error::UnsupportedCommand {
                 received: unexpected,
-                expected: &[Command::BeginSession, Command::CommandError],
+                expected: Self::RESPONSES,
             }
             .into()),
         }
@@ -69,6 +69,7 @@ impl From<super::Error> for Error {
 impl Payload for CloseTcp {
     type Response<'a> = Response;
     type Error = Error;
+    const RESPONSES: &'static [Command] = &[Command::CloseTcpConnection, Command::CommandError];
 
     fn parse<'a>(self, data: &'a Data) -> Result<Self::Response<'a>, Self::Error> {
         match data.command {
@@ -107,7 +108,7 @@ impl Payload for CloseTcp {
             }
             unexpected => Err(super::Error::UnsupportedCommand {
                 received: unexpected,
-                expected: &[Command::CloseTcpConnection, Command::CommandError],
+                expected: Self::RESPONSES,
             }
             .into()),
         }
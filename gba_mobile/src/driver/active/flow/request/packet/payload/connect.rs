@@ -1,6 +1,6 @@
 use super::{super::Data, Error, Payload, command_error};
 use crate::{
-    Adapter, ArrayVec, Digit,
+    Adapter, ArrayVec, digit,
     driver::{Command, command},
 };
 use core::{iter, marker::PhantomData};
@@ -14,7 +14,7 @@ impl Connect {
     pub(in crate::driver::active::flow) fn new(
         data: &mut Data,
         adapter: Adapter,
-        digits: &ArrayVec<Digit, 32>,
+        digits: &digit::NumberBuf,
     ) -> Self {
         data.command = Command::DialTelephone;
         data.data = unsafe {
@@ -39,6 +39,7 @@ pub(in crate::driver::active::flow) enum Response {
 impl Payload for Connect {
     type Response<'a> = Response;
     type Error = Error;
+    const RESPONSES: &'static [Command] = &[Command::DialTelephone, Command::CommandError];
 
     fn parse<'a>(self, data: &'a Data) -> Result<Self::Response<'a>, Self::Error> {
         match data.command {
@@ -66,7 +67,7 @@ impl Payload for Connect {
             }
             unexpected => Err(Error::UnsupportedCommand {
                 received: unexpected,
-                expected: &[Command::DialTelephone, Command::CommandError],
+                expected: Self::RESPONSES,
             }),
         }
     }
@@ -89,6 +89,7 @@ impl TryFrom<u8> for Status {
 impl Payload for ConnectionStatus {
     type Response<'a> = Response;
     type Error = Error;
+    const RESPONSES: &'static [Command] = &[Command::TelephoneStatus, Command::CommandError];
 
     fn parse<'a>(self, data: &'a Data) -> Result<Self::Response<'a>, Self::Error> {
         match data.command {
@@ -114,7 +115,7 @@ impl Payload for ConnectionStatus {
             }
             unexpected => Err(super::Error::UnsupportedCommand {
                 received: unexpected,
-                expected: &[Command::TelephoneStatus, Command::CommandError],
+                expected: Self::RESPONSES,
             }
             .into()),
         }
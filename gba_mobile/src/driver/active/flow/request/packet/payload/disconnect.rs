@@ -1,6 +1,7 @@
 use super::{super::Data, Error, Payload, command_error};
 use crate::{
     ArrayVec,
+    connection::DisconnectReason,
     driver::{Command, command},
 };
 use core::marker::PhantomData;
@@ -22,14 +23,15 @@ impl Disconnect {
 }
 
 impl Payload for Disconnect {
-    type Response<'a> = ();
+    type Response<'a> = DisconnectReason;
     type Error = Error;
+    const RESPONSES: &'static [Command] = &[Command::HangUpTelephone, Command::CommandError];
 
     fn parse<'a>(self, data: &'a Data) -> Result<Self::Response<'a>, Self::Error> {
         match data.command {
             Command::HangUpTelephone => {
                 if data.data.is_empty() {
-                    Ok(())
+                    Ok(DisconnectReason::Local)
                 } else {
                     Err(Error::InvalidLength {
                         command: Command::HangUpTelephone,
@@ -41,15 +43,16 @@ impl Payload for Disconnect {
             Command::CommandError => {
                 let error = command_error::parse(&data.data)?;
                 match error {
+                    // The other side hung up first, or at the same time as us.
                     command::Error::HangUpTelephone(
                         command::error::hang_up_telephone::Error::NotConnected,
-                    ) => Ok(()),
+                    ) => Ok(DisconnectReason::MutualOrRemoteFirst),
                     _ => Err(Error::UnexpectedCommandError(error)),
                 }
             }
             unexpected => Err(Error::UnsupportedCommand {
                 received: unexpected,
-                expected: &[Command::HangUpTelephone, Command::CommandError],
+                expected: Self::RESPONSES,
             }),
         }
     }
@@ -34,11 +34,20 @@ pub(in crate::driver::active::flow) enum Response {
 impl<const MAX_LEN: usize> Payload for Dns<MAX_LEN> {
     type Response<'a> = Response;
     type Error = Error;
+    const RESPONSES: &'static [Command] = &[Command::DnsQuery, Command::CommandError];
 
     fn parse<'a>(self, data: &'a Data) -> Result<Self::Response<'a>, Self::Error> {
         match data.command {
             Command::DnsQuery => addr::parse(data.data.iter().copied(), Command::DnsQuery, 0, 4)
-                .map(|ip| Response::Success(ip)),
+                .map(|ip| {
+                    // Some adapters report a failed lookup as the broadcast address instead of as
+                    // a command error.
+                    if ip == Ipv4Addr::BROADCAST {
+                        Response::NotFound
+                    } else {
+                        Response::Success(ip)
+                    }
+                }),
             Command::CommandError => {
                 let error = command_error::parse(&data.data)?;
                 match error {
@@ -50,7 +59,7 @@ impl<const MAX_LEN: usize> Payload for Dns<MAX_LEN> {
             }
             unexpected => Err(Error::UnsupportedCommand {
                 received: unexpected,
-                expected: &[Command::DnsQuery, Command::CommandError],
+                expected: Self::RESPONSES,
             }),
         }
     }
@@ -21,6 +21,8 @@ impl EnableSio32 {
 impl Payload for EnableSio32 {
     type Response<'a> = TransferLength;
     type Error = Error;
+    const RESPONSES: &'static [Command] =
+        &[Command::Sio32Mode, Command::Reset, Command::CommandError];
 
     fn parse<'a>(self, data: &'a Data) -> Result<Self::Response<'a>, Self::Error> {
         match data.command {
@@ -50,7 +52,7 @@ impl Payload for EnableSio32 {
                 .and_then(|error| Err(Error::UnexpectedCommandError(error))),
             unexpected => Err(Error::UnsupportedCommand {
                 received: unexpected,
-                expected: &[Command::Sio32Mode, Command::Reset, Command::CommandError],
+                expected: Self::RESPONSES,
             }),
         }
     }
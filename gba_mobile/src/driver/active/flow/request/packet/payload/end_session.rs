@@ -21,6 +21,7 @@ impl EndSession {
 impl Payload for EndSession {
     type Response<'a> = ();
     type Error = Error;
+    const RESPONSES: &'static [Command] = &[Command::EndSession, Command::CommandError];
 
     fn parse<'a>(self, data: &'a Data) -> Result<Self::Response<'a>, Self::Error> {
         match data.command {
@@ -39,7 +40,7 @@ impl Payload for EndSession {
                 .and_then(|error| Err(Error::UnexpectedCommandError(error))),
             unexpected => Err(Error::UnsupportedCommand {
                 received: unexpected,
-                expected: &[Command::EndSession, Command::CommandError],
+                expected: Self::RESPONSES,
             }),
         }
     }
@@ -50,6 +50,7 @@ pub(in crate::driver::active::flow) enum Response {
 impl Payload for Login {
     type Response<'a> = Response;
     type Error = Error;
+    const RESPONSES: &'static [Command] = &[Command::PppLogin, Command::CommandError];
 
     fn parse<'a>(self, data: &'a Data) -> Result<Self::Response<'a>, Self::Error> {
         match data.command {
@@ -76,7 +77,7 @@ impl Payload for Login {
             }
             unexpected => Err(Error::UnsupportedCommand {
                 received: unexpected,
-                expected: &[Command::PppLogin, Command::CommandError],
+                expected: Self::RESPONSES,
             }),
         }
     }
@@ -0,0 +1,60 @@
+use super::{super::Data, Error, Payload, command_error};
+use crate::{
+    ArrayVec,
+    driver::{Command, command},
+};
+use core::marker::PhantomData;
+
+#[derive(Debug)]
+pub(in crate::driver::active::flow) struct Logout {
+    _private: PhantomData<()>,
+}
+
+impl Logout {
+    pub(in crate::driver::active::flow) fn new(data: &mut Data) -> Self {
+        data.command = Command::PppLogout;
+        data.data = ArrayVec::new();
+
+        Self {
+            _private: PhantomData,
+        }
+    }
+}
+
+impl Payload for Logout {
+    type Response<'a> = ();
+    type Error = Error;
+    const RESPONSES: &'static [Command] = &[Command::PppLogout, Command::CommandError];
+
+    fn parse<'a>(self, data: &'a Data) -> Result<Self::Response<'a>, Self::Error> {
+        match data.command {
+            Command::PppLogout => {
+                if data.data.is_empty() {
+                    Ok(())
+                } else {
+                    Err(Error::InvalidLength {
+                        command: Command::PppLogout,
+                        received: data.data.len(),
+                        expected: 0,
+                    })
+                }
+            }
+            Command::CommandError => {
+                let error = command_error::parse(&data.data)?;
+                match error {
+                    // The session has already ended on the adapter's side, which is what logging
+                    // out would have achieved anyway.
+                    command::Error::PppLogout(
+                        command::error::ppp_logout::Error::NotLoggedIn
+                        | command::error::ppp_logout::Error::NotInCall,
+                    ) => Ok(()),
+                    _ => Err(Error::UnexpectedCommandError(error)),
+                }
+            }
+            unexpected => Err(Error::UnsupportedCommand {
+                received: unexpected,
+                expected: Self::RESPONSES,
+            }),
+        }
+    }
+}
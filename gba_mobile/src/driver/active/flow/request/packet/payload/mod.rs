@@ -9,6 +9,7 @@ pub(in crate::driver::active::flow) mod dns;
 pub(in crate::driver::active::flow) mod enable_sio32;
 pub(in crate::driver::active::flow) mod end_session;
 pub(in crate::driver::active::flow) mod login;
+pub(in crate::driver::active::flow) mod logout;
 pub(in crate::driver::active::flow) mod open_tcp;
 pub(in crate::driver::active::flow) mod open_udp;
 pub(in crate::driver::active::flow) mod read_config;
@@ -33,6 +34,7 @@ pub(in crate::driver::active::flow) use dns::Dns;
 pub(in crate::driver::active::flow) use enable_sio32::EnableSio32;
 pub(in crate::driver::active::flow) use end_session::EndSession;
 pub(in crate::driver::active::flow) use login::Login;
+pub(in crate::driver::active::flow) use logout::Logout;
 pub(in crate::driver::active::flow) use open_tcp::OpenTcp;
 pub(in crate::driver::active::flow) use open_udp::OpenUdp;
 pub(in crate::driver::active::flow) use read_config::ReadConfig;
@@ -41,11 +43,182 @@ pub(in crate::driver::active::flow) use transfer_data::TransferData;
 pub(in crate::driver::active::flow) use write_config::WriteConfig;
 
 use super::Data;
+use crate::driver::Command;
 use core::fmt::Debug;
 
 pub(in crate::driver) trait Payload: Debug {
     type Response<'a>;
     type Error: core::error::Error + Clone + 'static;
 
+    /// The commands the adapter may respond with.
+    ///
+    /// A received packet with any other command is rejected as soon as its header is received.
+    const RESPONSES: &'static [Command];
+
     fn parse<'a>(self, data: &'a Data) -> Result<Self::Response<'a>, Self::Error>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::Data, BeginSession, ConnectionStatus, Dns, EndSession, Payload, TransferData, dns,
+    };
+    use crate::{ArrayVec, driver::Command, socket};
+    use alloc::format;
+    use claims::assert_ok;
+    use core::{iter, net::Ipv4Addr};
+    use gba_test::test;
+
+    /// Byte values that are likely to trigger edge cases when substituted into a response.
+    const INTERESTING: [u8; 3] = [0x00, 0xff, 0xd2];
+
+    fn data<Bytes>(command: Command, bytes: Bytes) -> Data
+    where
+        Bytes: IntoIterator<Item = u8>,
+    {
+        let mut data = Data::new();
+        data.command = command;
+        data.data = assert_ok!(ArrayVec::try_from_iter(bytes));
+        data
+    }
+
+    /// Parses deterministic single mutations of a known-good response.
+    ///
+    /// Every byte is replaced with each interesting value and each of its single bit flips, the
+    /// response is truncated at every length and extended by a byte, and the command is replaced
+    /// with every known command. Parsing must never panic, and every error must be displayable.
+    ///
+    /// Returns the number of mutants that parsed successfully.
+    fn parse_mutants<Payload, NewPayload>(
+        new_payload: NewPayload,
+        command: Command,
+        bytes: &[u8],
+    ) -> usize
+    where
+        Payload: self::Payload,
+        NewPayload: Fn() -> Payload,
+    {
+        let mut successes = 0;
+        let mut parse = |data: Data| match new_payload().parse(&data) {
+            Ok(_) => successes += 1,
+            Err(error) => {
+                let _ = format!("{error} {error:?}");
+            }
+        };
+
+        for index in 0..bytes.len() {
+            let values = INTERESTING
+                .into_iter()
+                .chain((0..8).map(|bit| bytes[index] ^ (1 << bit)));
+            for value in values {
+                parse(data(
+                    command,
+                    bytes
+                        .iter()
+                        .enumerate()
+                        .map(|(i, &byte)| if i == index { value } else { byte }),
+                ));
+            }
+        }
+        for len in 0..bytes.len() {
+            parse(data(command, bytes[..len].iter().copied()));
+        }
+        parse(data(command, bytes.iter().copied().chain(iter::once(0xd2))));
+        for byte in 0..=0x7f {
+            if let Ok(command) = Command::try_from(byte) {
+                parse(data(command, bytes.iter().copied()));
+            }
+        }
+
+        successes
+    }
+
+    fn parses<Payload>(payload: Payload, command: Command, bytes: &[u8]) -> bool
+    where
+        Payload: self::Payload,
+    {
+        payload.parse(&data(command, bytes.iter().copied())).is_ok()
+    }
+
+    #[test]
+    fn begin_session_mutants() {
+        let response = *b"NINTENDO";
+        let new_payload = || BeginSession::new(&mut Data::new());
+
+        assert!(parses(new_payload(), Command::BeginSession, &response));
+        // Every change to the handshake is rejected, leaving only the mutant that "replaces" the
+        // command with itself.
+        assert_eq!(
+            parse_mutants(new_payload, Command::BeginSession, &response),
+            1
+        );
+    }
+
+    #[test]
+    fn begin_session_command_error_mutants() {
+        let response = [Command::BeginSession as u8, 0x01];
+        let new_payload = || BeginSession::new(&mut Data::new());
+
+        assert!(parses(new_payload(), Command::CommandError, &response));
+        parse_mutants(new_payload, Command::CommandError, &response);
+    }
+
+    #[test]
+    fn end_session_request_is_empty() {
+        let mut data = Data::new();
+        EndSession::new(&mut data);
+
+        assert_eq!(data.command, Command::EndSession);
+        assert!(data.data.is_empty());
+    }
+
+    #[test]
+    fn end_session_mutants() {
+        let new_payload = || EndSession::new(&mut Data::new());
+
+        assert!(parses(new_payload(), Command::EndSession, &[]));
+        parse_mutants(new_payload, Command::EndSession, &[]);
+    }
+
+    #[test]
+    fn connection_status_mutants() {
+        let response = [0x04, 0x4d, 0x00];
+        let new_payload = || ConnectionStatus::new(&mut Data::new());
+
+        assert!(parses(new_payload(), Command::TelephoneStatus, &response));
+        parse_mutants(new_payload, Command::TelephoneStatus, &response);
+    }
+
+    #[test]
+    fn dns_success() {
+        let response = assert_ok!(
+            Dns::new(&mut Data::new(), &ArrayVec::<u8, 11>::new())
+                .parse(&data(Command::DnsQuery, [93, 184, 216, 34]))
+        );
+
+        assert!(matches!(
+            response,
+            dns::Response::Success(ip) if ip == Ipv4Addr::new(93, 184, 216, 34)
+        ));
+    }
+
+    #[test]
+    fn dns_broadcast_not_found() {
+        let response = assert_ok!(
+            Dns::new(&mut Data::new(), &ArrayVec::<u8, 11>::new())
+                .parse(&data(Command::DnsQuery, [0xff; 4]))
+        );
+
+        assert!(matches!(response, dns::Response::NotFound));
+    }
+
+    #[test]
+    fn transfer_data_mutants() {
+        let response = [0x00, 0x01, 0x02, 0x03];
+        let new_payload =
+            || TransferData::new(&mut Data::new(), socket::Id(0), &mut ArrayVec::new());
+
+        assert!(parses(new_payload(), Command::TransferData, &response));
+        parse_mutants(new_payload, Command::TransferData, &response);
+    }
+}
@@ -40,6 +40,7 @@ pub(in crate::driver::active::flow) enum Response {
 impl Payload for OpenTcp {
     type Response<'a> = Response;
     type Error = Error;
+    const RESPONSES: &'static [Command] = &[Command::OpenTcpConnection, Command::CommandError];
 
     fn parse<'a>(self, data: &'a Data) -> Result<Self::Response<'a>, Self::Error> {
         match data.command {
@@ -67,7 +68,7 @@ impl Payload for OpenTcp {
             }
             unexpected => Err(Error::UnsupportedCommand {
                 received: unexpected,
-                expected: &[Command::OpenTcpConnection, Command::CommandError],
+                expected: Self::RESPONSES,
             }),
         }
     }
@@ -30,6 +30,7 @@ impl OpenUdp {
 impl Payload for OpenUdp {
     type Response<'a> = socket::Id;
     type Error = Error;
+    const RESPONSES: &'static [Command] = &[Command::OpenUdpConnection, Command::CommandError];
 
     fn parse<'a>(self, data: &'a Data) -> Result<Self::Response<'a>, Self::Error> {
         match data.command {
@@ -48,7 +49,7 @@ impl Payload for OpenUdp {
                 .and_then(|error| Err(Error::UnexpectedCommandError(error))),
             unexpected => Err(Error::UnsupportedCommand {
                 received: unexpected,
-                expected: &[Command::OpenUdpConnection, Command::CommandError],
+                expected: Self::RESPONSES,
             }),
         }
     }
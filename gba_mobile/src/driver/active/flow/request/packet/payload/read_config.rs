@@ -57,6 +57,7 @@ impl From<super::Error> for Error {
 impl Payload for ReadConfig {
     type Response<'a> = &'a [u8];
     type Error = Error;
+    const RESPONSES: &'static [Command] = &[Command::ReadConfigurationData, Command::CommandError];
 
     fn parse<'a>(self, data: &'a Data) -> Result<Self::Response<'a>, Self::Error> {
         match data.command {
@@ -93,7 +94,7 @@ impl Payload for ReadConfig {
                 .and_then(|error| Err(super::Error::UnexpectedCommandError(error).into())),
             unexpected => Err(super::Error::UnsupportedCommand {
                 received: unexpected,
-                expected: &[Command::ReadConfigurationData, Command::CommandError],
+                expected: Self::RESPONSES,
             }
             .into()),
         }
@@ -83,6 +83,11 @@ pub(in crate::driver::active::flow) enum Response {
 impl Payload for TransferData {
     type Response<'a> = Response;
     type Error = Error;
+    const RESPONSES: &'static [Command] = &[
+        Command::TransferData,
+        Command::ConnectionClosed,
+        Command::CommandError,
+    ];
 
     fn parse<'a>(self, data: &'a Data) -> Result<Self::Response<'a>, Self::Error> {
         match data.command {
@@ -116,11 +121,7 @@ impl Payload for TransferData {
             }
             unexpected => Err(super::Error::UnsupportedCommand {
                 received: unexpected,
-                expected: &[
-                    Command::TransferData,
-                    Command::ConnectionClosed,
-                    Command::CommandError,
-                ],
+                expected: Self::RESPONSES,
             }
             .into()),
         }
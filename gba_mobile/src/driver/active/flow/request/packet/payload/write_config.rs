@@ -71,6 +71,7 @@ impl From<super::Error> for Error {
 impl Payload for WriteConfig {
     type Response<'a> = ();
     type Error = Error;
+    const RESPONSES: &'static [Command] = &[Command::WriteConfigurationData, Command::CommandError];
 
     fn parse<'a>(self, data: &'a Data) -> Result<Self::Response<'a>, Self::Error> {
         match data.command {
@@ -129,7 +130,7 @@ impl Payload for WriteConfig {
                 .and_then(|error| Err(super::Error::UnexpectedCommandError(error).into())),
             unexpected => Err(super::Error::UnsupportedCommand {
                 received: unexpected,
-                expected: &[Command::WriteConfigurationData, Command::CommandError],
+                expected: Self::RESPONSES,
             }
             .into()),
         }
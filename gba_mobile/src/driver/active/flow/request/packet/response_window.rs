@@ -0,0 +1,69 @@
+use super::Timeout;
+use crate::driver::frames;
+
+/// The time the adapter has to start responding to a packet.
+///
+/// This is kept by the packet rather than by the step waiting for the response, since that step is
+/// recreated every time a response is rejected and retried. Otherwise an adapter that never
+/// answers, combined with line noise that is mistaken for a response, could keep a packet waiting
+/// indefinitely.
+#[derive(Debug)]
+pub(super) struct ResponseWindow {
+    frame: u16,
+}
+
+impl ResponseWindow {
+    pub(super) fn new() -> Self {
+        Self { frame: 0 }
+    }
+
+    /// Counts a frame spent waiting for the response.
+    pub(super) fn vblank(&mut self) -> Result<(), Timeout> {
+        if self.frame > frames::FIFTEEN_SECONDS {
+            Err(Timeout::Packet)
+        } else {
+            self.frame += 1;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ResponseWindow, frames};
+    use alloc::format;
+    use claims::{assert_err, assert_ok};
+    use gba_test::test;
+
+    #[test]
+    fn within_window() {
+        let mut window = ResponseWindow::new();
+
+        for _ in 0..=frames::FIFTEEN_SECONDS {
+            assert_ok!(window.vblank());
+        }
+    }
+
+    #[test]
+    fn expires() {
+        let mut window = ResponseWindow::new();
+
+        for _ in 0..=frames::FIFTEEN_SECONDS {
+            assert_ok!(window.vblank());
+        }
+        let timeout = assert_err!(window.vblank());
+        assert_eq!(format!("{timeout:?}"), "Packet");
+    }
+
+    #[test]
+    fn stays_expired() {
+        let mut window = ResponseWindow::new();
+
+        for _ in 0..=frames::FIFTEEN_SECONDS {
+            assert_ok!(window.vblank());
+        }
+        for _ in 0..1000 {
+            assert_err!(window.vblank());
+        }
+    }
+}
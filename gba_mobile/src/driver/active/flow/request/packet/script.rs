@@ -0,0 +1,350 @@
+//! A scripted adapter for driving packets through the SIO8 and SIO32 steps in tests.
+
+use super::{Data, Error, Packet, Payload};
+use crate::{
+    Generation, Timer, config, dns,
+    driver::{self, Adapter, Command, Driver, active, active::Active},
+    guardrails,
+    mmio::serial::{Control, SIOCNT, SIODATA8, SIODATA32, TransferLength},
+    socket,
+};
+use alloc::vec::Vec;
+use core::iter;
+use either::Either;
+
+/// The number of bytes in a packet with `length` bytes of data, up to and including its checksum.
+///
+/// In SIO32 mode, the data is padded so that the checksum ends a word.
+fn packet_length(transfer_length: TransferLength, length: usize) -> usize {
+    match transfer_length {
+        TransferLength::_8Bit => 8 + length,
+        TransferLength::_32Bit => (8 + length).div_ceil(4) * 4,
+    }
+}
+
+/// The device's half of the acknowledgement signal, padded to a full transfer.
+fn footer(transfer_length: TransferLength, device: u8, command: u8) -> impl Iterator<Item = u8> {
+    [device, command, 0x00, 0x00]
+        .into_iter()
+        .take(match transfer_length {
+            TransferLength::_8Bit => 2,
+            TransferLength::_32Bit => 4,
+        })
+}
+
+/// The bytes the adapter sends while receiving a packet with `length` bytes of data, ending with
+/// its acknowledgement of `command`.
+pub(in crate::driver) fn acknowledge(
+    transfer_length: TransferLength,
+    command: Command,
+    length: usize,
+) -> impl Iterator<Item = u8> {
+    iter::repeat_n(0xd2, packet_length(transfer_length, length)).chain(footer(
+        transfer_length,
+        0x88,
+        command as u8 ^ 0x80,
+    ))
+}
+
+/// A packet as sent by the adapter, followed by its half of the acknowledgement signal.
+pub(in crate::driver) fn response(
+    transfer_length: TransferLength,
+    command: Command,
+    bytes: &[u8],
+) -> Vec<u8> {
+    let command = command as u8 | 0x80;
+    let checksum = bytes
+        .iter()
+        .fold(command as u16 + bytes.len() as u16, |checksum, &byte| {
+            checksum.wrapping_add(byte as u16)
+        });
+    let padding = packet_length(transfer_length, bytes.len()) - 8 - bytes.len();
+    [0x99, 0x66, command, 0x00, 0x00, bytes.len() as u8]
+        .into_iter()
+        .chain(bytes.iter().copied())
+        .chain(iter::repeat_n(0x00, padding))
+        .chain([(checksum >> 8) as u8, checksum as u8])
+        .chain(footer(transfer_length, 0x88, 0x00))
+        .collect()
+}
+
+/// Like [`response()`], but with a checksum that doesn't match the packet.
+pub(in crate::driver::active) fn corrupted_response(
+    transfer_length: TransferLength,
+    command: Command,
+    bytes: &[u8],
+) -> Vec<u8> {
+    let mut response = response(transfer_length, command, bytes);
+    let checksum = response.len() - footer(transfer_length, 0x88, 0x00).count() - 1;
+    response[checksum] ^= 0x01;
+    response
+}
+
+/// Completes one transfer, answering with the next bytes of `adapter`.
+///
+/// Returns `false`, without transferring anything, if `adapter` has no bytes left.
+///
+/// # Panics
+///
+/// Panics if `adapter` runs out of bytes partway through an SIO32 word.
+pub(super) fn transfer<Bytes>(
+    transfer_length: TransferLength,
+    adapter: &mut Bytes,
+    sent: &mut Vec<u8>,
+) -> bool
+where
+    Bytes: Iterator<Item = u8>,
+{
+    match transfer_length {
+        TransferLength::_8Bit => {
+            let Some(byte) = adapter.next() else {
+                return false;
+            };
+            sent.push(unsafe { SIODATA8.read_volatile() });
+            unsafe { SIODATA8.write_volatile(byte) };
+        }
+        TransferLength::_32Bit => {
+            let mut bytes = [0x00; 4];
+            for (index, slot) in bytes.iter_mut().enumerate() {
+                match adapter.next() {
+                    Some(byte) => *slot = byte,
+                    None if index == 0 => return false,
+                    None => panic!("the adapter ran out of bytes partway through a word"),
+                }
+            }
+            sent.extend(unsafe { SIODATA32.read_volatile() }.to_be_bytes());
+            unsafe { SIODATA32.write_volatile(u32::from_be_bytes(bytes)) };
+        }
+    }
+    true
+}
+
+/// Exchanges `packet` with an adapter answering each transfer with the next bytes of `adapter`.
+///
+/// The adapter is assumed to answer every poll while the driver waits for a response, so a frame
+/// passes only while waiting. Returns the bytes sent by the driver, along with the adapter that
+/// acknowledged the response.
+///
+/// # Panics
+///
+/// Panics if `adapter` runs out of bytes before the exchange ends, or if the packet times out.
+pub(in crate::driver::active) fn exchange<Payload, Bytes>(
+    packet: Packet<Payload>,
+    data: &mut Data,
+    adapter: Bytes,
+) -> (Vec<u8>, Result<Adapter, Error<Payload>>)
+where
+    Payload: self::Payload,
+    Bytes: IntoIterator<Item = u8>,
+{
+    let (sent, result, _) = exchange_timed(packet, data, adapter);
+    (sent, result)
+}
+
+/// Like [`exchange()`], but also measures the most cycles spent in a single call to `serial()`.
+pub(in crate::driver::active) fn exchange_timed<Payload, Bytes>(
+    mut packet: Packet<Payload>,
+    data: &mut Data,
+    adapter: Bytes,
+) -> (Vec<u8>, Result<Adapter, Error<Payload>>, u16)
+where
+    Payload: self::Payload,
+    Bytes: IntoIterator<Item = u8>,
+{
+    let mut adapter = adapter.into_iter();
+    let mut sent = Vec::new();
+    let mut longest = 0;
+    loop {
+        if packet.state.is_waiting_for_receive() {
+            // Idle bytes are sent to poll the adapter on the next frame.
+            if let Err(timeout) = packet.vblank() {
+                panic!("packet timed out: {timeout}");
+            }
+        } else {
+            packet.timer(data);
+        }
+        if !transfer(packet.state.transfer_length(), &mut adapter, &mut sent) {
+            panic!("the adapter ran out of bytes before the exchange ended");
+        }
+        let (result, cycles) = guardrails::cycles(|| packet.serial(Timer::_0, data));
+        longest = longest.max(cycles);
+        match result {
+            Ok(Either::Left(next)) => packet = next,
+            Ok(Either::Right(response)) => return (sent, Ok(response.adapter), longest),
+            Err(error) => return (sent, Err(error), longest),
+        }
+    }
+}
+
+/// Runs `active` against an adapter answering each transfer with the next bytes of `adapter`.
+///
+/// Like [`exchange()`], but with whole flows scheduled by the driver. A frame passes whenever no
+/// transfer or timer is pending. Returns the bytes sent by the driver, or the first error a flow
+/// fails with.
+///
+/// # Panics
+///
+/// Panics if the driver times out, or if it goes three seconds without starting a transfer.
+pub(in crate::driver) fn run<Socket1, Socket2, Dns, Config, Bytes>(
+    active: &mut Active<Socket1, Socket2, Dns, Config>,
+    socket_1: &mut Socket1,
+    socket_2: &mut Socket2,
+    dns: &mut Dns,
+    config: &mut Config,
+    adapter: Bytes,
+) -> Result<Vec<u8>, active::Error<Socket1, Socket2, Dns, Config>>
+where
+    Socket1: socket::Slot,
+    Socket2: socket::Slot,
+    Dns: dns::Mode,
+    Config: config::Mode,
+    Bytes: IntoIterator<Item = u8>,
+{
+    let mut adapter = adapter.into_iter().peekable();
+    let mut sent = Vec::new();
+    while adapter.peek().is_some() {
+        unsafe { SIOCNT.write_volatile(Control::new()) };
+        let mut frames = 0;
+        while !unsafe { SIOCNT.read_volatile() }.is_started() {
+            if Timer::_0.is_running() {
+                active.timer(Timer::_0);
+            } else if frames > crate::driver::frames::THREE_SECONDS {
+                panic!("no transfer was started");
+            } else {
+                if let Err(timeout) = active.vblank(
+                    Timer::_0,
+                    Generation::new(),
+                    socket_1,
+                    socket_2,
+                    dns,
+                    config,
+                ) {
+                    panic!("driver timed out: {timeout}");
+                }
+                frames += 1;
+            }
+        }
+        transfer(active.state.transfer_length, &mut adapter, &mut sent);
+        active.serial(
+            Timer::_0,
+            Generation::new(),
+            socket_1,
+            socket_2,
+            dns,
+            config,
+        )?;
+    }
+    Ok(sent)
+}
+
+/// Runs `active` for exactly `frames` frames, answering each transfer started along the way with
+/// the next bytes of `adapter`.
+///
+/// Like [`run()`], but stopping after a fixed number of frames instead of once `adapter` runs out,
+/// so that the driver's frame counters can be checked. Returns the bytes sent by the driver, or the
+/// first error a flow fails with.
+///
+/// # Panics
+///
+/// Panics if the driver times out, or if `adapter` runs out of bytes while a transfer is waiting.
+pub(in crate::driver) fn run_frames<Socket1, Socket2, Dns, Config, Bytes>(
+    active: &mut Active<Socket1, Socket2, Dns, Config>,
+    socket_1: &mut Socket1,
+    socket_2: &mut Socket2,
+    dns: &mut Dns,
+    config: &mut Config,
+    frames: u32,
+    adapter: Bytes,
+) -> Result<Vec<u8>, active::Error<Socket1, Socket2, Dns, Config>>
+where
+    Socket1: socket::Slot,
+    Socket2: socket::Slot,
+    Dns: dns::Mode,
+    Config: config::Mode,
+    Bytes: IntoIterator<Item = u8>,
+{
+    let mut adapter = adapter.into_iter();
+    let mut sent = Vec::new();
+    // Any transfer started before now has already been answered.
+    unsafe { SIOCNT.write_volatile(SIOCNT.read_volatile().start(false)) };
+    for _ in 0..frames {
+        if let Err(timeout) = active.vblank(
+            Timer::_0,
+            Generation::new(),
+            socket_1,
+            socket_2,
+            dns,
+            config,
+        ) {
+            panic!("driver timed out: {timeout}");
+        }
+        // Everything scheduled during this frame is exchanged before the next one.
+        loop {
+            if unsafe { SIOCNT.read_volatile() }.is_started() {
+                // Completing a transfer clears its start bit.
+                unsafe { SIOCNT.write_volatile(SIOCNT.read_volatile().start(false)) };
+                if !transfer(active.state.transfer_length, &mut adapter, &mut sent) {
+                    panic!("the adapter ran out of bytes while a transfer was waiting");
+                }
+                active.serial(
+                    Timer::_0,
+                    Generation::new(),
+                    socket_1,
+                    socket_2,
+                    dns,
+                    config,
+                )?;
+            } else if Timer::_0.is_running() {
+                active.timer(Timer::_0);
+            } else {
+                break;
+            }
+        }
+    }
+    Ok(sent)
+}
+
+/// Runs `driver` against an adapter answering each transfer with the next bytes of `adapter`.
+///
+/// Like [`run()`], but through the driver's interrupt handlers, so that whatever the driver does
+/// when a link ends is also run. Errors are left for the driver to record. Returns the bytes sent
+/// by the driver.
+///
+/// # Panics
+///
+/// Panics if the driver goes three seconds without starting a transfer.
+pub(in crate::driver) fn run_driver<Socket1, Socket2, Dns, Config, Bytes>(
+    driver: &mut Driver<Socket1, Socket2, Dns, Config>,
+    adapter: Bytes,
+) -> Vec<u8>
+where
+    Socket1: socket::Slot,
+    Socket2: socket::Slot,
+    Dns: dns::Mode,
+    Config: config::Mode,
+    Bytes: IntoIterator<Item = u8>,
+{
+    let mut adapter = adapter.into_iter().peekable();
+    let mut sent = Vec::new();
+    while adapter.peek().is_some() {
+        // Completing a transfer clears its start bit.
+        unsafe { SIOCNT.write_volatile(SIOCNT.read_volatile().start(false)) };
+        let mut frames = 0;
+        while !unsafe { SIOCNT.read_volatile() }.is_started() {
+            if driver.timer.is_running() {
+                driver.timer();
+            } else if frames > crate::driver::frames::THREE_SECONDS {
+                panic!("no transfer was started");
+            } else {
+                driver.vblank();
+                frames += 1;
+            }
+        }
+        let driver::State::Active(active) = &driver.state else {
+            panic!("the link ended before the exchange did");
+        };
+        transfer(active.state.transfer_length, &mut adapter, &mut sent);
+        driver.serial();
+    }
+    sent
+}
@@ -1,15 +1,15 @@
 use super::{
-    super::{Data, Timeout, communication, error, schedule_serial},
+    super::{
+        Data, Timeout, acknowledgement_command, acknowledgement_footer, capture, check_length,
+        communication, drain_length, error, schedule_serial,
+    },
     ReceiveError, receive_error,
 };
 use crate::{
-    driver::{Adapter, frames},
+    driver::{Adapter, Command, frames},
     mmio::serial::{SIODATA32, TransferLength},
 };
-use core::{
-    cmp,
-    num::{NonZeroU8, NonZeroU16},
-};
+use core::num::{NonZeroU8, NonZeroU16};
 use either::Either;
 
 #[derive(Debug)]
@@ -20,6 +20,17 @@ enum Step {
     Footer,
 }
 
+impl Step {
+    fn capture_step(&self) -> capture::Step {
+        match self {
+            Self::HeaderLength => capture::Step::Header,
+            Self::Data { .. } => capture::Step::Data,
+            Self::Checksum => capture::Step::Checksum,
+            Self::Footer => capture::Step::Footer,
+        }
+    }
+}
+
 #[derive(Debug)]
 struct State {
     command_xor: bool,
@@ -71,6 +82,24 @@ impl Receive {
             state: state.next(),
         }
     }
+
+    /// Compares the checksum received in the last two bytes of a word against the calculated one.
+    fn check_checksum(state: State, bytes: [u8; 4]) -> Result<Self, ReceiveError> {
+        let full_checksum = ((bytes[2] as u16) << 8) | (bytes[3] as u16);
+        if full_checksum == state.checksum {
+            Ok(Self::next(Step::Footer, state))
+        } else {
+            Err(ReceiveError::new(
+                receive_error::Step::Footer,
+                error::Receive::Checksum {
+                    calculated: state.checksum,
+                    received: full_checksum,
+                },
+                state.attempt,
+                state.command_xor,
+            ))
+        }
+    }
 }
 
 impl super::super::Receive for Receive {
@@ -85,15 +114,13 @@ impl super::super::Receive for Receive {
         }
     }
 
+    #[cfg_attr(feature = "iwram", inline(never), unsafe(link_section = ".iwram"))]
     fn timer(&mut self, data: &Data) {
         if matches!(self.state.communication_state, communication::State::Send) {
             let bytes = match &self.step {
                 Step::Footer => {
-                    let command_byte = if self.state.command_xor {
-                        data.command as u8 | 0x80
-                    } else {
-                        data.command as u8
-                    };
+                    let command_byte =
+                        acknowledgement_command(data.command, self.state.command_xor);
                     u32::from_be_bytes([0x81, command_byte, 0x00, 0x00])
                 }
                 _ => 0x4b_4b_4b_4b,
@@ -105,25 +132,37 @@ impl super::super::Receive for Receive {
         }
     }
 
+    #[cfg_attr(feature = "iwram", inline(never), unsafe(link_section = ".iwram"))]
     fn serial(
         mut self,
         data: &mut Data,
+        _responses: &[Command],
     ) -> Result<Either<Result<Self, Self::ReceiveError>, Adapter>, error::Receive> {
         match self.state.communication_state {
             communication::State::Send => Ok(Either::Left(Ok(self))),
             communication::State::Receive => {
                 let bytes = unsafe { SIODATA32.read_volatile().to_be_bytes() };
+                data.capture.push_word(self.step.capture_step(), bytes);
                 match self.step {
                     Step::HeaderLength => {
-                        if bytes[0] > 0 {
-                            let full_length = ((bytes[0] as u16) << 8) | (bytes[1] as u16);
+                        let full_length = ((bytes[0] as u16) << 8) | (bytes[1] as u16);
+                        if let Err(error) = check_length(data.command, full_length) {
                             Ok(Either::Left(Err(ReceiveError::new(
-                                receive_error::Step::Data {
-                                    index: 2,
-                                    length: unsafe { NonZeroU16::new_unchecked(full_length) },
+                                match NonZeroU16::new(full_length) {
+                                    // The data fits in this transfer, so the checksum is next.
+                                    Some(length) if length.get() <= 2 => {
+                                        receive_error::Step::Checksum
+                                    }
+                                    Some(length) => receive_error::Step::Data {
+                                        index: 2,
+                                        length: drain_length(length),
+                                    },
+                                    // The checksum was in this transfer.
+                                    None => receive_error::Step::Footer,
                                 },
-                                error::Receive::LengthTooLarge(full_length),
+                                error,
                                 self.state.attempt,
+                                self.state.command_xor,
                             ))))
                         } else if let Some(nonzero_length) = NonZeroU8::new(bytes[1]) {
                             // Receive the last two bytes as data.
@@ -157,19 +196,7 @@ impl super::super::Receive for Receive {
                             }
                         } else {
                             // No data to receive, so we move right on to checksum.
-                            let full_checksum = ((bytes[2] as u16) << 8) | (bytes[3] as u16);
-                            if full_checksum == self.state.checksum {
-                                Ok(Either::Left(Ok(Self::next(Step::Footer, self.state))))
-                            } else {
-                                Ok(Either::Left(Err(ReceiveError::new(
-                                    receive_error::Step::Footer,
-                                    error::Receive::Checksum {
-                                        calculated: self.state.checksum,
-                                        received: full_checksum,
-                                    },
-                                    self.state.attempt,
-                                ))))
-                            }
+                            Ok(Either::Left(Self::check_checksum(self.state, bytes)))
                         }
                     }
                     Step::Data { index, length } => {
@@ -180,26 +207,14 @@ impl super::super::Receive for Receive {
                             .wrapping_add(bytes[1] as u16);
 
                         let bytes_to_receive = length.get().saturating_sub(index);
-                        for index in 0..cmp::min(4, bytes_to_receive) {
+                        for &byte in bytes.iter().take(bytes_to_receive as usize) {
                             unsafe {
-                                data.data.try_push(bytes[index as usize]).unwrap_unchecked();
+                                data.data.try_push(byte).unwrap_unchecked();
                             }
                         }
                         if bytes_to_receive <= 2 {
                             // Checksum is included in last two bytes.
-                            let full_checksum = ((bytes[2] as u16) << 8) | (bytes[3] as u16);
-                            if full_checksum == self.state.checksum {
-                                Ok(Either::Left(Ok(Self::next(Step::Footer, self.state))))
-                            } else {
-                                Ok(Either::Left(Err(ReceiveError::new(
-                                    receive_error::Step::Footer,
-                                    error::Receive::Checksum {
-                                        calculated: self.state.checksum,
-                                        received: full_checksum,
-                                    },
-                                    self.state.attempt,
-                                ))))
-                            }
+                            Ok(Either::Left(Self::check_checksum(self.state, bytes)))
                         } else {
                             self.state.checksum = self
                                 .state
@@ -228,33 +243,17 @@ impl super::super::Receive for Receive {
                             .checksum
                             .wrapping_add(bytes[0] as u16)
                             .wrapping_add(bytes[1] as u16);
-                        let full_checksum = ((bytes[2] as u16) << 8) | (bytes[3] as u16);
-                        if full_checksum == self.state.checksum {
-                            Ok(Either::Left(Ok(Self::next(Step::Footer, self.state))))
-                        } else {
-                            Ok(Either::Left(Err(ReceiveError::new(
-                                receive_error::Step::Footer,
-                                error::Receive::Checksum {
-                                    calculated: self.state.checksum,
-                                    received: full_checksum,
-                                },
-                                self.state.attempt,
-                            ))))
-                        }
+                        Ok(Either::Left(Self::check_checksum(self.state, bytes)))
                     }
                     Step::Footer => {
                         match Adapter::try_from(bytes[0]) {
-                            Ok(adapter) => match NonZeroU8::new(bytes[1]) {
-                                None => {
-                                    // We don't care about what the adapter was set to previously. We just
-                                    // return the device this packet indicated.
-                                    Ok(Either::Right(adapter))
-                                }
-                                Some(nonzero) => {
-                                    // We can no longer retry at this point. We simply return the error.
-                                    Err(error::Receive::NonZeroFooterCommand(nonzero))
-                                }
-                            },
+                            Ok(adapter) => {
+                                // We can no longer retry at this point. We simply return the error.
+                                acknowledgement_footer(data, bytes[1])?;
+                                // We don't care about what the adapter was set to previously. We just
+                                // return the device this packet indicated.
+                                Ok(Either::Right(adapter))
+                            }
                             Err(unknown) => {
                                 // We can no longer retry at this point. We simply return the error.
                                 Err(error::Receive::UnsupportedDevice(unknown))
@@ -1,5 +1,8 @@
 use super::{
-    super::{MAX_RETRIES, Timeout, communication, error, schedule_serial},
+    super::{
+        MAX_PAYLOAD_LENGTH, MAX_RETRIES, Timeout, acknowledgement_command, communication,
+        drain_length, error, protocol_violation, schedule_serial,
+    },
     WaitForReceive,
 };
 use crate::{
@@ -20,15 +23,17 @@ pub(super) enum Step {
 #[derive(Debug)]
 struct State {
     error: error::Receive,
+    command_xor: bool,
     attempt: u8,
     frame: u8,
     communication_state: communication::State,
 }
 
 impl State {
-    fn new(error: error::Receive, attempt: u8) -> Self {
+    fn new(error: error::Receive, attempt: u8, command_xor: bool) -> Self {
         Self {
             error,
+            command_xor,
             attempt,
             frame: 0,
             communication_state: communication::State::Send,
@@ -38,11 +43,31 @@ impl State {
     fn next(self) -> Self {
         Self {
             error: self.error,
+            command_xor: self.command_xor,
             attempt: self.attempt,
             frame: self.frame,
             communication_state: communication::State::Send,
         }
     }
+
+    /// The attempt to request the packet again with, if it should be requested again at all.
+    fn next_attempt(&self) -> Option<u8> {
+        let attempt = self.attempt + 1;
+        (self.error.is_retryable() && attempt < MAX_RETRIES).then_some(attempt)
+    }
+
+    /// The command sent in the acknowledgement signal, using the same convention for the high bit
+    /// as the adapter used for the packet's command.
+    fn footer_command(&self) -> u8 {
+        if self.next_attempt().is_some() {
+            acknowledgement_command(self.error.command(), self.command_xor)
+        } else {
+            // Since we've errored too many times, it doesn't matter what we send here. We will be
+            // propagating the error up through the driver anyway. Sending an empty command instead
+            // of an error command means the adapter won't try to send us another packet.
+            acknowledgement_command(Command::Empty, self.command_xor)
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -52,10 +77,11 @@ pub(in crate::driver::active) struct ReceiveError {
 }
 
 impl ReceiveError {
-    pub(super) fn new(step: Step, error: error::Receive, attempt: u8) -> Self {
+    pub(super) fn new(step: Step, error: error::Receive, attempt: u8, command_xor: bool) -> Self {
+        protocol_violation(&error);
         Self {
             step,
-            state: State::new(error, attempt),
+            state: State::new(error, attempt, command_xor),
         }
     }
 
@@ -79,21 +105,11 @@ impl super::super::ReceiveError for ReceiveError {
         }
     }
 
+    #[cfg_attr(feature = "iwram", inline(never), unsafe(link_section = ".iwram"))]
     fn timer(&mut self) {
         if matches!(self.state.communication_state, communication::State::Send) {
             let bytes = match self.step {
-                Step::Footer => {
-                    let command_byte = if self.state.attempt + 1 < MAX_RETRIES {
-                        self.state.error.command() as u8 | 0x80
-                    } else {
-                        // Since we've errored too many times, it doesn't matter what we send here. We
-                        // will be propagating the error up through the driver anyway. Sending an empty
-                        // command instead of an error command means the adapter won't try to send us
-                        // another packet.
-                        Command::Empty as u8 | 0x80
-                    };
-                    u32::from_be_bytes([0x81, command_byte, 0x00, 0x00])
-                }
+                Step::Footer => u32::from_be_bytes([0x81, self.state.footer_command(), 0x00, 0x00]),
                 _ => 0x4b_4b_4b_4b,
             };
 
@@ -103,7 +119,17 @@ impl super::super::ReceiveError for ReceiveError {
         }
     }
 
-    fn serial(self) -> Result<Either<Self, Self::WaitForReceive>, error::Receive> {
+    fn is_checksum(&self) -> bool {
+        matches!(self.state.error, error::Receive::Checksum { .. })
+    }
+
+    fn is_long_drain(&self) -> bool {
+        // Four bytes are drained per transfer, so even the longest payload is drained quickly.
+        false
+    }
+
+    #[cfg_attr(feature = "iwram", inline(never), unsafe(link_section = ".iwram"))]
+    fn serial(mut self) -> Result<Either<Self, Self::WaitForReceive>, error::Receive> {
         match self.state.communication_state {
             communication::State::Send => Ok(Either::Left(self)),
             communication::State::Receive => {
@@ -113,11 +139,19 @@ impl super::super::ReceiveError for ReceiveError {
                         let full_length = ((bytes[0] as u16) << 8) | (bytes[1] as u16);
                         match NonZeroU16::new(full_length) {
                             Some(length) => {
-                                if 2 < length.get() {
+                                if length > MAX_PAYLOAD_LENGTH {
+                                    self.state.error =
+                                        error::Receive::UnexpectedLength(full_length);
+                                }
+                                if length.get() <= 2 {
+                                    // The data fits in this transfer.
                                     Ok(Either::Left(Self::next(Step::Checksum, self.state)))
                                 } else {
                                     Ok(Either::Left(Self::next(
-                                        Step::Data { length, index: 2 },
+                                        Step::Data {
+                                            length: drain_length(length),
+                                            index: 2,
+                                        },
                                         self.state,
                                     )))
                                 }
@@ -145,12 +179,12 @@ impl super::super::ReceiveError for ReceiveError {
                     }
                     Step::Checksum => Ok(Either::Left(Self::next(Step::Footer, self.state))),
                     Step::Footer => {
-                        let new_attempt = self.state.attempt + 1;
-                        if new_attempt < MAX_RETRIES {
+                        if let Some(new_attempt) = self.state.next_attempt() {
                             // Retry.
                             Ok(Either::Right(WaitForReceive::new(new_attempt)))
                         } else {
-                            // Too many retries. Stop trying and return error.
+                            // Too many retries, or retrying is pointless. Stop trying and return
+                            // error.
                             Err(self.state.error)
                         }
                     }
@@ -159,3 +193,31 @@ impl super::super::ReceiveError for ReceiveError {
         }
     }
 }
+
+#[cfg(all(test, not(feature = "protocol-assertions")))]
+mod tests {
+    use super::{ReceiveError, Step, error};
+    use gba_test::test;
+
+    fn checksum_error(command_xor: bool) -> ReceiveError {
+        ReceiveError::new(
+            Step::Footer,
+            error::Receive::Checksum {
+                calculated: 0x0010,
+                received: 0x0011,
+            },
+            0,
+            command_xor,
+        )
+    }
+
+    #[test]
+    fn footer_command_xor() {
+        assert_eq!(checksum_error(true).state.footer_command(), 0xf1);
+    }
+
+    #[test]
+    fn footer_command_no_xor() {
+        assert_eq!(checksum_error(false).state.footer_command(), 0x71);
+    }
+}
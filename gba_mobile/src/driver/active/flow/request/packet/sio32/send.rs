@@ -1,5 +1,8 @@
 use super::{
-    super::{Data, MAX_RETRIES, Timeout, communication, error, schedule_serial},
+    super::{
+        Cursor, Data, MAX_RETRIES, Timeout, communication, error, schedule_serial,
+        verify_acknowledgement, verify_acknowledgement_padding,
+    },
     WaitForReceive,
 };
 use crate::{
@@ -12,7 +15,10 @@ use either::Either;
 enum Step {
     MagicByte,
     HeaderLength,
-    Data { index: u8 },
+    Data {
+        /// Whether the checksum was packed in alongside the final data bytes.
+        checksum_packed: bool,
+    },
     Checksum,
     Footer,
 }
@@ -20,6 +26,7 @@ enum Step {
 #[derive(Debug)]
 pub(in crate::driver::active) struct Send {
     step: Step,
+    cursor: Cursor,
     checksum: u16,
     attempt: u8,
     frame: u8,
@@ -30,6 +37,7 @@ impl Send {
     pub(in super::super) fn new() -> Self {
         Self {
             step: Step::MagicByte,
+            cursor: Cursor::new(),
             checksum: 0,
             attempt: 0,
             frame: 0,
@@ -41,6 +49,7 @@ impl Send {
     fn next(self, step: Step) -> Self {
         Self {
             step,
+            cursor: self.cursor,
             checksum: self.checksum,
             attempt: self.attempt,
             frame: 0,
@@ -52,6 +61,7 @@ impl Send {
     fn retry(self, new_attempt: u8) -> Self {
         Self {
             step: Step::MagicByte,
+            cursor: Cursor::new(),
             checksum: 0,
             attempt: new_attempt,
             frame: 0,
@@ -63,6 +73,10 @@ impl Send {
 impl super::super::Send for Send {
     type WaitForReceive = WaitForReceive;
 
+    fn attempt(&self) -> u8 {
+        self.attempt
+    }
+
     fn vblank(&mut self) -> Result<(), Timeout> {
         if self.frame > frames::THREE_SECONDS {
             return Err(Timeout::Serial);
@@ -72,9 +86,10 @@ impl super::super::Send for Send {
         }
     }
 
+    #[cfg_attr(feature = "iwram", inline(never), unsafe(link_section = ".iwram"))]
     fn timer(&mut self, data: &Data) {
         if matches!(self.communication_state, communication::State::Send) {
-            let bytes = match self.step {
+            let bytes = match &mut self.step {
                 Step::MagicByte => {
                     let command = data.command as u8;
                     self.checksum = self.checksum.wrapping_add(command as u16);
@@ -92,8 +107,8 @@ impl super::super::Send for Send {
                             self.checksum as u8,
                         ])
                     } else {
-                        let data_0 = data.data.get(0).copied().unwrap_or(0x00);
-                        let data_1 = data.data.get(1).copied().unwrap_or(0x00);
+                        let data_0 = self.cursor.next_byte(data).unwrap_or(0x00);
+                        let data_1 = self.cursor.next_byte(data).unwrap_or(0x00);
                         self.checksum = self
                             .checksum
                             .wrapping_add(data_0 as u16)
@@ -101,20 +116,22 @@ impl super::super::Send for Send {
                         u32::from_be_bytes([0x00, length, data_0, data_1])
                     }
                 }
-                Step::Data { index } => {
-                    let length = data.data.len();
+                Step::Data { checksum_packed } => {
                     let mut bytes = [0x00; 4];
                     let mut offset = 0;
-                    while offset < 4 && index + offset < length {
-                        let byte = data.data.get(index + offset).copied().unwrap_or(0x00);
+                    for slot in &mut bytes {
+                        let Some(byte) = self.cursor.next_byte(data) else {
+                            break;
+                        };
                         self.checksum = self.checksum.wrapping_add(byte as u16);
-                        bytes[offset as usize] = byte;
+                        *slot = byte;
                         offset += 1;
                     }
                     if offset < 3 {
                         // If we have room, we pack the checksum in as well.
                         bytes[2] = (self.checksum >> 8) as u8;
                         bytes[3] = self.checksum as u8;
+                        *checksum_packed = true;
                     }
                     u32::from_be_bytes(bytes)
                 }
@@ -133,6 +150,7 @@ impl super::super::Send for Send {
         }
     }
 
+    #[cfg_attr(feature = "iwram", inline(never), unsafe(link_section = ".iwram"))]
     fn serial(self, data: &Data) -> Result<Either<Self, Self::WaitForReceive>, error::Send> {
         match self.communication_state {
             communication::State::Send => Ok(Either::Left(self)),
@@ -141,22 +159,27 @@ impl super::super::Send for Send {
                 match self.step {
                     Step::MagicByte => Ok(Either::Left(self.next(Step::HeaderLength))),
                     Step::HeaderLength => {
-                        let next_step = match data.data.len() {
-                            0 => Step::Footer,
-                            1..=2 => Step::Checksum,
-                            _ => Step::Data { index: 2 },
+                        let next_step = if data.data.is_empty() {
+                            // The checksum was sent in place of the data.
+                            Step::Footer
+                        } else if self.cursor.is_exhausted(data) {
+                            Step::Checksum
+                        } else {
+                            Step::Data {
+                                checksum_packed: false,
+                            }
                         };
                         Ok(Either::Left(self.next(next_step)))
                     }
-                    Step::Data { index } => {
-                        let length = data.data.len();
-                        let next_step = if index + 2 >= length {
-                            // We can fit the checksum in here.
+                    Step::Data { checksum_packed } => {
+                        let next_step = if checksum_packed {
                             Step::Footer
-                        } else if index + 4 >= length {
+                        } else if self.cursor.is_exhausted(data) {
                             Step::Checksum
                         } else {
-                            Step::Data { index: index + 4 }
+                            Step::Data {
+                                checksum_packed: false,
+                            }
                         };
                         Ok(Either::Left(self.next(next_step)))
                     }
@@ -185,9 +208,27 @@ impl super::super::Send for Send {
                                 Err(error::Send::AdapterInternalError)
                             }
                             _ => {
-                                // We don't verify anything here and simply assume the adapter responded
-                                // with a correct command. If the adapter is in an invalid state, we will
-                                // find out when receiving the response packet.
+                                // An acknowledgement of anything other than our own command means
+                                // the adapter may not have accepted the packet, so it is sent
+                                // again.
+                                if let Err(error) =
+                                    verify_acknowledgement(data, bytes.to_be_bytes()[1])
+                                {
+                                    return if new_attempt < MAX_RETRIES {
+                                        Ok(Either::Left(self.retry(new_attempt)))
+                                    } else {
+                                        Err(error)
+                                    };
+                                }
+                                if let Err(error) =
+                                    verify_acknowledgement_padding(data, bytes.to_be_bytes())
+                                {
+                                    return if new_attempt < MAX_RETRIES {
+                                        Ok(Either::Left(self.retry(new_attempt)))
+                                    } else {
+                                        Err(error)
+                                    };
+                                }
                                 Ok(Either::Right(WaitForReceive::new(0)))
                             }
                         }
@@ -197,3 +238,125 @@ impl super::super::Send for Send {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::super::Send as _, Command, Cursor, Data, Either, MAX_RETRIES, SIODATA32, Send, Step,
+        WaitForReceive, communication, error,
+    };
+    use alloc::{format, vec::Vec};
+    use claims::{assert_err, assert_matches, assert_ok};
+    use core::iter;
+    use gba_test::test;
+
+    /// Sends a `TransferData` packet holding the bytes `0..length` through every step, returning
+    /// the bytes sent.
+    fn send_all(length: u8) -> Vec<u8> {
+        let mut data = Data::new();
+        data.command = Command::TransferData;
+        for byte in 0..length {
+            data.data.try_push(byte).unwrap();
+        }
+
+        let mut send = Send::new();
+        let mut sent = Vec::new();
+        loop {
+            send.timer(&data);
+            sent.extend(unsafe { SIODATA32.read_volatile() }.to_be_bytes());
+            let reply = if matches!(send.step, Step::Footer) {
+                u32::from_be_bytes([0x88, Command::TransferData as u8 ^ 0x80, 0x00, 0x00])
+            } else {
+                0xd2_d2_d2_d2
+            };
+            unsafe { SIODATA32.write_volatile(reply) };
+            match assert_ok!(send.serial(&data)) {
+                Either::Left(next) => send = next,
+                Either::Right(_) => return sent,
+            }
+        }
+    }
+
+    /// Completes the acknowledgement of a `BeginSession` packet with the given command byte.
+    fn acknowledge(attempt: u8, byte: u8) -> Result<Either<Send, WaitForReceive>, error::Send> {
+        let send = Send {
+            step: Step::Footer,
+            cursor: Cursor::new(),
+            checksum: 0,
+            attempt,
+            frame: 0,
+            communication_state: communication::State::Receive,
+        };
+        let mut data = Data::new();
+        data.command = Command::BeginSession;
+        unsafe { SIODATA32.write_volatile(u32::from_be_bytes([0x88, byte, 0x00, 0x00])) };
+        send.serial(&data)
+    }
+
+    #[test]
+    fn all_lengths_send_exactly_the_data() {
+        for length in 0..=254 {
+            let checksum = (0..length).fold(
+                Command::TransferData as u16 + length as u16,
+                |checksum, byte| checksum + byte as u16,
+            );
+            // The data is padded so that the checksum ends a word.
+            let padding = (8 + length as usize).div_ceil(4) * 4 - 8 - length as usize;
+            let expected = [0x99, 0x66, Command::TransferData as u8, 0x00, 0x00, length]
+                .into_iter()
+                .chain(0..length)
+                .chain(iter::repeat_n(0x00, padding))
+                .chain([
+                    (checksum >> 8) as u8,
+                    checksum as u8,
+                    0x81,
+                    0x00,
+                    0x00,
+                    0x00,
+                ])
+                .collect::<Vec<_>>();
+
+            assert_eq!(send_all(length), expected, "length {length}");
+        }
+    }
+
+    #[test]
+    fn acknowledgement_own_command() {
+        assert_matches!(assert_ok!(acknowledge(0, 0x90)), Either::Right(_));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "protocol-assertions", should_panic)]
+    fn acknowledgement_wrong_command_retries() {
+        let send = match assert_ok!(acknowledge(0, 0x91)) {
+            Either::Left(send) => send,
+            Either::Right(_) => panic!("wrong acknowledgement was accepted"),
+        };
+        assert_matches!(send.step, Step::MagicByte);
+        assert_eq!(send.attempt, 1);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "protocol-assertions", should_panic)]
+    fn acknowledgement_wrong_command_error() {
+        let error = assert_err!(acknowledge(MAX_RETRIES - 1, 0x91));
+        assert_eq!(format!("{error:?}"), "BadAcknowledgement { received: 145 }");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "protocol-assertions", should_panic)]
+    fn acknowledgement_invalid_byte_retries() {
+        let send = match assert_ok!(acknowledge(0, 0xff)) {
+            Either::Left(send) => send,
+            Either::Right(_) => panic!("invalid acknowledgement was accepted"),
+        };
+        assert_eq!(send.attempt, 1);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "protocol-assertions", should_panic)]
+    fn acknowledgement_invalid_byte_error() {
+        let error = assert_err!(acknowledge(MAX_RETRIES - 1, 0xff));
+        assert_eq!(format!("{error:?}"), "BadAcknowledgement { received: 255 }");
+    }
+}
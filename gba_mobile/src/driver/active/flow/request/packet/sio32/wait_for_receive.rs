@@ -1,5 +1,8 @@
 use super::{
-    super::{Data, Timeout, communication, error, schedule_serial},
+    super::{
+        Data, Timeout, capture, check_command, check_empty_byte, communication, error,
+        schedule_serial,
+    },
     Receive, ReceiveError, receive_error,
 };
 use crate::{
@@ -40,10 +43,12 @@ impl super::super::WaitForReceive for WaitForReceive {
     type Receive = Receive;
     type ReceiveError = ReceiveError;
 
+    fn attempt(&self) -> u8 {
+        self.attempt
+    }
+
     fn vblank(&mut self) -> Result<(), Timeout> {
-        if self.packet_frame > frames::FIFTEEN_SECONDS {
-            Err(Timeout::Packet)
-        } else if self.serial_frame > frames::THREE_SECONDS {
+        if self.serial_frame > frames::THREE_SECONDS {
             Err(Timeout::Serial)
         } else {
             if self.packet_frame % frames::ONE_HUNDRED_MILLISECONDS as u16 == 0
@@ -60,7 +65,12 @@ impl super::super::WaitForReceive for WaitForReceive {
         }
     }
 
-    fn serial(self, data: &mut Data) -> Result<Either<Self, Self::Receive>, Self::ReceiveError> {
+    #[cfg_attr(feature = "iwram", inline(never), unsafe(link_section = ".iwram"))]
+    fn serial(
+        self,
+        data: &mut Data,
+        responses: &[Command],
+    ) -> Result<Either<Self, Self::Receive>, Self::ReceiveError> {
         match self.communication_state {
             communication::State::Send => Ok(Either::Left(self)),
             communication::State::Receive => {
@@ -68,10 +78,17 @@ impl super::super::WaitForReceive for WaitForReceive {
 
                 match (bytes[0], bytes[1]) {
                     (0x99, 0x66) => {
+                        data.begin_response();
+                        data.capture.push_word(capture::Step::Magic, bytes);
                         let command_xor = bytes[2] & 0x80 == 0;
-                        match Command::try_from(bytes[2] & 0x7f) {
+                        match Command::try_from(bytes[2] & 0x7f)
+                            .map_err(error::Receive::UnknownCommand)
+                            .and_then(|command| {
+                                check_command(data.command, command, responses).map(|()| command)
+                            })
+                            .and_then(|command| check_empty_byte(data, bytes[3]).map(|()| command))
+                        {
                             Ok(command) => {
-                                *data = Data::new();
                                 data.command = command;
                                 Ok(Either::Right(Receive::new(
                                     0,
@@ -79,14 +96,17 @@ impl super::super::WaitForReceive for WaitForReceive {
                                     command_xor,
                                 )))
                             }
-                            Err(unknown) => Err(ReceiveError::new(
+                            Err(error) => Err(ReceiveError::new(
                                 receive_error::Step::HeaderLength,
-                                error::Receive::UnknownCommand(unknown),
+                                error,
                                 self.attempt,
+                                command_xor,
                             )),
                         }
                     }
-                    // Anything else should be ignored.
+                    // Anything else should be ignored. This includes idle words and any trailing
+                    // word the adapter was still sending for the previous packet, so a late word
+                    // never costs an attempt.
                     _ => Ok(Either::Left(self.reset())),
                 }
             }
@@ -1,7 +1,10 @@
 use core::num::{NonZeroU8, NonZeroU16};
 
 use super::{
-    super::{Data, Timeout, communication, error, schedule_serial},
+    super::{
+        Data, Timeout, acknowledgement_command, acknowledgement_footer, capture, check_command,
+        check_empty_byte, check_length, communication, drain_length, error, schedule_serial,
+    },
     ReceiveError, receive_error,
 };
 use crate::{
@@ -28,6 +31,21 @@ enum Step {
     FooterCommand { adapter: Adapter },
 }
 
+impl Step {
+    fn capture_step(&self) -> capture::Step {
+        match self {
+            Self::MagicByte2 => capture::Step::Magic,
+            Self::HeaderCommand
+            | Self::HeaderEmptyByte
+            | Self::HeaderLength1
+            | Self::HeaderLength2 { .. } => capture::Step::Header,
+            Self::Data { .. } => capture::Step::Data,
+            Self::Checksum1 | Self::Checksum2 { .. } => capture::Step::Checksum,
+            Self::FooterDevice | Self::FooterCommand { .. } => capture::Step::Footer,
+        }
+    }
+}
+
 #[derive(Debug)]
 struct State {
     command_xor: bool,
@@ -40,7 +58,9 @@ struct State {
 impl State {
     fn new(attempt: u8) -> Self {
         Self {
-            command_xor: false,
+            // Until the adapter's command byte is seen, assume the usual convention of the adapter
+            // sending the command with the high bit clear.
+            command_xor: true,
             checksum: 0,
             attempt,
             frame: 0,
@@ -93,16 +113,13 @@ impl super::super::Receive for Receive {
         }
     }
 
+    #[cfg_attr(feature = "iwram", inline(never), unsafe(link_section = ".iwram"))]
     fn timer(&mut self, data: &Data) {
         if matches!(self.state.communication_state, communication::State::Send) {
             let byte = match &self.step {
                 Step::FooterDevice => 0x81,
                 Step::FooterCommand { .. } => {
-                    if self.state.command_xor {
-                        data.command as u8 | 0x80
-                    } else {
-                        data.command as u8
-                    }
+                    acknowledgement_command(data.command, self.state.command_xor)
                 }
                 _ => 0x4b,
             };
@@ -112,14 +129,17 @@ impl super::super::Receive for Receive {
         }
     }
 
+    #[cfg_attr(feature = "iwram", inline(never), unsafe(link_section = ".iwram"))]
     fn serial(
         mut self,
         data: &mut Data,
+        responses: &[Command],
     ) -> Result<Either<Result<Self, Self::ReceiveError>, Adapter>, error::Receive> {
         match self.state.communication_state {
             communication::State::Send => Ok(Either::Left(Ok(self))),
             communication::State::Receive => {
                 let byte = unsafe { SIODATA8.read_volatile() };
+                data.capture.push(self.step.capture_step(), byte);
                 match self.step {
                     Step::MagicByte2 => match byte {
                         0x66 => Ok(Either::Left(Ok(Self::next(
@@ -130,12 +150,17 @@ impl super::super::Receive for Receive {
                             receive_error::Step::HeaderCommand,
                             error::Receive::MagicValue2(byte),
                             self.state.attempt,
+                            self.state.command_xor,
                         )))),
                     },
                     Step::HeaderCommand => {
                         self.state.checksum = self.state.checksum.wrapping_add(byte as u16);
                         self.state.command_xor = byte & 0x80 == 0;
-                        match Command::try_from(byte & 0x7f) {
+                        match Command::try_from(byte & 0x7f)
+                            .map_err(error::Receive::UnknownCommand)
+                            .and_then(|command| {
+                                check_command(data.command, command, responses).map(|()| command)
+                            }) {
                             Ok(command) => {
                                 data.command = command;
                                 Ok(Either::Left(Ok(Self::next(
@@ -143,19 +168,28 @@ impl super::super::Receive for Receive {
                                     self.state,
                                 ))))
                             }
-                            Err(unknown) => Ok(Either::Left(Err(ReceiveError::new(
+                            Err(error) => Ok(Either::Left(Err(ReceiveError::new(
                                 receive_error::Step::HeaderEmptyByte,
-                                error::Receive::UnknownCommand(unknown),
+                                error,
                                 self.state.attempt,
+                                self.state.command_xor,
                             )))),
                         }
                     }
                     Step::HeaderEmptyByte => {
                         self.state.checksum = self.state.checksum.wrapping_add(byte as u16);
-                        Ok(Either::Left(Ok(Self::next(
-                            Step::HeaderLength1,
-                            self.state,
-                        ))))
+                        match check_empty_byte(data, byte) {
+                            Ok(()) => Ok(Either::Left(Ok(Self::next(
+                                Step::HeaderLength1,
+                                self.state,
+                            )))),
+                            Err(error) => Ok(Either::Left(Err(ReceiveError::new(
+                                receive_error::Step::HeaderLength1,
+                                error,
+                                self.state.attempt,
+                                self.state.command_xor,
+                            )))),
+                        }
                     }
                     Step::HeaderLength1 => {
                         self.state.checksum = self.state.checksum.wrapping_add(byte as u16);
@@ -166,15 +200,20 @@ impl super::super::Receive for Receive {
                     }
                     Step::HeaderLength2 { first_byte } => {
                         self.state.checksum = self.state.checksum.wrapping_add(byte as u16);
-                        if first_byte > 0 {
-                            let full_length = ((first_byte as u16) << 8) | (byte as u16);
+                        let full_length = ((first_byte as u16) << 8) | (byte as u16);
+                        if let Err(error) = check_length(data.command, full_length) {
                             Ok(Either::Left(Err(ReceiveError::new(
-                                receive_error::Step::Data {
-                                    index: 0,
-                                    length: unsafe { NonZeroU16::new_unchecked(full_length) },
+                                match NonZeroU16::new(full_length) {
+                                    Some(length) => receive_error::Step::Data {
+                                        index: 0,
+                                        length: drain_length(length),
+                                    },
+                                    // An empty command error still has its checksum to drain.
+                                    None => receive_error::Step::Checksum1,
                                 },
-                                error::Receive::LengthTooLarge(full_length),
+                                error,
                                 self.state.attempt,
+                                self.state.command_xor,
                             ))))
                         } else if let Some(nonzero_length) = NonZeroU8::new(byte) {
                             Ok(Either::Left(Ok(Self::next(
@@ -223,6 +262,7 @@ impl super::super::Receive for Receive {
                                     received: full_checksum,
                                 },
                                 self.state.attempt,
+                                self.state.command_xor,
                             ))))
                         }
                     }
@@ -235,18 +275,15 @@ impl super::super::Receive for Receive {
                             receive_error::Step::FooterCommand,
                             error::Receive::UnsupportedDevice(unknown),
                             self.state.attempt,
+                            self.state.command_xor,
                         )))),
                     },
                     Step::FooterCommand { adapter } => {
-                        // The acknowledgement signal command we receive is expected to be 0x00.
-                        if let Some(nonzero) = NonZeroU8::new(byte) {
-                            // We can no longer retry at this point. We simply enter an error state.
-                            Err(error::Receive::NonZeroFooterCommand(nonzero))
-                        } else {
-                            // We don't care about what the adapter was set to previously. We just
-                            // return the device this packet indicated.
-                            Ok(Either::Right(adapter))
-                        }
+                        // We can no longer retry at this point. We simply enter an error state.
+                        acknowledgement_footer(data, byte)?;
+                        // We don't care about what the adapter was set to previously. We just
+                        // return the device this packet indicated.
+                        Ok(Either::Right(adapter))
                     }
                 }
             }
@@ -1,5 +1,8 @@
 use super::{
-    super::{MAX_RETRIES, Timeout, communication, error, schedule_serial},
+    super::{
+        MAX_PAYLOAD_LENGTH, MAX_RETRIES, Timeout, acknowledgement_command, communication,
+        drain_length, error, protocol_violation, schedule_serial,
+    },
     WaitForReceive,
 };
 use crate::{
@@ -9,6 +12,9 @@ use crate::{
 use core::num::NonZeroU16;
 use either::Either;
 
+/// The number of payload bytes beyond which a drain is paced more slowly.
+const LONG_DRAIN: u16 = 32;
+
 #[derive(Debug)]
 pub(super) enum Step {
     HeaderCommand,
@@ -28,15 +34,17 @@ pub(super) enum Step {
 #[derive(Debug)]
 struct State {
     error: error::Receive,
+    command_xor: bool,
     attempt: u8,
     frame: u8,
     communication_state: communication::State,
 }
 
 impl State {
-    fn new(error: error::Receive, attempt: u8) -> Self {
+    fn new(error: error::Receive, attempt: u8, command_xor: bool) -> Self {
         Self {
             error,
+            command_xor,
             attempt,
             frame: 0,
             communication_state: communication::State::Send,
@@ -46,11 +54,30 @@ impl State {
     fn next(self) -> Self {
         Self {
             error: self.error,
+            command_xor: self.command_xor,
             attempt: self.attempt,
             frame: self.frame,
             communication_state: communication::State::Send,
         }
     }
+
+    /// The attempt to request the packet again with, if it should be requested again at all.
+    fn next_attempt(&self) -> Option<u8> {
+        let attempt = self.attempt + 1;
+        (self.error.is_retryable() && attempt < MAX_RETRIES).then_some(attempt)
+    }
+
+    /// The command sent in the acknowledgement signal, using the same convention for the high bit
+    /// as the adapter used for the packet's command.
+    fn footer_command(&self) -> u8 {
+        if self.next_attempt().is_some() {
+            acknowledgement_command(self.error.command(), self.command_xor)
+        } else {
+            // Since we've errored on communication too much, it doesn't matter what we send here.
+            // We are going to error out the entire link session anyway.
+            acknowledgement_command(Command::Empty, self.command_xor)
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -60,10 +87,11 @@ pub(in crate::driver::active) struct ReceiveError {
 }
 
 impl ReceiveError {
-    pub(super) fn new(step: Step, error: error::Receive, attempt: u8) -> Self {
+    pub(super) fn new(step: Step, error: error::Receive, attempt: u8, command_xor: bool) -> Self {
+        protocol_violation(&error);
         Self {
             step,
-            state: State::new(error, attempt),
+            state: State::new(error, attempt, command_xor),
         }
     }
 
@@ -87,19 +115,12 @@ impl super::super::ReceiveError for ReceiveError {
         }
     }
 
+    #[cfg_attr(feature = "iwram", inline(never), unsafe(link_section = ".iwram"))]
     fn timer(&mut self) {
         if matches!(self.state.communication_state, communication::State::Send) {
             let byte = match self.step {
                 Step::FooterDevice { .. } => 0x81,
-                Step::FooterCommand { .. } => {
-                    if self.state.attempt + 1 < MAX_RETRIES {
-                        self.state.error.command() as u8 | 0x80
-                    } else {
-                        // Since we've errored on communication too much, it doesn't matter what we
-                        // send here. We are going to error out the entire link session anyway.
-                        Command::Empty as u8 | 0x80
-                    }
-                }
+                Step::FooterCommand { .. } => self.state.footer_command(),
                 _ => 0x4b,
             };
             self.state.communication_state = communication::State::Receive;
@@ -108,7 +129,16 @@ impl super::super::ReceiveError for ReceiveError {
         }
     }
 
-    fn serial(self) -> Result<Either<Self, Self::WaitForReceive>, error::Receive> {
+    fn is_checksum(&self) -> bool {
+        matches!(self.state.error, error::Receive::Checksum { .. })
+    }
+
+    fn is_long_drain(&self) -> bool {
+        matches!(self.step, Step::Data { length, .. } if length.get() > LONG_DRAIN)
+    }
+
+    #[cfg_attr(feature = "iwram", inline(never), unsafe(link_section = ".iwram"))]
+    fn serial(mut self) -> Result<Either<Self, Self::WaitForReceive>, error::Receive> {
         match self.state.communication_state {
             communication::State::Send => Ok(Either::Left(self)),
             communication::State::Receive => {
@@ -127,10 +157,19 @@ impl super::super::ReceiveError for ReceiveError {
                     Step::HeaderLength2 { first_byte } => {
                         let full_length = ((first_byte as u16) << 8) | (byte as u16);
                         match NonZeroU16::new(full_length) {
-                            Some(length) => Ok(Either::Left(Self::next(
-                                Step::Data { index: 0, length },
-                                self.state,
-                            ))),
+                            Some(length) => {
+                                if length > MAX_PAYLOAD_LENGTH {
+                                    self.state.error =
+                                        error::Receive::UnexpectedLength(full_length);
+                                }
+                                Ok(Either::Left(Self::next(
+                                    Step::Data {
+                                        index: 0,
+                                        length: drain_length(length),
+                                    },
+                                    self.state,
+                                )))
+                            }
                             None => Ok(Either::Left(Self::next(Step::Checksum1, self.state))),
                         }
                     }
@@ -155,12 +194,12 @@ impl super::super::ReceiveError for ReceiveError {
                         Ok(Either::Left(Self::next(Step::FooterCommand, self.state)))
                     }
                     Step::FooterCommand => {
-                        let new_attempt = self.state.attempt + 1;
-                        if new_attempt < MAX_RETRIES {
+                        if let Some(new_attempt) = self.state.next_attempt() {
                             // Retry.
                             Ok(Either::Right(WaitForReceive::new(new_attempt)))
                         } else {
-                            // Too many retries. Stop trying and return error.
+                            // Too many retries, or retrying is pointless. Stop trying and return
+                            // error.
                             Err(self.state.error)
                         }
                     }
@@ -169,3 +208,77 @@ impl super::super::ReceiveError for ReceiveError {
         }
     }
 }
+
+#[cfg(all(test, not(feature = "protocol-assertions")))]
+mod tests {
+    use super::{super::super::ReceiveError as _, MAX_RETRIES, ReceiveError, Step, error};
+    use claims::assert_some;
+    use core::num::NonZeroU16;
+    use gba_test::test;
+
+    fn checksum_error(attempt: u8, command_xor: bool) -> ReceiveError {
+        ReceiveError::new(
+            Step::FooterCommand,
+            error::Receive::Checksum {
+                calculated: 0x0010,
+                received: 0x0011,
+            },
+            attempt,
+            command_xor,
+        )
+    }
+
+    fn draining(length: u16) -> ReceiveError {
+        ReceiveError::new(
+            Step::Data {
+                index: 0,
+                length: assert_some!(NonZeroU16::new(length)),
+            },
+            error::Receive::UnexpectedLength(length),
+            0,
+            true,
+        )
+    }
+
+    #[test]
+    fn short_drain() {
+        assert!(!draining(32).is_long_drain());
+    }
+
+    #[test]
+    fn long_drain() {
+        assert!(draining(33).is_long_drain());
+    }
+
+    #[test]
+    fn long_drain_only_in_data() {
+        let receive_error = ReceiveError::new(
+            Step::Checksum1,
+            error::Receive::UnexpectedLength(0x0100),
+            0,
+            true,
+        );
+
+        assert!(!receive_error.is_long_drain());
+    }
+
+    #[test]
+    fn footer_command_xor() {
+        assert_eq!(checksum_error(0, true).state.footer_command(), 0xf1);
+    }
+
+    #[test]
+    fn footer_command_no_xor() {
+        assert_eq!(checksum_error(0, false).state.footer_command(), 0x71);
+    }
+
+    #[test]
+    fn footer_command_last_attempt_no_xor() {
+        assert_eq!(
+            checksum_error(MAX_RETRIES - 1, false)
+                .state
+                .footer_command(),
+            0x0f
+        );
+    }
+}
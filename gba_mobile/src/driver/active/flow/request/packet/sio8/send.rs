@@ -1,5 +1,8 @@
 use super::{
-    super::{Data, MAX_RETRIES, Timeout, communication, error, schedule_serial},
+    super::{
+        Cursor, Data, MAX_RETRIES, Timeout, communication, error, schedule_serial,
+        verify_acknowledgement,
+    },
     WaitForReceive,
 };
 use crate::{
@@ -18,7 +21,7 @@ enum Step {
     HeaderLength1,
     HeaderLength2,
 
-    Data { index: u8 },
+    Data,
 
     Checksum1,
     Checksum2,
@@ -30,6 +33,7 @@ enum Step {
 #[derive(Debug)]
 pub(in crate::driver::active) struct Send {
     step: Step,
+    cursor: Cursor,
     checksum: u16,
     attempt: u8,
     frame: u8,
@@ -40,6 +44,7 @@ impl Send {
     pub(in super::super) fn new() -> Self {
         Self {
             step: Step::MagicByte1,
+            cursor: Cursor::new(),
             checksum: 0,
             attempt: 0,
             frame: 0,
@@ -51,6 +56,7 @@ impl Send {
     fn next(self, step: Step) -> Self {
         Self {
             step,
+            cursor: self.cursor,
             checksum: self.checksum,
             attempt: self.attempt,
             frame: 0,
@@ -62,6 +68,7 @@ impl Send {
     fn retry(self, new_attempt: u8) -> Self {
         Self {
             step: Step::MagicByte1,
+            cursor: Cursor::new(),
             checksum: 0,
             attempt: new_attempt,
             frame: 0,
@@ -73,6 +80,10 @@ impl Send {
 impl super::super::Send for Send {
     type WaitForReceive = WaitForReceive;
 
+    fn attempt(&self) -> u8 {
+        self.attempt
+    }
+
     fn vblank(&mut self) -> Result<(), Timeout> {
         if self.frame > frames::THREE_SECONDS {
             return Err(Timeout::Serial);
@@ -82,6 +93,7 @@ impl super::super::Send for Send {
         }
     }
 
+    #[cfg_attr(feature = "iwram", inline(never), unsafe(link_section = ".iwram"))]
     fn timer(&mut self, data: &Data) {
         if matches!(self.communication_state, communication::State::Send) {
             let byte = match self.step {
@@ -99,8 +111,8 @@ impl super::super::Send for Send {
                     self.checksum = self.checksum.wrapping_add(byte as u16);
                     byte
                 }
-                Step::Data { index } => {
-                    let byte = data.data.get(index).copied().unwrap_or(0x00);
+                Step::Data => {
+                    let byte = self.cursor.next_byte(data).unwrap_or(0x00);
                     self.checksum = self.checksum.wrapping_add(byte as u16);
                     byte
                 }
@@ -116,6 +128,7 @@ impl super::super::Send for Send {
         }
     }
 
+    #[cfg_attr(feature = "iwram", inline(never), unsafe(link_section = ".iwram"))]
     fn serial(self, data: &Data) -> Result<Either<Self, Self::WaitForReceive>, error::Send> {
         match self.communication_state {
             communication::State::Send => Ok(Either::Left(self)),
@@ -127,20 +140,11 @@ impl super::super::Send for Send {
                     Step::HeaderCommand => Ok(Either::Left(self.next(Step::HeaderEmptyByte))),
                     Step::HeaderEmptyByte => Ok(Either::Left(self.next(Step::HeaderLength1))),
                     Step::HeaderLength1 => Ok(Either::Left(self.next(Step::HeaderLength2))),
-                    Step::HeaderLength2 => {
-                        if data.data.is_empty() {
+                    Step::HeaderLength2 | Step::Data => {
+                        if self.cursor.is_exhausted(data) {
                             Ok(Either::Left(self.next(Step::Checksum1)))
                         } else {
-                            Ok(Either::Left(self.next(Step::Data { index: 0 })))
-                        }
-                    }
-                    Step::Data { index } => {
-                        if let Some(next_index) = index.checked_add(1)
-                            && data.data.len() > next_index
-                        {
-                            Ok(Either::Left(self.next(Step::Data { index: next_index })))
-                        } else {
-                            Ok(Either::Left(self.next(Step::Checksum1)))
+                            Ok(Either::Left(self.next(Step::Data)))
                         }
                     }
                     Step::Checksum1 => Ok(Either::Left(self.next(Step::Checksum2))),
@@ -169,9 +173,16 @@ impl super::super::Send for Send {
                                 Err(error::Send::AdapterInternalError)
                             }
                             _ => {
-                                // We don't verify anything here and simply assume the adapter
-                                // responded with a correct command. If the adapter is in an invalid
-                                // state, we will find out when receiving the response packet instead.
+                                // An acknowledgement of anything other than our own command means
+                                // the adapter may not have accepted the packet, so it is sent
+                                // again.
+                                if let Err(error) = verify_acknowledgement(data, byte) {
+                                    return if new_attempt < MAX_RETRIES {
+                                        Ok(Either::Left(self.retry(new_attempt)))
+                                    } else {
+                                        Err(error)
+                                    };
+                                }
                                 Ok(Either::Right(WaitForReceive::new(0)))
                             }
                         }
@@ -181,3 +192,114 @@ impl super::super::Send for Send {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::super::Send as _, Command, Cursor, Data, Either, MAX_RETRIES, SIODATA8, Send, Step,
+        WaitForReceive, communication, error,
+    };
+    use alloc::{format, vec::Vec};
+    use claims::{assert_err, assert_matches, assert_ok};
+    use gba_test::test;
+
+    /// Sends a `TransferData` packet holding the bytes `0..length` through every step, returning
+    /// the bytes sent.
+    fn send_all(length: u8) -> Vec<u8> {
+        let mut data = Data::new();
+        data.command = Command::TransferData;
+        for byte in 0..length {
+            data.data.try_push(byte).unwrap();
+        }
+
+        let mut send = Send::new();
+        let mut sent = Vec::new();
+        loop {
+            send.timer(&data);
+            sent.push(unsafe { SIODATA8.read_volatile() });
+            let reply = if matches!(send.step, Step::FooterCommand) {
+                Command::TransferData as u8 ^ 0x80
+            } else {
+                0xd2
+            };
+            unsafe { SIODATA8.write_volatile(reply) };
+            match assert_ok!(send.serial(&data)) {
+                Either::Left(next) => send = next,
+                Either::Right(_) => return sent,
+            }
+        }
+    }
+
+    /// Completes the acknowledgement of a `BeginSession` packet with the given command byte.
+    fn acknowledge(attempt: u8, byte: u8) -> Result<Either<Send, WaitForReceive>, error::Send> {
+        let send = Send {
+            step: Step::FooterCommand,
+            cursor: Cursor::new(),
+            checksum: 0,
+            attempt,
+            frame: 0,
+            communication_state: communication::State::Receive,
+        };
+        let mut data = Data::new();
+        data.command = Command::BeginSession;
+        unsafe { SIODATA8.write_volatile(byte) };
+        send.serial(&data)
+    }
+
+    #[test]
+    fn all_lengths_send_exactly_the_data() {
+        for length in 0..=254 {
+            let checksum = (0..length).fold(
+                Command::TransferData as u16 + length as u16,
+                |checksum, byte| checksum + byte as u16,
+            );
+            let expected = [0x99, 0x66, Command::TransferData as u8, 0x00, 0x00, length]
+                .into_iter()
+                .chain(0..length)
+                .chain([(checksum >> 8) as u8, checksum as u8, 0x81, 0x00])
+                .collect::<Vec<_>>();
+
+            assert_eq!(send_all(length), expected, "length {length}");
+        }
+    }
+
+    #[test]
+    fn acknowledgement_own_command() {
+        assert_matches!(assert_ok!(acknowledge(0, 0x90)), Either::Right(_));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "protocol-assertions", should_panic)]
+    fn acknowledgement_wrong_command_retries() {
+        let send = match assert_ok!(acknowledge(0, 0x91)) {
+            Either::Left(send) => send,
+            Either::Right(_) => panic!("wrong acknowledgement was accepted"),
+        };
+        assert_matches!(send.step, Step::MagicByte1);
+        assert_eq!(send.attempt, 1);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "protocol-assertions", should_panic)]
+    fn acknowledgement_wrong_command_error() {
+        let error = assert_err!(acknowledge(MAX_RETRIES - 1, 0x91));
+        assert_eq!(format!("{error:?}"), "BadAcknowledgement { received: 145 }");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "protocol-assertions", should_panic)]
+    fn acknowledgement_invalid_byte_retries() {
+        let send = match assert_ok!(acknowledge(0, 0xff)) {
+            Either::Left(send) => send,
+            Either::Right(_) => panic!("invalid acknowledgement was accepted"),
+        };
+        assert_eq!(send.attempt, 1);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "protocol-assertions", should_panic)]
+    fn acknowledgement_invalid_byte_error() {
+        let error = assert_err!(acknowledge(MAX_RETRIES - 1, 0xff));
+        assert_eq!(format!("{error:?}"), "BadAcknowledgement { received: 255 }");
+    }
+}
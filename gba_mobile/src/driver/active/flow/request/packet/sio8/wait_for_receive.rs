@@ -1,9 +1,9 @@
 use super::{
-    super::{Data, Timeout, communication, schedule_serial},
+    super::{Data, Timeout, capture, communication, schedule_serial},
     Receive, ReceiveError,
 };
 use crate::{
-    driver::frames,
+    driver::{Command, frames},
     mmio::serial::{SIODATA8, TransferLength},
 };
 use either::Either;
@@ -40,10 +40,12 @@ impl super::super::WaitForReceive for WaitForReceive {
     type Receive = Receive;
     type ReceiveError = ReceiveError;
 
+    fn attempt(&self) -> u8 {
+        self.attempt
+    }
+
     fn vblank(&mut self) -> Result<(), Timeout> {
-        if self.packet_frame > frames::FIFTEEN_SECONDS {
-            Err(Timeout::Packet)
-        } else if self.serial_frame > frames::THREE_SECONDS {
+        if self.serial_frame > frames::THREE_SECONDS {
             Err(Timeout::Serial)
         } else {
             if self.packet_frame % frames::ONE_HUNDRED_MILLISECONDS as u16 == 0
@@ -60,7 +62,12 @@ impl super::super::WaitForReceive for WaitForReceive {
         }
     }
 
-    fn serial(self, data: &mut Data) -> Result<Either<Self, Self::Receive>, Self::ReceiveError> {
+    #[cfg_attr(feature = "iwram", inline(never), unsafe(link_section = ".iwram"))]
+    fn serial(
+        self,
+        data: &mut Data,
+        _responses: &[Command],
+    ) -> Result<Either<Self, Self::Receive>, Self::ReceiveError> {
         match self.communication_state {
             communication::State::Send => Ok(Either::Left(self)),
             communication::State::Receive => {
@@ -69,10 +76,13 @@ impl super::super::WaitForReceive for WaitForReceive {
                 match byte {
                     0x99 => {
                         // Begin receiving the new packet.
-                        *data = Data::new();
+                        data.begin_response();
+                        data.capture.push(capture::Step::Magic, byte);
                         Ok(Either::Right(Receive::new(self.attempt)))
                     }
-                    // Anything else should be ignored.
+                    // Anything else should be ignored. This includes idle bytes and any trailing
+                    // bytes the adapter was still sending for the previous packet, so a late byte
+                    // never costs an attempt. Only frames count toward the timeouts.
                     _ => Ok(Either::Left(self.reset())),
                 }
             }
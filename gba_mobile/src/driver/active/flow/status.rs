@@ -1,8 +1,10 @@
 use super::{
-    super::{ConnectionFailure, Phase},
+    super::{ConnectionFailure, Phase, Queue},
     request::{Packet, packet, packet::payload},
 };
-use crate::{Adapter, Timer, mmio::serial::TransferLength};
+use crate::{
+    Adapter, Timer, config, connection::DisconnectReason, dns, mmio::serial::TransferLength, socket,
+};
 use either::Either;
 
 #[derive(Debug)]
@@ -33,13 +35,21 @@ impl Status {
         self.packet.timer(packet_data);
     }
 
-    pub(super) fn serial(
+    pub(super) fn serial<Socket1, Socket2, Dns, Config>(
         self,
         timer: Timer,
         adapter: &mut Adapter,
         packet_data: &mut packet::Data,
         phase: &mut Phase,
-    ) -> Result<Option<Self>, packet::Error<payload::ConnectionStatus>> {
+        queue: &mut Queue<Socket1, Socket2, Dns, Config>,
+        disconnect_reason: &mut Option<DisconnectReason>,
+    ) -> Result<Option<Self>, packet::Error<payload::ConnectionStatus>>
+    where
+        Socket1: socket::Slot,
+        Socket2: socket::Slot,
+        Dns: dns::Mode,
+        Config: config::Mode,
+    {
         self.packet
             .serial(timer, packet_data)
             .map(|response| match response {
@@ -52,7 +62,7 @@ impl Status {
                         // It is possible that we could have had the phase change between when we
                         // started execution of this flow and when we completed it. In that case, we do
                         // not want to overwrite the phase.
-                        Phase::Connected(frame) => {
+                        Phase::Connected { frame, .. } => {
                             match response.payload {
                                 payload::connection_status::Response::Connected => {
                                     // Reset the frame count so that we can trigger this flow again.
@@ -80,7 +90,17 @@ impl Status {
                                 }
                             }
                         }
-                        _ => {}
+                        _ => {
+                            if let payload::connection_status::Response::NotConnected =
+                                response.payload
+                                && queue.skip_disconnect()
+                            {
+                                // We have already left the call, but the hang-up has not been sent
+                                // yet. The other side has hung up as well, so there is nothing
+                                // left to hang up.
+                                *disconnect_reason = Some(DisconnectReason::MutualOrRemoteFirst);
+                            }
+                        }
                     }
                     None
                 }
@@ -16,6 +16,7 @@ pub(in crate::driver) enum Timeout {
     Accept(packet::Timeout),
     Connect(packet::Timeout),
     Login(login::Timeout),
+    Logout(packet::Timeout),
     Disconnect(packet::Timeout),
     OpenTcp(packet::Timeout),
     OpenUdp(packet::Timeout),
@@ -38,6 +39,7 @@ impl Display for Timeout {
             Self::Accept(_) => formatter.write_str("timeout during accept"),
             Self::Connect(_) => formatter.write_str("timeout during connect"),
             Self::Login(_) => formatter.write_str("timeout during login"),
+            Self::Logout(_) => formatter.write_str("timeout during logout"),
             Self::Disconnect(_) => formatter.write_str("timeout during disconnect"),
             Self::OpenTcp(_) => formatter.write_str("timeout during open tcp"),
             Self::OpenUdp(_) => formatter.write_str("timeout during open udp"),
@@ -62,6 +64,7 @@ impl core::error::Error for Timeout {
             Self::Accept(timeout) => Some(timeout),
             Self::Connect(timeout) => Some(timeout),
             Self::Login(timeout) => Some(timeout),
+            Self::Logout(timeout) => Some(timeout),
             Self::Disconnect(timeout) => Some(timeout),
             Self::OpenTcp(timeout) => Some(timeout),
             Self::OpenUdp(timeout) => Some(timeout),
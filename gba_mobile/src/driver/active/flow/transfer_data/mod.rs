@@ -68,8 +68,16 @@ impl TransferData {
                     Either::Left(packet) => Ok(Some(Self::TransferData(packet))),
                     Either::Right(response) => {
                         *adapter = response.adapter;
+                        let payload = response.payload;
+                        if packet_data.data.len() > 1
+                            && let Phase::Connected { quiet_frames, .. } = phase
+                        {
+                            // The first byte is the socket id, so anything beyond it was sent by
+                            // the peer.
+                            *quiet_frames = 0;
+                        }
                         if matches!(socket.status, socket::Status::Connected) {
-                            match response.payload {
+                            match payload {
                                 payload::transfer_data::Response::Data => {
                                     if packet_data.data.len() == 0 {
                                         if socket.read_buffer.is_empty() {
@@ -98,8 +106,10 @@ impl TransferData {
                                     }
                                 }
                                 payload::transfer_data::Response::ConnectionFailed => {
-                                    if matches!(phase, Phase::Connected(_) | Phase::LoggedIn { .. })
-                                    {
+                                    if matches!(
+                                        phase,
+                                        Phase::Connected { .. } | Phase::LoggedIn { .. }
+                                    ) {
                                         // If we are currently connected, this response indicates
                                         // that the connection has been terminated for some reason.
                                         *phase = Phase::Linked {
@@ -60,7 +60,7 @@ where
         adapter: &mut Adapter,
         packet_data: &mut packet::Data,
         transfer_length: TransferLength,
-        config: &Config<Format>,
+        config: &mut Config<Format>,
     ) -> Result<Option<Self>, packet::Error<payload::WriteConfig>> {
         match self.packet.serial(timer, packet_data)? {
             Either::Left(packet) => Ok(Some(Self {
@@ -71,7 +71,8 @@ where
             Either::Right(response) => {
                 *adapter = response.adapter;
                 if self.request + 1 == Format::WRITES {
-                    // We are done writing.
+                    // We are done writing. Only now has the adapter actually been initialized.
+                    config.written();
                     Ok(None)
                 } else {
                     // We still have more to write.
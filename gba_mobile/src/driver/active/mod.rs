@@ -6,15 +6,22 @@ mod timeout;
 pub(in crate::driver) use flow::Error;
 pub(in crate::driver) use timeout::Timeout;
 
+#[cfg(test)]
+pub(in crate::driver) use flow::request::packet::script;
+
 use crate::{
-    ArrayVec, Digit, Generation, Socket, Timer, config, dns,
-    driver::{Adapter, frames},
+    ArrayVec, Generation, Socket, Timer, config,
+    connection::{CallInfo, DisconnectReason},
+    digit, dns,
+    driver::{Adapter, Command, frames},
     mmio::serial::TransferLength,
     socket,
+    stats::LinkStats,
 };
 use core::{
     fmt::{self, Display, Formatter},
     net::{Ipv4Addr, SocketAddrV4},
+    num::NonZeroU8,
 };
 use flow::{Flow, request::packet};
 use queue::Queue;
@@ -25,10 +32,10 @@ enum ConnectionRequest {
         frame: u8,
     },
     Connect {
-        digits: ArrayVec<Digit, 32>,
+        digits: digit::NumberBuf,
     },
     Login {
-        digits: ArrayVec<Digit, 32>,
+        digits: digit::NumberBuf,
         id: ArrayVec<u8, 32>,
         password: ArrayVec<u8, 32>,
         primary_dns: Ipv4Addr,
@@ -55,6 +62,13 @@ impl Display for ConnectionFailure {
 
 impl core::error::Error for ConnectionFailure {}
 
+/// The state of a [`Phase::Connected`] call.
+struct Connected<'a> {
+    call: &'a CallInfo,
+    quiet_frames: u16,
+    call_frames: u32,
+}
+
 #[derive(Debug)]
 enum Phase {
     /// Attempting to link with a Mobile Adapter device.
@@ -68,7 +82,14 @@ enum Phase {
     /// Attempting to establish a connection.
     Connecting(ConnectionRequest),
     /// Connection established.
-    Connected(u8),
+    Connected {
+        frame: u8,
+        call: CallInfo,
+        /// Frames elapsed since data was last received from the peer.
+        quiet_frames: u16,
+        /// Frames elapsed since the call was established.
+        call_frames: u32,
+    },
     // Logged in to PPP.
     LoggedIn {
         frame: u8,
@@ -86,6 +107,30 @@ enum Phase {
     Ending,
 }
 
+/// A connection requested before linking completed.
+///
+/// Only the requests that can be made from a pending link are kept, which keeps this much smaller
+/// than a full [`ConnectionRequest`].
+#[derive(Debug)]
+enum QueuedConnection {
+    Accept,
+    Connect { digits: digit::NumberBuf },
+}
+
+#[cfg(feature = "debug-overlay")]
+impl Phase {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Linking => "linking",
+            Self::Linked { .. } => "linked",
+            Self::Connecting(_) => "connecting",
+            Self::Connected { .. } => "connected",
+            Self::LoggedIn { .. } => "logged in",
+            Self::Ending => "ending",
+        }
+    }
+}
+
 #[derive(Debug)]
 struct State {
     connection_generation: Generation,
@@ -94,8 +139,14 @@ struct State {
     adapter: Adapter,
 
     phase: Phase,
+    /// A connection requested while still linking, to be made once linking completes.
+    queued_connection: Option<QueuedConnection>,
 
     frame: u8,
+    /// Frames elapsed since the link was established.
+    session_frames: u32,
+    /// How the most recent hang-up ended, once it has.
+    disconnect_reason: Option<DisconnectReason>,
 
     packet_data: packet::Data,
 }
@@ -110,8 +161,11 @@ impl State {
             adapter: Adapter::Blue,
 
             phase: Phase::Linking,
+            queued_connection: None,
 
             frame: 0,
+            session_frames: 0,
+            disconnect_reason: None,
 
             packet_data: packet::Data::new(),
         }
@@ -150,6 +204,57 @@ where
         }
     }
 
+    /// Verify fields the adapter is only expected to leave empty, treating anything else as an
+    /// error.
+    pub(super) fn strict(mut self, strict: bool) -> Self {
+        self.state.packet_data.strict = strict;
+        self
+    }
+
+    /// Record a nonzero command byte in the adapter's acknowledgement signal as its extended
+    /// acknowledgement, rather than treating it as an error.
+    pub(super) fn extended_ack(mut self, extended_ack: bool) -> Self {
+        self.state.packet_data.extended_ack = extended_ack;
+        self
+    }
+
+    /// Define an active communication state that has already linked with the Mobile Adapter.
+    #[cfg(test)]
+    pub(in crate::driver) fn linked(link_generation: Generation) -> Self {
+        let mut active = Self::new(link_generation);
+        active.flow = None;
+        active.state.phase = Phase::Linked {
+            frame: 0,
+            connection_failure: None,
+        };
+        active
+    }
+
+    /// Whether no flow is currently exchanging packets with the adapter.
+    pub(super) fn is_idle(&self) -> bool {
+        self.flow.is_none()
+    }
+
+    /// Whether no flow is exchanging packets with the adapter, and none is waiting to.
+    ///
+    /// This takes the next item off the queue, if there is one.
+    #[cfg(test)]
+    pub(super) fn is_unscheduled(&mut self) -> bool {
+        self.is_idle() && self.queue.next().is_none()
+    }
+
+    /// Schedules hanging up the current call.
+    ///
+    /// This must be called before the phase is changed, since a call that is logged in to an ISP
+    /// is logged out of first.
+    fn hang_up(&mut self) {
+        if matches!(self.state.phase, Phase::LoggedIn { .. }) {
+            self.queue.set_logout();
+        }
+        self.queue.set_disconnect();
+        self.state.disconnect_reason = None;
+    }
+
     /// Start a new link, closing any existing link if one is active.
     pub(super) fn start_link(&mut self) {
         match self.state.phase {
@@ -164,6 +269,9 @@ where
             }
         }
         self.state.phase = Phase::Linking;
+        self.state.queued_connection = None;
+        self.state.disconnect_reason = None;
+        self.state.packet_data.stats = LinkStats::default();
     }
 
     pub(super) fn link_status(
@@ -177,15 +285,61 @@ where
     }
 
     /// End the existing session.
+    ///
+    /// Teardown happens in a fixed order: any pending config write completes first, then an
+    /// active call is hung up, and only then is the session ended. Hanging up also closes any
+    /// sockets that were opened over the call.
     pub(super) fn close_link(
         &mut self,
     ) -> Result<(), super::error::link::Error<Socket1, Socket2, Dns, Config>> {
-        if matches!(self.state.phase, Phase::Ending) {
-            Err(super::error::link::Error::closed())
-        } else {
-            self.queue.set_end();
-            self.state.phase = Phase::Ending;
-            Ok(())
+        match self.state.phase {
+            Phase::Ending => Err(super::error::link::Error::closed()),
+            Phase::Connecting(_) | Phase::Connected { .. } | Phase::LoggedIn { .. } => {
+                self.hang_up();
+                self.queue.set_end();
+                self.state.phase = Phase::Ending;
+                Ok(())
+            }
+            _ => {
+                self.queue.set_end();
+                self.state.phase = Phase::Ending;
+                Ok(())
+            }
+        }
+    }
+
+    /// Replaces any current connection with a new one.
+    fn request_connection(&mut self, request: ConnectionRequest) -> Generation {
+        self.state.connection_generation = self.state.connection_generation.increment();
+        if matches!(
+            self.state.phase,
+            Phase::Connecting(_) | Phase::Connected { .. } | Phase::LoggedIn { .. }
+        ) {
+            // If we are already connected or attempting to connect, disconnect first.
+            self.hang_up();
+        }
+        self.state.phase = Phase::Connecting(request);
+        self.queue.set_connect();
+        self.state.connection_generation
+    }
+
+    /// Holds a connection requested while linking until linking completes.
+    fn queue_connection(&mut self, connection: QueuedConnection) -> Generation {
+        self.state.connection_generation = self.state.connection_generation.increment();
+        self.state.queued_connection = Some(connection);
+        self.state.connection_generation
+    }
+
+    /// Starts the connection requested while linking, now that linking has completed.
+    fn start_queued_connection(&mut self) {
+        if matches!(self.state.phase, Phase::Linked { .. })
+            && let Some(connection) = self.state.queued_connection.take()
+        {
+            self.state.phase = Phase::Connecting(match connection {
+                QueuedConnection::Accept => ConnectionRequest::Accept { frame: 255 },
+                QueuedConnection::Connect { digits } => ConnectionRequest::Connect { digits },
+            });
+            self.queue.set_connect();
         }
     }
 
@@ -197,39 +351,25 @@ where
             return Err(super::error::link::Error::closed());
         }
 
-        self.state.connection_generation = self.state.connection_generation.increment();
-        if matches!(
-            self.state.phase,
-            Phase::Connecting(_) | Phase::Connected(_) | Phase::LoggedIn { .. }
-        ) {
-            // If we are already connected or attempting to connect, disconnect first.
-            self.queue.set_disconnect();
+        if matches!(self.state.phase, Phase::Linking) {
+            return Ok(self.queue_connection(QueuedConnection::Accept));
         }
-        self.state.phase = Phase::Connecting(ConnectionRequest::Accept { frame: 255 });
-        self.queue.set_connect();
-        Ok(self.state.connection_generation)
+        Ok(self.request_connection(ConnectionRequest::Accept { frame: 255 }))
     }
 
     /// Connect to a p2p peer.
     pub(super) fn connect(
         &mut self,
-        digits: ArrayVec<Digit, 32>,
+        digits: digit::NumberBuf,
     ) -> Result<Generation, super::error::link::Error<Socket1, Socket2, Dns, Config>> {
         if matches!(self.state.phase, Phase::Ending) {
             return Err(super::error::link::Error::closed());
         }
 
-        self.state.connection_generation = self.state.connection_generation.increment();
-        if matches!(
-            self.state.phase,
-            Phase::Connecting(_) | Phase::Connected(_) | Phase::LoggedIn { .. }
-        ) {
-            // If we are already connected or attempting to connect, disconnect first.
-            self.queue.set_disconnect();
+        if matches!(self.state.phase, Phase::Linking) {
+            return Ok(self.queue_connection(QueuedConnection::Connect { digits }));
         }
-        self.state.phase = Phase::Connecting(ConnectionRequest::Connect { digits });
-        self.queue.set_connect();
-        Ok(self.state.connection_generation)
+        Ok(self.request_connection(ConnectionRequest::Connect { digits }))
     }
 
     pub(super) fn disconnect(
@@ -241,17 +381,23 @@ where
         }
 
         match self.state.phase {
-            Phase::Linking => Err(super::error::connection::Error::superseded()),
+            Phase::Linking => {
+                if self.state.queued_connection.take().is_some() {
+                    Ok(())
+                } else {
+                    Err(super::error::connection::Error::superseded())
+                }
+            }
             Phase::Linked { .. } => {
                 // Not connected, so no need to disconnect.
                 Ok(())
             }
-            Phase::Connecting(_) | Phase::Connected(_) | Phase::LoggedIn { .. } => {
+            Phase::Connecting(_) | Phase::Connected { .. } | Phase::LoggedIn { .. } => {
+                self.hang_up();
                 self.state.phase = Phase::Linked {
                     frame: 0,
                     connection_failure: None,
                 };
-                self.queue.set_disconnect();
                 Ok(())
             }
             Phase::Ending => Err(super::error::link::Error::closed().into()),
@@ -261,33 +407,29 @@ where
     /// Connect via PPP protocol.
     pub(super) fn login(
         &mut self,
-        digits: ArrayVec<Digit, 32>,
+        digits: digit::NumberBuf,
         id: ArrayVec<u8, 32>,
         password: ArrayVec<u8, 32>,
         primary_dns: Ipv4Addr,
         secondary_dns: Ipv4Addr,
+        config: &Config,
     ) -> Result<Generation, super::error::link::Error<Socket1, Socket2, Dns, Config>> {
         if matches!(self.state.phase, Phase::Ending) {
             return Err(super::error::link::Error::closed());
         }
-
-        self.state.connection_generation = self.state.connection_generation.increment();
-        if matches!(
-            self.state.phase,
-            Phase::Connecting(_) | Phase::Connected(_) | Phase::LoggedIn { .. }
-        ) {
-            // If we are already connected or attempting to connect, disconnect first.
-            self.queue.set_disconnect();
+        if config.needs_initialization() {
+            // Logging in relies on the adapter's configuration, so there is no point in
+            // attempting it until the configuration has been written.
+            return Err(super::error::link::Error::not_initialized());
         }
-        self.state.phase = Phase::Connecting(ConnectionRequest::Login {
+
+        Ok(self.request_connection(ConnectionRequest::Login {
             digits,
             id,
             password,
             primary_dns,
             secondary_dns,
-        });
-        self.queue.set_connect();
-        Ok(self.state.connection_generation)
+        }))
     }
 
     pub(crate) fn connection_status(
@@ -299,7 +441,13 @@ where
         }
 
         match &self.state.phase {
-            Phase::Linking => Err(super::error::connection::Error::superseded()),
+            Phase::Linking => {
+                if self.state.queued_connection.is_some() {
+                    Ok(false)
+                } else {
+                    Err(super::error::connection::Error::superseded())
+                }
+            }
             Phase::Linked {
                 connection_failure: Some(failure),
                 ..
@@ -309,11 +457,70 @@ where
                 ..
             } => Err(super::error::connection::Error::closed()),
             Phase::Connecting(_) => Ok(false),
-            Phase::Connected(_) | Phase::LoggedIn { .. } => Ok(true),
+            Phase::Connected { .. } | Phase::LoggedIn { .. } => Ok(true),
             Phase::Ending => Err(super::error::link::Error::closed().into()),
         }
     }
 
+    /// The call of the connection with the given generation, if it is still connected.
+    fn connected(
+        &self,
+        connection_generation: Generation,
+    ) -> Result<Connected<'_>, super::error::connection::Error<Socket1, Socket2, Dns, Config>> {
+        if self.state.connection_generation != connection_generation {
+            return Err(super::error::connection::Error::superseded());
+        }
+
+        match &self.state.phase {
+            Phase::Linking => Err(super::error::connection::Error::superseded()),
+            Phase::Linked {
+                connection_failure: Some(failure),
+                ..
+            } => Err(failure.clone().into()),
+            Phase::Linked {
+                connection_failure: None,
+                ..
+            } => Err(super::error::connection::Error::closed()),
+            Phase::Connecting(_) => Err(super::error::connection::Error::superseded()),
+            Phase::LoggedIn { .. } => Err(super::error::connection::Error::superseded()),
+            Phase::Ending => Err(super::error::link::Error::closed().into()),
+            Phase::Connected {
+                call,
+                quiet_frames,
+                call_frames,
+                ..
+            } => Ok(Connected {
+                call,
+                quiet_frames: *quiet_frames,
+                call_frames: *call_frames,
+            }),
+        }
+    }
+
+    pub(crate) fn connection_info(
+        &self,
+        connection_generation: Generation,
+    ) -> Result<&CallInfo, super::error::connection::Error<Socket1, Socket2, Dns, Config>> {
+        self.connected(connection_generation)
+            .map(|connected| connected.call)
+    }
+
+    pub(crate) fn peer_quiet_frames(
+        &self,
+        connection_generation: Generation,
+    ) -> Result<u16, super::error::connection::Error<Socket1, Socket2, Dns, Config>> {
+        self.connected(connection_generation)
+            .map(|connected| connected.quiet_frames)
+    }
+
+    pub(crate) fn call_frames(
+        &self,
+        connection_generation: Generation,
+    ) -> Result<u32, super::error::connection::Error<Socket1, Socket2, Dns, Config>> {
+        self.connected(connection_generation)
+            .map(|connected| connected.call_frames)
+    }
+
     pub(super) fn connection_read<Buffer>(
         &mut self,
         connection_generation: Generation,
@@ -343,7 +550,7 @@ where
             Phase::Connecting(_) => Err(super::error::connection::Error::superseded().into()),
             Phase::LoggedIn { .. } => Err(super::error::connection::Error::superseded().into()),
             Phase::Ending => Err(super::error::connection::Error::closed().into()),
-            Phase::Connected(_) => {
+            Phase::Connected { .. } => {
                 let read_amount = socket
                     .read(buf)
                     .map_err(super::error::connection_io::Error::io)?;
@@ -384,7 +591,7 @@ where
             Phase::Connecting(_) => Err(super::error::connection::Error::superseded()),
             Phase::LoggedIn { .. } => Err(super::error::connection::Error::superseded()),
             Phase::Ending => Err(super::error::connection::Error::closed()),
-            Phase::Connected(_) => {
+            Phase::Connected { .. } => {
                 let write_amount = socket.write(buf);
 
                 if socket.write_buffer.is_full() {
@@ -423,7 +630,7 @@ where
             Phase::Connecting(_) => Err(super::error::connection::Error::superseded()),
             Phase::LoggedIn { .. } => Err(super::error::connection::Error::superseded()),
             Phase::Ending => Err(super::error::connection::Error::closed()),
-            Phase::Connected(_) => {
+            Phase::Connected { .. } => {
                 self.queue.set_socket_1_transfer();
                 // Accelerate the next automatic transfer.
                 socket.frame = u8::MAX;
@@ -454,7 +661,7 @@ where
                 ..
             } => Err(super::error::connection::Error::closed()),
             Phase::Connecting(_) => Err(super::error::connection::Error::superseded()),
-            Phase::Connected(_) => Err(super::error::connection::Error::superseded()),
+            Phase::Connected { .. } => Err(super::error::connection::Error::superseded()),
             Phase::Ending => Err(super::error::link::Error::closed().into()),
             Phase::LoggedIn {
                 socket_generations,
@@ -497,7 +704,7 @@ where
                 ..
             } => Err(super::error::connection::Error::closed().into()),
             Phase::Connecting(_) => Err(super::error::connection::Error::superseded().into()),
-            Phase::Connected(_) => Err(super::error::connection::Error::superseded().into()),
+            Phase::Connected { .. } => Err(super::error::connection::Error::superseded().into()),
             Phase::Ending => Err(super::error::link::Error::closed().into()),
             Phase::LoggedIn {
                 socket_generations, ..
@@ -542,7 +749,7 @@ where
                 ..
             } => Err(super::error::connection::Error::closed().into()),
             Phase::Connecting(_) => Err(super::error::connection::Error::superseded().into()),
-            Phase::Connected(_) => Err(super::error::connection::Error::superseded().into()),
+            Phase::Connected { .. } => Err(super::error::connection::Error::superseded().into()),
             Phase::Ending => Err(super::error::link::Error::closed().into()),
             Phase::LoggedIn {
                 socket_generations, ..
@@ -601,7 +808,7 @@ where
                 ..
             } => Err(super::error::connection::Error::closed().into()),
             Phase::Connecting(_) => Err(super::error::connection::Error::superseded().into()),
-            Phase::Connected(_) => Err(super::error::connection::Error::superseded().into()),
+            Phase::Connected { .. } => Err(super::error::connection::Error::superseded().into()),
             Phase::Ending => Err(super::error::link::Error::closed().into()),
             Phase::LoggedIn {
                 socket_generations, ..
@@ -669,7 +876,7 @@ where
                 ..
             } => Err(super::error::connection::Error::closed().into()),
             Phase::Connecting(_) => Err(super::error::connection::Error::superseded().into()),
-            Phase::Connected(_) => Err(super::error::connection::Error::superseded().into()),
+            Phase::Connected { .. } => Err(super::error::connection::Error::superseded().into()),
             Phase::Ending => Err(super::error::link::Error::closed().into()),
             Phase::LoggedIn {
                 socket_generations, ..
@@ -735,7 +942,7 @@ where
                 ..
             } => Err(super::error::connection::Error::closed().into()),
             Phase::Connecting(_) => Err(super::error::connection::Error::superseded().into()),
-            Phase::Connected(_) => Err(super::error::connection::Error::superseded().into()),
+            Phase::Connected { .. } => Err(super::error::connection::Error::superseded().into()),
             Phase::Ending => Err(super::error::link::Error::closed().into()),
             Phase::LoggedIn {
                 socket_generations, ..
@@ -793,7 +1000,7 @@ where
                 ..
             } => Err(super::error::connection::Error::closed().into()),
             Phase::Connecting(_) => Err(super::error::connection::Error::superseded()),
-            Phase::Connected(_) => Err(super::error::connection::Error::superseded()),
+            Phase::Connected { .. } => Err(super::error::connection::Error::superseded()),
             Phase::Ending => Err(super::error::link::Error::closed().into()),
             Phase::LoggedIn { .. } => {
                 dns.state = dns::State::Request(name);
@@ -825,7 +1032,7 @@ where
                 ..
             } => Err(super::error::connection::Error::closed().into()),
             Phase::Connecting(_) => Err(super::error::connection::Error::superseded().into()),
-            Phase::Connected(_) => Err(super::error::connection::Error::superseded().into()),
+            Phase::Connected { .. } => Err(super::error::connection::Error::superseded().into()),
             Phase::Ending => Err(super::error::link::Error::closed().into()),
             Phase::LoggedIn { .. } => {
                 if dns.generation != dns_generation {
@@ -863,7 +1070,7 @@ where
                 ..
             } => Err(super::error::connection::Error::closed().into()),
             Phase::Connecting(_) => Err(super::error::connection::Error::superseded().into()),
-            Phase::Connected(_) => Err(super::error::connection::Error::superseded().into()),
+            Phase::Connected { .. } => Err(super::error::connection::Error::superseded().into()),
             Phase::Ending => Err(super::error::link::Error::closed().into()),
             Phase::LoggedIn { .. } => {
                 if dns.generation != dns_generation {
@@ -887,6 +1094,63 @@ where
         }
     }
 
+    pub(crate) fn session_frames(
+        &self,
+    ) -> Result<u32, super::error::link::Error<Socket1, Socket2, Dns, Config>> {
+        if matches!(self.state.phase, Phase::Ending) {
+            Err(super::error::link::Error::closed())
+        } else {
+            Ok(self.state.session_frames)
+        }
+    }
+
+    pub(crate) fn disconnect_reason(
+        &self,
+    ) -> Result<Option<DisconnectReason>, super::error::link::Error<Socket1, Socket2, Dns, Config>>
+    {
+        if matches!(self.state.phase, Phase::Ending) {
+            Err(super::error::link::Error::closed())
+        } else {
+            Ok(self.state.disconnect_reason)
+        }
+    }
+
+    pub(crate) fn link_stats(
+        &self,
+    ) -> Result<LinkStats, super::error::link::Error<Socket1, Socket2, Dns, Config>> {
+        if matches!(self.state.phase, Phase::Ending) {
+            Err(super::error::link::Error::closed())
+        } else {
+            Ok(self.state.packet_data.stats)
+        }
+    }
+
+    /// The statistics for this session, along with the command most recently exchanged.
+    pub(super) fn session_summary(&self) -> (LinkStats, Command) {
+        (self.state.packet_data.stats, self.state.packet_data.command)
+    }
+
+    pub(crate) fn reset_link_stats(
+        &mut self,
+    ) -> Result<(), super::error::link::Error<Socket1, Socket2, Dns, Config>> {
+        if matches!(self.state.phase, Phase::Ending) {
+            Err(super::error::link::Error::closed())
+        } else {
+            self.state.packet_data.stats = LinkStats::default();
+            Ok(())
+        }
+    }
+
+    pub(crate) fn extended_acknowledgement(
+        &self,
+    ) -> Result<Option<NonZeroU8>, super::error::link::Error<Socket1, Socket2, Dns, Config>> {
+        if matches!(self.state.phase, Phase::Ending) {
+            Err(super::error::link::Error::closed())
+        } else {
+            Ok(self.state.packet_data.extended_acknowledgement)
+        }
+    }
+
     pub(crate) fn ip(
         &self,
         connection_generation: Generation,
@@ -906,7 +1170,7 @@ where
                 ..
             } => Err(super::error::connection::Error::closed().into()),
             Phase::Connecting(_) => Err(super::error::connection::Error::superseded()),
-            Phase::Connected(_) => Err(super::error::connection::Error::superseded()),
+            Phase::Connected { .. } => Err(super::error::connection::Error::superseded()),
             Phase::Ending => Err(super::error::link::Error::closed().into()),
             Phase::LoggedIn { ip, .. } => Ok(*ip),
         }
@@ -931,7 +1195,7 @@ where
                 ..
             } => Err(super::error::connection::Error::closed().into()),
             Phase::Connecting(_) => Err(super::error::connection::Error::superseded()),
-            Phase::Connected(_) => Err(super::error::connection::Error::superseded()),
+            Phase::Connected { .. } => Err(super::error::connection::Error::superseded()),
             Phase::Ending => Err(super::error::link::Error::closed().into()),
             Phase::LoggedIn { primary_dns, .. } => Ok(*primary_dns),
         }
@@ -956,12 +1220,23 @@ where
                 ..
             } => Err(super::error::connection::Error::closed().into()),
             Phase::Connecting(_) => Err(super::error::connection::Error::superseded()),
-            Phase::Connected(_) => Err(super::error::connection::Error::superseded()),
+            Phase::Connected { .. } => Err(super::error::connection::Error::superseded()),
             Phase::Ending => Err(super::error::link::Error::closed().into()),
             Phase::LoggedIn { secondary_dns, .. } => Ok(*secondary_dns),
         }
     }
 
+    pub(crate) fn needs_initialization(
+        &self,
+        config: &Config,
+    ) -> Result<bool, super::error::link::Error<Socket1, Socket2, Dns, Config>> {
+        if matches!(self.state.phase, Phase::Ending) {
+            Err(super::error::link::Error::closed())
+        } else {
+            Ok(config.needs_initialization())
+        }
+    }
+
     pub(crate) unsafe fn config<Format>(
         &self,
         config: &crate::Config<Format>,
@@ -1000,6 +1275,75 @@ where
         }
     }
 
+    /// Writes `defaults` to the adapter if its configuration was found to be uninitialized,
+    /// returning whether they were written.
+    pub(crate) fn initialize_config<Format>(
+        &mut self,
+        config: &mut crate::Config<Format>,
+        defaults: Format,
+    ) -> Result<bool, super::error::link::Error<Socket1, Socket2, Dns, Config>>
+    where
+        Format: config::Format,
+    {
+        if matches!(self.state.phase, Phase::Ending) {
+            Err(super::error::link::Error::closed())
+        } else if config::Sealed::needs_initialization(config) {
+            self.write_config(config, defaults)?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    #[cfg(feature = "debug-overlay")]
+    pub(super) fn render_overlay(
+        &self,
+        lines: &mut crate::overlay::Lines,
+        last_error: crate::overlay::LastError,
+    ) {
+        let packet_data = &self.state.packet_data;
+        lines.line(format_args!("phase: {}", self.state.phase.name()));
+        if let Some(flow) = &self.flow {
+            lines.line(format_args!("flow: {}", flow.name()));
+            lines.line(format_args!("command: {:?}", packet_data.command));
+        } else {
+            lines.line(format_args!("flow: none"));
+            lines.line(format_args!("command: none"));
+        }
+        lines.line(format_args!(
+            "{} {:?} attempt: {}",
+            match self.state.transfer_length {
+                TransferLength::_8Bit => "sio8",
+                TransferLength::_32Bit => "sio32",
+            },
+            self.state.adapter,
+            packet_data.attempt
+        ));
+        lines.line(format_args!(
+            "sent: {} received: {}",
+            packet_data.stats.packets_sent, packet_data.stats.packets_received
+        ));
+        lines.line(format_args!(
+            "errors: {} retries: {}",
+            packet_data.stats.receive_errors, packet_data.stats.retries
+        ));
+        lines.line(format_args!(
+            "frames: {} last error: {last_error}",
+            self.state.frame
+        ));
+        if let Phase::Connected { call_frames, .. } = &self.state.phase {
+            lines.line(format_args!(
+                "session: {} call: {call_frames}",
+                self.state.session_frames
+            ));
+        } else {
+            lines.line(format_args!(
+                "session: {} call: none",
+                self.state.session_frames
+            ));
+        }
+    }
+
     pub(super) fn vblank(
         &mut self,
         timer: Timer,
@@ -1009,6 +1353,13 @@ where
         dns: &Dns,
         config: &Config,
     ) -> Result<StateChange, Timeout> {
+        self.start_queued_connection();
+        if matches!(self.state.phase, Phase::Linking) {
+            self.state.session_frames = 0;
+        } else {
+            self.state.session_frames = self.state.session_frames.saturating_add(1);
+        }
+
         match &mut self.state.phase {
             Phase::Linked { frame, .. } => {
                 if *frame == frames::ONE_SECOND {
@@ -1027,7 +1378,14 @@ where
                 }
                 *frame = frame.saturating_add(1);
             }
-            Phase::Connected(frame) => {
+            Phase::Connected {
+                frame,
+                quiet_frames,
+                call_frames,
+                ..
+            } => {
+                *quiet_frames = quiet_frames.saturating_add(1);
+                *call_frames = call_frames.saturating_add(1);
                 if *frame == frames::ONE_SECOND {
                     // Schedule a new status flow once per second.
                     //
@@ -1079,6 +1437,13 @@ where
             } else {
                 Ok(StateChange::Inactive)
             }
+        } else if self.state.frame > frames::THREE_SECONDS {
+            // Three seconds is how long the adapter will remain connected without any bytes
+            // sent to it, so this timeout should align with the disconnect.
+            //
+            // This is checked before scheduling anything new, since a request made after the
+            // adapter has disconnected can't succeed anyway.
+            Err(Timeout::Queue)
         } else if let Some(new_flow) = self.queue.next_flow(
             &mut self.state,
             timer,
@@ -1092,10 +1457,6 @@ where
             self.state.frame = 0;
             self.flow = Some(new_flow);
             Ok(StateChange::StillActive)
-        } else if self.state.frame > frames::THREE_SECONDS {
-            // Three seconds is how long the adapter will remain connected without any bytes
-            // sent to it, so this timeout should align with the disconnect.
-            Err(Timeout::Queue)
         } else {
             // No flow being processed and none on the queue. Increment the frame so that we
             // timeout if we remain in this state too long.
@@ -1141,3 +1502,899 @@ pub(in crate::driver) enum StateChange {
     StillActive,
     Inactive,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Active, ConnectionRequest, Phase,
+        flow::{
+            ConfigSubFlow, Flow,
+            request::packet::{MAX_RETRIES, script},
+        },
+        frames,
+        queue::item::Item,
+    };
+    use crate::{
+        ArrayVec, Config, Digit, Generation, Socket, Timer,
+        config::{self, NoConfig, mobile_system_gb},
+        connection::{Bearer, CallInfo, DisconnectReason},
+        dns::NoDns,
+        driver::{Adapter, Command},
+        mmio::serial::TransferLength,
+        socket,
+        socket::NoSocket,
+    };
+    use alloc::{format, vec::Vec};
+    use claims::{assert_err, assert_ok};
+    use core::{iter, net::Ipv4Addr};
+    use gba_test::test;
+
+    type TestActive = Active<NoSocket, NoSocket, NoDns, Config<mobile_system_gb::Config>>;
+
+    /// Links `active` with an adapter whose configuration was never written, reading the blank
+    /// block it returns.
+    fn link_uninitialized(active: &mut TestActive, config: &mut Config<mobile_system_gb::Config>) {
+        active.flow = <Config<mobile_system_gb::Config> as config::Sealed>::Flow::read_config(
+            TransferLength::_8Bit,
+            Timer::_0,
+            &mut active.state.packet_data,
+            Generation::new(),
+            config,
+        )
+        .map(Flow::Config);
+        let block = iter::once(0x00)
+            .chain(iter::repeat_n(0xff, 0x71))
+            .collect::<Vec<_>>();
+        let adapter = script::acknowledge(TransferLength::_8Bit, Command::ReadConfigurationData, 2)
+            .chain(script::response(
+                TransferLength::_8Bit,
+                Command::ReadConfigurationData,
+                &block,
+            ));
+        assert_ok!(script::run(
+            active,
+            &mut NoSocket,
+            &mut NoSocket,
+            &mut NoDns,
+            config,
+            adapter,
+        ));
+        assert!(matches!(active.state.phase, Phase::Linked { .. }));
+    }
+
+    /// Logs in to an ISP, using the adapter's configuration.
+    fn login(
+        active: &mut TestActive,
+        config: &Config<mobile_system_gb::Config>,
+    ) -> Result<
+        Generation,
+        crate::driver::error::link::Error<
+            NoSocket,
+            NoSocket,
+            NoDns,
+            Config<mobile_system_gb::Config>,
+        >,
+    > {
+        let mut digits = ArrayVec::new();
+        digits.write(&['1', '2', '3'].map(|c| assert_ok!(Digit::try_from(c))));
+        active.login(
+            digits,
+            ArrayVec::new(),
+            ArrayVec::new(),
+            Ipv4Addr::UNSPECIFIED,
+            Ipv4Addr::UNSPECIFIED,
+            config,
+        )
+    }
+
+    #[test]
+    fn not_idle_while_linking() {
+        let active = TestActive::new(Generation::new());
+
+        assert!(!active.is_idle());
+    }
+
+    #[test]
+    fn idle_between_flows() {
+        let mut active = TestActive::new(Generation::new());
+        active.flow = None;
+
+        assert!(active.is_idle());
+    }
+
+    #[test]
+    fn close_link_during_call() {
+        let mut active = TestActive::new(Generation::new());
+        active.state.phase = Phase::Connected {
+            frame: 0,
+            call: CallInfo::inbound(Bearer::Pdc),
+            quiet_frames: 0,
+            call_frames: 0,
+        };
+        let connection_generation = active.state.connection_generation;
+        active.queue.set_write_config();
+
+        assert!(active.close_link().is_ok());
+
+        assert!(matches!(active.queue.next(), Some(Item::Config(_))));
+        assert!(matches!(active.queue.next(), Some(Item::Disconnect)));
+        assert!(matches!(active.queue.next(), Some(Item::End)));
+        assert!(active.queue.next().is_none());
+
+        assert_eq!(
+            format!("{:?}", active.connection_status(connection_generation)),
+            "Err(Link(Closed))"
+        );
+        assert_eq!(format!("{:?}", active.link_status()), "Err(Closed)");
+    }
+
+    #[test]
+    fn close_link_while_logged_in() {
+        let mut active = TestActive::new(Generation::new());
+        active.state.phase = Phase::LoggedIn {
+            frame: 0,
+            ip: Ipv4Addr::UNSPECIFIED,
+            primary_dns: Ipv4Addr::UNSPECIFIED,
+            secondary_dns: Ipv4Addr::UNSPECIFIED,
+            socket_generations: [Generation::new(); 2],
+            socket_requests: [None, None],
+            socket_protocols: [socket::Protocol::Tcp, socket::Protocol::Tcp],
+        };
+
+        assert!(active.close_link().is_ok());
+
+        assert!(matches!(active.queue.next(), Some(Item::Logout)));
+        assert!(matches!(active.queue.next(), Some(Item::Disconnect)));
+        assert!(matches!(active.queue.next(), Some(Item::End)));
+        assert!(active.queue.next().is_none());
+    }
+
+    #[test]
+    fn close_link_while_linked() {
+        let mut active = TestActive::new(Generation::new());
+        active.state.phase = Phase::Linked {
+            frame: 0,
+            connection_failure: None,
+        };
+
+        assert!(active.close_link().is_ok());
+
+        assert!(matches!(active.queue.next(), Some(Item::End)));
+        assert!(active.queue.next().is_none());
+    }
+
+    #[test]
+    fn close_link_twice() {
+        let mut active = TestActive::new(Generation::new());
+        assert!(active.close_link().is_ok());
+
+        assert_eq!(format!("{:?}", active.close_link()), "Err(Closed)");
+    }
+
+    #[test]
+    fn connection_info_inbound() {
+        let mut active = TestActive::new(Generation::new());
+        active.state.phase = Phase::Connecting(ConnectionRequest::Accept { frame: 0 });
+        let connection_generation = active.state.connection_generation;
+        assert_err!(active.connection_info(connection_generation));
+
+        active.state.phase = Phase::Connected {
+            frame: 0,
+            call: CallInfo::inbound(Bearer::Pdc),
+            quiet_frames: 0,
+            call_frames: 0,
+        };
+        let info = assert_ok!(active.connection_info(connection_generation));
+
+        assert!(info.is_inbound());
+        assert_eq!(info.remote_number(), None);
+    }
+
+    #[test]
+    fn connection_info_accepted() {
+        let mut active =
+            Active::<Socket<[u8; 0]>, NoSocket, NoDns, NoConfig>::linked(Generation::new());
+        let mut socket = Socket::new([]);
+        let connection_generation = assert_ok!(active.accept());
+        // The call is answered by a Yellow adapter.
+        let mut response =
+            script::response(TransferLength::_8Bit, Command::WaitForTelephoneCall, &[]);
+        let device = response.len() - 2;
+        response[device] = Adapter::Yellow as u8;
+        let adapter = script::acknowledge(TransferLength::_8Bit, Command::WaitForTelephoneCall, 0)
+            .chain(response);
+        assert_ok!(script::run(
+            &mut active,
+            &mut socket,
+            &mut NoSocket,
+            &mut NoDns,
+            &mut NoConfig,
+            adapter,
+        ));
+
+        let info = assert_ok!(active.connection_info(connection_generation));
+        assert!(info.is_inbound());
+        assert_eq!(info.bearer(), Bearer::CdmaOne);
+        assert_eq!(info.remote_number(), None);
+    }
+
+    #[test]
+    fn connection_info_dialed() {
+        let mut active =
+            Active::<Socket<[u8; 0]>, NoSocket, NoDns, NoConfig>::linked(Generation::new());
+        let mut socket = Socket::new([]);
+        let number = ['1', '2', '3'].map(|c| assert_ok!(Digit::try_from(c)));
+        let mut digits = ArrayVec::new();
+        digits.write(&number);
+        let connection_generation = assert_ok!(active.connect(digits));
+        let adapter = script::acknowledge(TransferLength::_8Bit, Command::DialTelephone, 4).chain(
+            script::response(TransferLength::_8Bit, Command::DialTelephone, &[]),
+        );
+        assert_ok!(script::run(
+            &mut active,
+            &mut socket,
+            &mut NoSocket,
+            &mut NoDns,
+            &mut NoConfig,
+            adapter,
+        ));
+
+        let info = assert_ok!(active.connection_info(connection_generation));
+        assert!(!info.is_inbound());
+        assert_eq!(info.bearer(), Bearer::Pdc);
+        assert_eq!(info.remote_number(), Some(number.as_slice()));
+    }
+
+    #[test]
+    fn connection_info_outbound() {
+        let mut active = TestActive::new(Generation::new());
+        let number = ['1', '2', '3'].map(|c| assert_ok!(Digit::try_from(c)));
+        let mut digits = ArrayVec::new();
+        digits.write(&number);
+        active.state.phase = Phase::Connected {
+            frame: 0,
+            call: CallInfo::outbound(digits, Bearer::Pdc),
+            quiet_frames: 0,
+            call_frames: 0,
+        };
+        let info = assert_ok!(active.connection_info(active.state.connection_generation));
+
+        assert!(!info.is_inbound());
+        assert_eq!(info.bearer(), Bearer::Pdc);
+        assert_eq!(info.remote_number(), Some(number.as_slice()));
+    }
+
+    #[test]
+    fn connection_info_superseded() {
+        let mut active = TestActive::new(Generation::new());
+        active.state.phase = Phase::Connected {
+            frame: 0,
+            call: CallInfo::inbound(Bearer::Pdc),
+            quiet_frames: 0,
+            call_frames: 0,
+        };
+
+        assert_eq!(
+            format!(
+                "{:?}",
+                active.connection_info(active.state.connection_generation.increment())
+            ),
+            "Err(Superseded)"
+        );
+    }
+
+    #[test]
+    fn peer_quiet_frames_while_connected() {
+        let mut active = TestActive::new(Generation::new());
+        active.state.phase = Phase::Connected {
+            frame: 0,
+            call: CallInfo::inbound(Bearer::Pdc),
+            quiet_frames: 300,
+            call_frames: 0,
+        };
+        let connection_generation = active.state.connection_generation;
+
+        assert_eq!(
+            assert_ok!(active.peer_quiet_frames(connection_generation)),
+            300
+        );
+        assert_ok!(active.connection_status(connection_generation));
+    }
+
+    #[test]
+    fn call_frames_while_connected() {
+        let mut active = TestActive::new(Generation::new());
+        active.state.phase = Phase::Connected {
+            frame: 0,
+            call: CallInfo::inbound(Bearer::Pdc),
+            quiet_frames: 0,
+            call_frames: 9000,
+        };
+        let connection_generation = active.state.connection_generation;
+
+        assert_eq!(assert_ok!(active.call_frames(connection_generation)), 9000);
+    }
+
+    #[test]
+    fn call_frames_reset_on_reconnect() {
+        let mut active =
+            Active::<Socket<[u8; 0]>, NoSocket, NoDns, NoConfig>::linked(Generation::new());
+        let mut socket = Socket::new([]);
+        let mut digits = ArrayVec::new();
+        digits.write(&['1', '2', '3'].map(|c| assert_ok!(Digit::try_from(c))));
+
+        let connection_generation = assert_ok!(active.connect(digits.clone()));
+        let adapter = script::acknowledge(TransferLength::_8Bit, Command::DialTelephone, 4).chain(
+            script::response(TransferLength::_8Bit, Command::DialTelephone, &[]),
+        );
+        assert_ok!(script::run(
+            &mut active,
+            &mut socket,
+            &mut NoSocket,
+            &mut NoDns,
+            &mut NoConfig,
+            adapter,
+        ));
+
+        for frame in 0..3 {
+            assert_eq!(assert_ok!(active.call_frames(connection_generation)), frame);
+            assert_eq!(
+                assert_ok!(active.peer_quiet_frames(connection_generation)),
+                frame as u16
+            );
+            assert_ok!(active.vblank(
+                Timer::_0,
+                Generation::new(),
+                &mut socket,
+                &mut NoSocket,
+                &NoDns,
+                &NoConfig,
+            ));
+        }
+
+        let connection_generation = assert_ok!(active.connect(digits));
+        let adapter = script::acknowledge(TransferLength::_8Bit, Command::HangUpTelephone, 0)
+            .chain(script::response(
+                TransferLength::_8Bit,
+                Command::HangUpTelephone,
+                &[],
+            ))
+            .chain(script::acknowledge(
+                TransferLength::_8Bit,
+                Command::DialTelephone,
+                4,
+            ))
+            .chain(script::response(
+                TransferLength::_8Bit,
+                Command::DialTelephone,
+                &[],
+            ));
+        assert_ok!(script::run(
+            &mut active,
+            &mut socket,
+            &mut NoSocket,
+            &mut NoDns,
+            &mut NoConfig,
+            adapter,
+        ));
+
+        assert_eq!(assert_ok!(active.call_frames(connection_generation)), 0);
+        assert_eq!(
+            assert_ok!(active.peer_quiet_frames(connection_generation)),
+            0
+        );
+    }
+
+    #[test]
+    fn session_and_call_frames() {
+        let mut active =
+            Active::<Socket<[u8; 0]>, NoSocket, NoDns, NoConfig>::new(Generation::new());
+        let mut socket = Socket::new([]);
+        let mut digits = ArrayVec::new();
+        digits.write(&['1', '2', '3'].map(|c| assert_ok!(Digit::try_from(c))));
+        // The adapter wakes, begins the session, and declines to switch to SIO32.
+        let adapter = iter::once(0xd2)
+            .chain(script::acknowledge(
+                TransferLength::_8Bit,
+                Command::BeginSession,
+                8,
+            ))
+            .chain(script::response(
+                TransferLength::_8Bit,
+                Command::BeginSession,
+                b"NINTENDO",
+            ))
+            .chain(script::acknowledge(
+                TransferLength::_8Bit,
+                Command::Sio32Mode,
+                1,
+            ))
+            .chain(script::response(TransferLength::_8Bit, Command::Reset, &[]))
+            .chain(iter::once(0xd2));
+        assert_ok!(script::run(
+            &mut active,
+            &mut socket,
+            &mut NoSocket,
+            &mut NoDns,
+            &mut NoConfig,
+            adapter,
+        ));
+        assert!(matches!(active.state.phase, Phase::Linked { .. }));
+        assert_eq!(assert_ok!(active.session_frames()), 0);
+
+        // Idle pulses keep the link alive in the meantime.
+        assert_ok!(script::run_frames(
+            &mut active,
+            &mut socket,
+            &mut NoSocket,
+            &mut NoDns,
+            &mut NoConfig,
+            120,
+            iter::repeat(0xd2),
+        ));
+        assert_eq!(assert_ok!(active.session_frames()), 120);
+
+        let connection_generation = assert_ok!(active.connect(digits.clone()));
+        let adapter = script::acknowledge(TransferLength::_8Bit, Command::DialTelephone, 4).chain(
+            script::response(TransferLength::_8Bit, Command::DialTelephone, &[]),
+        );
+        assert_ok!(script::run(
+            &mut active,
+            &mut socket,
+            &mut NoSocket,
+            &mut NoDns,
+            &mut NoConfig,
+            adapter,
+        ));
+        let session_frames = assert_ok!(active.session_frames());
+        assert!(session_frames > 120);
+        assert_eq!(assert_ok!(active.call_frames(connection_generation)), 0);
+
+        assert_ok!(script::run_frames(
+            &mut active,
+            &mut socket,
+            &mut NoSocket,
+            &mut NoDns,
+            &mut NoConfig,
+            60,
+            iter::empty(),
+        ));
+        assert_eq!(assert_ok!(active.session_frames()), session_frames + 60);
+        assert_eq!(assert_ok!(active.call_frames(connection_generation)), 60);
+
+        assert_ok!(active.disconnect(connection_generation));
+        let adapter =
+            script::acknowledge(TransferLength::_8Bit, Command::HangUpTelephone, 0).chain(
+                script::response(TransferLength::_8Bit, Command::HangUpTelephone, &[]),
+            );
+        assert_ok!(script::run(
+            &mut active,
+            &mut socket,
+            &mut NoSocket,
+            &mut NoDns,
+            &mut NoConfig,
+            adapter,
+        ));
+        // Hanging up ends the call, but not the session.
+        assert_eq!(
+            format!("{:?}", active.call_frames(connection_generation)),
+            "Err(Closed)"
+        );
+        let hung_up_frames = assert_ok!(active.session_frames());
+        assert!(hung_up_frames >= session_frames + 60);
+
+        // The next call is counted from its own start.
+        let connection_generation = assert_ok!(active.connect(digits));
+        let adapter = script::acknowledge(TransferLength::_8Bit, Command::DialTelephone, 4).chain(
+            script::response(TransferLength::_8Bit, Command::DialTelephone, &[]),
+        );
+        assert_ok!(script::run(
+            &mut active,
+            &mut socket,
+            &mut NoSocket,
+            &mut NoDns,
+            &mut NoConfig,
+            adapter,
+        ));
+        assert_eq!(assert_ok!(active.call_frames(connection_generation)), 0);
+        assert!(assert_ok!(active.session_frames()) >= hung_up_frames);
+    }
+
+    #[test]
+    fn session_frames_after_end() {
+        let mut active = TestActive::new(Generation::new());
+        active.state.phase = Phase::Ending;
+
+        assert_eq!(format!("{:?}", active.session_frames()), "Err(Closed)");
+    }
+
+    #[test]
+    fn peer_quiet_frames_after_disconnect() {
+        let mut active = TestActive::new(Generation::new());
+        active.state.phase = Phase::Connected {
+            frame: 0,
+            call: CallInfo::inbound(Bearer::Pdc),
+            quiet_frames: 0,
+            call_frames: 0,
+        };
+        let connection_generation = active.state.connection_generation;
+        assert_ok!(active.disconnect(connection_generation));
+
+        assert_eq!(
+            format!("{:?}", active.peer_quiet_frames(connection_generation)),
+            "Err(Closed)"
+        );
+    }
+
+    /// Hangs up a call that `active` is connected to, without exchanging anything yet.
+    fn hang_up_connected(active: &mut TestActive) {
+        active.state.phase = Phase::Connected {
+            frame: 0,
+            call: CallInfo::inbound(Bearer::Pdc),
+            quiet_frames: 0,
+            call_frames: 0,
+        };
+        assert_ok!(active.disconnect(active.state.connection_generation));
+        assert_eq!(assert_ok!(active.disconnect_reason()), None);
+    }
+
+    #[test]
+    fn hang_up_reason_local() {
+        let mut active = TestActive::linked(Generation::new());
+        hang_up_connected(&mut active);
+
+        let adapter =
+            script::acknowledge(TransferLength::_8Bit, Command::HangUpTelephone, 0).chain(
+                script::response(TransferLength::_8Bit, Command::HangUpTelephone, &[]),
+            );
+        assert_ok!(script::run(
+            &mut active,
+            &mut NoSocket,
+            &mut NoSocket,
+            &mut NoDns,
+            &mut Config::new(mobile_system_gb::Config::new()),
+            adapter,
+        ));
+
+        assert_eq!(
+            assert_ok!(active.disconnect_reason()),
+            Some(DisconnectReason::Local)
+        );
+    }
+
+    #[test]
+    fn hang_up_after_remote_hang_up() {
+        let mut active = TestActive::linked(Generation::new());
+        hang_up_connected(&mut active);
+
+        // The adapter reports that there is no call left to hang up.
+        let adapter = script::acknowledge(TransferLength::_8Bit, Command::HangUpTelephone, 0)
+            .chain(script::response(
+                TransferLength::_8Bit,
+                Command::CommandError,
+                &[Command::HangUpTelephone as u8, 0x01],
+            ));
+        assert_ok!(script::run(
+            &mut active,
+            &mut NoSocket,
+            &mut NoSocket,
+            &mut NoDns,
+            &mut Config::new(mobile_system_gb::Config::new()),
+            adapter,
+        ));
+
+        assert_eq!(
+            assert_ok!(active.disconnect_reason()),
+            Some(DisconnectReason::MutualOrRemoteFirst)
+        );
+        assert!(matches!(
+            active.state.phase,
+            Phase::Linked {
+                connection_failure: None,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn line_down_before_hang_up_is_sent() {
+        let mut active = TestActive::linked(Generation::new());
+        // A status poll is already being exchanged when the game hangs up.
+        active.flow = Some(Flow::status(
+            TransferLength::_8Bit,
+            Timer::_0,
+            &mut active.state.packet_data,
+        ));
+        hang_up_connected(&mut active);
+
+        // The adapter reports that the line is down.
+        let adapter = script::acknowledge(TransferLength::_8Bit, Command::TelephoneStatus, 0)
+            .chain(script::response(
+                TransferLength::_8Bit,
+                Command::TelephoneStatus,
+                &[0x00, 0x00, 0x00],
+            ));
+        assert_ok!(script::run(
+            &mut active,
+            &mut NoSocket,
+            &mut NoSocket,
+            &mut NoDns,
+            &mut Config::new(mobile_system_gb::Config::new()),
+            adapter,
+        ));
+
+        assert_eq!(
+            assert_ok!(active.disconnect_reason()),
+            Some(DisconnectReason::MutualOrRemoteFirst)
+        );
+        // The hang-up is never sent.
+        assert!(active.is_unscheduled());
+    }
+
+    /// Runs a status poll against an adapter that answers it with `adapter`, returning the error
+    /// it fails with.
+    fn failed_status<Bytes>(
+        adapter: Bytes,
+    ) -> super::Error<NoSocket, NoSocket, NoDns, Config<mobile_system_gb::Config>>
+    where
+        Bytes: IntoIterator<Item = u8>,
+    {
+        let mut active = TestActive::linked(Generation::new());
+        active.flow = Some(Flow::status(
+            TransferLength::_8Bit,
+            Timer::_0,
+            &mut active.state.packet_data,
+        ));
+        assert_err!(script::run(
+            &mut active,
+            &mut NoSocket,
+            &mut NoSocket,
+            &mut NoDns,
+            &mut Config::new(mobile_system_gb::Config::new()),
+            adapter,
+        ))
+    }
+
+    #[test]
+    fn adapter_error_command_error() {
+        // The error code is one this driver doesn't know.
+        let error = failed_status(
+            script::acknowledge(TransferLength::_8Bit, Command::TelephoneStatus, 0).chain(
+                script::response(
+                    TransferLength::_8Bit,
+                    Command::CommandError,
+                    &[Command::TelephoneStatus as u8, 0x00],
+                ),
+            ),
+        );
+
+        assert!(error.is_adapter_error());
+    }
+
+    #[test]
+    fn adapter_error_refused_packet() {
+        let error =
+            failed_status((0..MAX_RETRIES).flat_map(|_| {
+                script::acknowledge(TransferLength::_8Bit, Command::MalformedError, 0)
+            }));
+
+        assert!(error.is_adapter_error());
+    }
+
+    #[test]
+    fn adapter_error_invalid_response() {
+        let error = failed_status(
+            script::acknowledge(TransferLength::_8Bit, Command::TelephoneStatus, 0).chain(
+                script::response(TransferLength::_8Bit, Command::TelephoneStatus, &[0x00]),
+            ),
+        );
+
+        assert!(!error.is_adapter_error());
+    }
+
+    #[test]
+    fn connection_writes_batched_until_flush() {
+        let mut active =
+            Active::<Socket<[u8; 0]>, NoSocket, NoDns, Config<mobile_system_gb::Config>>::new(
+                Generation::new(),
+            );
+        let mut socket = Socket::new([]);
+        active.state.phase = Phase::Connected {
+            frame: 0,
+            call: CallInfo::inbound(Bearer::Pdc),
+            quiet_frames: 0,
+            call_frames: 0,
+        };
+        let connection_generation = active.state.connection_generation;
+
+        for message in [&b"ab"[..], b"c", b"de", b"f", b"gh"] {
+            assert_eq!(
+                assert_ok!(active.connection_write(connection_generation, message, &mut socket)),
+                message.len()
+            );
+        }
+        assert!(active.queue.next().is_none());
+
+        assert_ok!(active.connection_flush(connection_generation, &mut socket));
+
+        assert_eq!(socket.write_buffer.as_slice(), b"abcdefgh");
+        assert!(matches!(active.queue.next(), Some(Item::Socket1(_))));
+        assert!(active.queue.next().is_none());
+    }
+
+    #[test]
+    fn queue_timeout_before_scheduling() {
+        let mut active = TestActive::linked(Generation::new());
+        active.state.frame = frames::THREE_SECONDS + 1;
+        active.queue.set_idle();
+
+        assert_eq!(
+            format!(
+                "{:?}",
+                active.vblank(
+                    Timer::_0,
+                    Generation::new(),
+                    &mut NoSocket,
+                    &mut NoSocket,
+                    &NoDns,
+                    &Config::new(mobile_system_gb::Config::new()),
+                )
+            ),
+            "Err(Queue)"
+        );
+        assert!(active.flow.is_none());
+    }
+
+    #[test]
+    fn connect_while_linking() {
+        let mut active = TestActive::new(Generation::new());
+        let connection_generation = assert_ok!(active.connect(ArrayVec::new()));
+
+        assert!(active.queue.next().is_none());
+        assert_eq!(
+            format!("{:?}", active.connection_status(connection_generation)),
+            "Ok(false)"
+        );
+
+        active.state.phase = Phase::Linked {
+            frame: 0,
+            connection_failure: None,
+        };
+        active.start_queued_connection();
+
+        assert!(matches!(
+            active.state.phase,
+            Phase::Connecting(ConnectionRequest::Connect { .. })
+        ));
+        assert!(matches!(active.queue.next(), Some(Item::Connect)));
+        assert_eq!(
+            format!("{:?}", active.connection_status(connection_generation)),
+            "Ok(false)"
+        );
+    }
+
+    #[test]
+    fn relink_drops_queued_connection() {
+        let mut active = TestActive::new(Generation::new());
+        let connection_generation = assert_ok!(active.accept());
+
+        active.start_link();
+
+        assert_eq!(
+            format!("{:?}", active.connection_status(connection_generation)),
+            "Err(Superseded)"
+        );
+        active.state.phase = Phase::Linked {
+            frame: 0,
+            connection_failure: None,
+        };
+        active.start_queued_connection();
+        assert!(matches!(active.state.phase, Phase::Linked { .. }));
+    }
+
+    #[test]
+    fn cancel_queued_connection() {
+        let mut active = TestActive::new(Generation::new());
+        let connection_generation = assert_ok!(active.accept());
+
+        assert_ok!(active.disconnect(connection_generation));
+
+        active.state.phase = Phase::Linked {
+            frame: 0,
+            connection_failure: None,
+        };
+        active.start_queued_connection();
+        assert!(matches!(active.state.phase, Phase::Linked { .. }));
+        assert!(active.queue.next().is_none());
+    }
+
+    #[test]
+    fn login_fails_fast_when_uninitialized() {
+        let mut active = TestActive::new(Generation::new());
+        let mut config = Config::new(mobile_system_gb::Config::new());
+        link_uninitialized(&mut active, &mut config);
+
+        assert!(assert_ok!(active.needs_initialization(&config)));
+        let connection_generation = active.state.connection_generation;
+        assert_eq!(
+            format!("{:?}", login(&mut active, &config)),
+            "Err(NotInitialized)"
+        );
+
+        // No login was attempted.
+        assert!(active.queue.next().is_none());
+        assert_eq!(active.state.connection_generation, connection_generation);
+    }
+
+    #[test]
+    fn initialize_config_writes_defaults() {
+        let mut active = TestActive::new(Generation::new());
+        let mut config = Config::new(mobile_system_gb::Config::new());
+        link_uninitialized(&mut active, &mut config);
+        let defaults = mobile_system_gb::Config {
+            registration: mobile_system_gb::Registration::Complete,
+            primary_dns: Ipv4Addr::UNSPECIFIED,
+            secondary_dns: Ipv4Addr::UNSPECIFIED,
+            login_id: [0; 10],
+            email: [0; 24],
+            smtp_server: [0; 20],
+            pop_server: [0; 19],
+            configuration_slots: Default::default(),
+        };
+
+        assert!(assert_ok!(
+            active.initialize_config(&mut config, defaults.clone())
+        ));
+        // Requesting the write is not enough; the adapter has to accept it first.
+        assert!(assert_ok!(active.needs_initialization(&config)));
+        assert_eq!(
+            format!("{:?}", login(&mut active, &config)),
+            "Err(NotInitialized)"
+        );
+        let adapter =
+            script::acknowledge(TransferLength::_8Bit, Command::WriteConfigurationData, 0x72)
+                .chain(script::response(
+                    TransferLength::_8Bit,
+                    Command::WriteConfigurationData,
+                    &[0x00, 0x71],
+                ))
+                .chain(script::acknowledge(
+                    TransferLength::_8Bit,
+                    Command::WriteConfigurationData,
+                    0x50,
+                ))
+                .chain(script::response(
+                    TransferLength::_8Bit,
+                    Command::WriteConfigurationData,
+                    &[0x71, 0x4f],
+                ));
+        let sent = assert_ok!(script::run(
+            &mut active,
+            &mut NoSocket,
+            &mut NoSocket,
+            &mut NoDns,
+            &mut config,
+            adapter,
+        ));
+
+        // The block is written starting with its "MA" header.
+        assert_eq!(
+            sent[..9],
+            [
+                0x99,
+                0x66,
+                Command::WriteConfigurationData as u8,
+                0x00,
+                0x00,
+                0x72,
+                0x00,
+                b'M',
+                b'A'
+            ]
+        );
+        assert!(active.is_idle());
+        assert!(!assert_ok!(active.needs_initialization(&config)));
+        assert!(!assert_ok!(active.initialize_config(&mut config, defaults)));
+        assert_ok!(login(&mut active, &config));
+    }
+}
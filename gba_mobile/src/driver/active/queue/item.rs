@@ -2,8 +2,9 @@
 
 use super::super::{Flow, Phase, State, flow::request::packet};
 use crate::{
-    Adapter, ArrayVec, Config, Digit, Generation, Timer,
+    Adapter, Config, Generation, Timer,
     config::{self, NoConfig},
+    digit,
     dns::{self, NoDns},
     mmio::serial::TransferLength,
     socket,
@@ -42,7 +43,7 @@ where
     Config: config::Sealed,
 {
     fn connect(
-        digits: &ArrayVec<Digit, 32>,
+        digits: &digit::NumberBuf,
         transfer_length: TransferLength,
         adapter: Adapter,
         connection_generation: Generation,
@@ -130,7 +131,7 @@ where
     Config: config::Sealed,
 {
     fn connect(
-        _digits: &ArrayVec<Digit, 32>,
+        _digits: &digit::NumberBuf,
         _transfer_length: TransferLength,
         _adapter: Adapter,
         _connection_generation: Generation,
@@ -389,7 +390,7 @@ where
     Config: config::Sealed,
 {
     fn connect(
-        digits: &ArrayVec<Digit, 32>,
+        digits: &digit::NumberBuf,
         transfer_length: TransferLength,
         adapter: Adapter,
         connection_generation: Generation,
@@ -490,6 +491,7 @@ where
     Reset,
 
     Connect,
+    Logout,
     Disconnect,
 
     Socket1(Socket1::Socket1Item<Socket2, Dns, Config>),
@@ -515,6 +517,7 @@ where
             Self::Reset => formatter.write_str("Reset"),
 
             Self::Connect => formatter.write_str("Connect"),
+            Self::Logout => formatter.write_str("Logout"),
             Self::Disconnect => formatter.write_str("Disconnect"),
 
             Self::Socket1(item) => formatter.debug_tuple("Socket1").field(item).finish(),
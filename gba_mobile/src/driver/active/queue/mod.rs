@@ -9,6 +9,13 @@ use core::{
 };
 use item::{ConnectionSubItem, DnsSubItem, Item, SocketSubItem};
 
+/// The set of flows waiting to be started.
+///
+/// Periodic work, such as idle pulses, status checks, and socket transfers, is scheduled by setting
+/// a bit here rather than by starting a flow directly. Only one flow is ever in progress, and the
+/// next one is only taken from the queue on a vblank once the previous flow has finished, so work
+/// that becomes due on the same frame is spread out in priority order instead of being sent
+/// back-to-back.
 pub(super) struct Queue<Socket1, Socket2, Dns, Config> {
     bits: u16,
     sockets: PhantomData<(Socket1, Socket2)>,
@@ -54,6 +61,11 @@ where
     /// progress made on the communication over both as well.
     const SOCKET_2_PRIORITY: Self = Self::bits(0b0000_0100_0000_0000);
 
+    /// Ends the PPP session before hanging up.
+    ///
+    /// This is only ever set along with [`DISCONNECT`](Self::DISCONNECT).
+    const LOGOUT: Self = Self::bits(0b0000_1000_0000_0000);
+
     const DNS: Self = Self::bits(0b0001_0000_0000_0000);
 
     const WRITE_CONFIG: Self = Self::bits(0b0010_0000_0000_0000);
@@ -100,6 +112,23 @@ where
         }
     }
 
+    pub(super) fn set_logout(&mut self) {
+        self.set(Self::LOGOUT);
+    }
+
+    /// Drops a pending hang-up after the adapter has reported that the line is already down.
+    ///
+    /// This happens when both sides hang up at nearly the same time. There is nothing left to hang
+    /// up, so the request is not sent at all. Returns whether a hang-up was pending.
+    pub(super) fn skip_disconnect(&mut self) -> bool {
+        if self.has(Self::DISCONNECT) {
+            self.clear_disconnect();
+            true
+        } else {
+            false
+        }
+    }
+
     pub(super) fn set_socket_1_open(&mut self) {
         self.set(Self::SOCKET_1_OPEN);
     }
@@ -176,6 +205,11 @@ where
                     &mut state.packet_data,
                     link_generation,
                 )),
+                Item::Logout => Some(Flow::logout(
+                    state.transfer_length,
+                    timer,
+                    &mut state.packet_data,
+                )),
                 Item::Disconnect => Some(Flow::disconnect(
                     state.transfer_length,
                     timer,
@@ -251,6 +285,7 @@ where
             Queue::START
                 | Queue::END
                 | Queue::DISCONNECT
+                | Queue::LOGOUT
                 | Queue::SOCKET_1_CLOSE
                 | Queue::SOCKET_2_CLOSE
                 | Queue::STATUS
@@ -260,7 +295,11 @@ where
 
     fn clear_disconnect(&mut self) {
         self.clear(
-            Queue::DISCONNECT | Queue::SOCKET_1_CLOSE | Queue::SOCKET_2_CLOSE | Queue::STATUS,
+            Queue::DISCONNECT
+                | Queue::LOGOUT
+                | Queue::SOCKET_1_CLOSE
+                | Queue::SOCKET_2_CLOSE
+                | Queue::STATUS,
         );
     }
 
@@ -339,6 +378,15 @@ where
             // When both start and end are set, we combine them into a single reset flow.
             self.clear_session();
             Some(Item::Reset)
+        } else if self.has(Queue::LOGOUT) {
+            // Log out before hanging up, since the adapter expects the PPP session to be ended
+            // before the call it runs over.
+            self.clear(Queue::LOGOUT);
+            Some(Item::Logout)
+        } else if self.has(Queue::END | Queue::DISCONNECT) {
+            // Hang up before ending the session, so the call isn't left dangling on the line.
+            self.clear_disconnect();
+            Some(Item::Disconnect)
         } else if self.has(Queue::END) {
             self.clear_session();
             Some(Item::End)
@@ -374,3 +422,124 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Item, Queue};
+    use crate::{Config, Socket, config::mobile_system_gb, dns::NoDns, socket::NoSocket};
+    use gba_test::test;
+
+    type TestQueue = Queue<NoSocket, NoSocket, NoDns, Config<mobile_system_gb::Config>>;
+
+    #[test]
+    fn empty() {
+        let mut queue = TestQueue::new();
+
+        assert!(queue.next().is_none());
+    }
+
+    #[test]
+    fn end_after_write_config_and_disconnect() {
+        let mut queue = TestQueue::new();
+        queue.set_disconnect();
+        queue.set_end();
+        queue.set_write_config();
+
+        assert!(matches!(queue.next(), Some(Item::Config(_))));
+        assert!(matches!(queue.next(), Some(Item::Disconnect)));
+        assert!(matches!(queue.next(), Some(Item::End)));
+        assert!(queue.next().is_none());
+    }
+
+    #[test]
+    fn logout_before_disconnect() {
+        let mut queue = TestQueue::new();
+        queue.set_logout();
+        queue.set_disconnect();
+        queue.set_end();
+
+        assert!(matches!(queue.next(), Some(Item::Logout)));
+        assert!(matches!(queue.next(), Some(Item::Disconnect)));
+        assert!(matches!(queue.next(), Some(Item::End)));
+        assert!(queue.next().is_none());
+    }
+
+    #[test]
+    fn skip_disconnect_skips_logout() {
+        let mut queue = TestQueue::new();
+        queue.set_logout();
+        queue.set_disconnect();
+        queue.skip_disconnect();
+
+        assert!(queue.next().is_none());
+    }
+
+    #[test]
+    fn end_clears_status_and_idle() {
+        let mut queue = TestQueue::new();
+        queue.set_status();
+        queue.set_idle();
+        queue.set_end();
+
+        assert!(matches!(queue.next(), Some(Item::End)));
+        assert!(queue.next().is_none());
+    }
+
+    #[test]
+    fn reset_does_not_disconnect() {
+        let mut queue = TestQueue::new();
+        queue.set_disconnect();
+        queue.set_end();
+        queue.set_start();
+
+        assert!(matches!(queue.next(), Some(Item::Reset)));
+        assert!(queue.next().is_none());
+    }
+
+    #[test]
+    fn periodic_items_released_one_at_a_time() {
+        let mut queue =
+            Queue::<Socket<[u8; 0]>, NoSocket, NoDns, Config<mobile_system_gb::Config>>::new();
+        queue.set_idle();
+        queue.set_status();
+        queue.set_socket_1_transfer();
+
+        assert!(matches!(queue.next(), Some(Item::Socket1(_))));
+        assert!(matches!(queue.next(), Some(Item::Status)));
+        assert!(matches!(queue.next(), Some(Item::Idle)));
+        assert!(queue.next().is_none());
+    }
+
+    #[test]
+    fn periodic_items_preempted_by_user_items() {
+        let mut queue = TestQueue::new();
+        queue.set_status();
+        queue.set_idle();
+        queue.set_write_config();
+
+        assert!(matches!(queue.next(), Some(Item::Config(_))));
+        assert!(matches!(queue.next(), Some(Item::Status)));
+        assert!(matches!(queue.next(), Some(Item::Idle)));
+    }
+
+    #[test]
+    fn skip_disconnect_keeps_connect() {
+        let mut queue = TestQueue::new();
+        queue.set_disconnect();
+        queue.set_connect();
+        assert!(queue.skip_disconnect());
+
+        assert!(matches!(queue.next(), Some(Item::Connect)));
+        assert!(queue.next().is_none());
+    }
+
+    #[test]
+    fn skip_disconnect_when_not_disconnecting() {
+        let mut queue = TestQueue::new();
+        queue.set_status();
+        assert!(!queue.skip_disconnect());
+
+        assert!(matches!(queue.next(), Some(Item::Status)));
+        assert!(queue.next().is_none());
+    }
+}
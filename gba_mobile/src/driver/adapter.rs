@@ -1,3 +1,4 @@
+use crate::connection::Bearer;
 use core::{
     fmt,
     fmt::{Display, Formatter},
@@ -30,16 +31,21 @@ pub enum Adapter {
 }
 
 impl Adapter {
+    /// The telephone network calls made or accepted with this adapter are carried over.
+    pub(in crate::driver) fn bearer(self) -> Bearer {
+        match self {
+            Self::Blue => Bearer::Pdc,
+            Self::Yellow => Bearer::CdmaOne,
+            Self::Green => Bearer::Phs,
+            Self::Red => Bearer::Phs,
+        }
+    }
+
     /// The byte used when dialing a number.
     ///
-    /// The required byte is different depending on the adapter being used.
+    /// The required byte identifies the network of the adapter being used.
     pub(in crate::driver) fn dial_byte(self) -> u8 {
-        match self {
-            Self::Blue => 0,
-            Self::Yellow => 2,
-            Self::Green => 1,
-            Self::Red => 1,
-        }
+        self.bearer() as u8
     }
 
     pub(in crate::driver) fn try_from(byte: u8) -> Result<Self, Unknown> {
@@ -66,7 +72,7 @@ impl Display for Adapter {
 
 #[cfg(test)]
 mod tests {
-    use super::{Adapter, Unknown};
+    use super::{Adapter, Bearer, Unknown};
     use alloc::format;
     use claims::{assert_err_eq, assert_ok_eq};
     use gba_test::test;
@@ -91,6 +97,26 @@ mod tests {
         assert_eq!(Adapter::Red.dial_byte(), 1);
     }
 
+    #[test]
+    fn bearer_blue() {
+        assert_eq!(Adapter::Blue.bearer(), Bearer::Pdc);
+    }
+
+    #[test]
+    fn bearer_yellow() {
+        assert_eq!(Adapter::Yellow.bearer(), Bearer::CdmaOne);
+    }
+
+    #[test]
+    fn bearer_green() {
+        assert_eq!(Adapter::Green.bearer(), Bearer::Phs);
+    }
+
+    #[test]
+    fn bearer_red() {
+        assert_eq!(Adapter::Red.bearer(), Bearer::Phs);
+    }
+
     #[test]
     fn try_from_blue() {
         assert_ok_eq!(Adapter::try_from(0x88), Adapter::Blue);
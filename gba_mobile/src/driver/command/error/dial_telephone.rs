@@ -19,7 +19,7 @@ impl Display for Error {
         match self {
             Self::LineBusy => formatter.write_str("the phone line is busy"),
             Self::AlreadyConnected => formatter.write_str("a call is already connected"),
-            Self::InvalidContents => formatter.write_str("a call is already connected"),
+            Self::InvalidContents => formatter.write_str("incorrect payload contents"),
             Self::CommunicationFailed => formatter.write_str("could not connect"),
             Self::CallNotEstablished => formatter.write_str("call not established"),
         }
@@ -42,3 +42,17 @@ impl TryFrom<u8> for Error {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_try_from, Error, UnknownError};
+    use gba_test::test;
+
+    test_try_from!(
+        LineBusy,
+        AlreadyConnected,
+        InvalidContents,
+        CommunicationFailed,
+        CallNotEstablished
+    );
+}
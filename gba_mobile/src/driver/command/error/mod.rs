@@ -24,6 +24,34 @@ use core::{
 };
 use unknown_error::UnknownError;
 
+/// Generates tests converting bytes into a command's `Error`, given the variants the command is
+/// documented to report.
+///
+/// Every documented error must round-trip, and every other byte must be reported as unknown.
+#[cfg(test)]
+macro_rules! test_try_from {
+    ($($documented:ident),+ $(,)?) => {
+        #[test]
+        fn try_from_documented() {
+            $(
+                claims::assert_ok_eq!(Error::try_from(Error::$documented as u8), Error::$documented);
+            )+
+        }
+
+        #[test]
+        fn try_from_all() {
+            for byte in 0..=u8::MAX {
+                match Error::try_from(byte) {
+                    Ok(error) => assert_eq!(error as u8, byte),
+                    Err(unknown) => assert_eq!(unknown, UnknownError(byte)),
+                }
+            }
+        }
+    };
+}
+#[cfg(test)]
+use test_try_from;
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub(in crate::driver) enum Unknown {
     Empty(UnknownError),
@@ -33,3 +33,11 @@ impl TryFrom<u8> for Error {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_try_from, Error, UnknownError};
+    use gba_test::test;
+
+    test_try_from!(ReadFailure, InvalidParameters);
+}
@@ -31,6 +31,30 @@ where
             kind: Kind::Superseded,
         }
     }
+
+    pub(crate) fn is_timeout(&self) -> bool {
+        matches!(&self.kind, Kind::Link(error) if error.is_timeout())
+    }
+
+    pub(crate) fn is_adapter_error(&self) -> bool {
+        matches!(&self.kind, Kind::Link(error) if error.is_adapter_error())
+    }
+
+    pub(crate) fn is_closed(&self) -> bool {
+        match &self.kind {
+            Kind::Closed => true,
+            Kind::Link(error) => error.is_closed(),
+            Kind::Superseded | Kind::Failure(_) => false,
+        }
+    }
+
+    pub(crate) fn is_superseded(&self) -> bool {
+        match &self.kind {
+            Kind::Superseded => true,
+            Kind::Link(error) => error.is_superseded(),
+            Kind::Closed | Kind::Failure(_) => false,
+        }
+    }
 }
 
 impl<Socket1, Socket2, Dns, Config> Debug for Error<Socket1, Socket2, Dns, Config>
@@ -161,3 +185,58 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{super::active, ConnectionFailure, Error, link};
+    use crate::{config::NoConfig, dns::NoDns, socket::NoSocket};
+    use gba_test::test;
+
+    type ConnectionError = Error<NoSocket, NoSocket, NoDns, NoConfig>;
+
+    #[test]
+    fn classify_closed() {
+        let error = ConnectionError::closed();
+
+        assert!(error.is_closed());
+        assert!(!error.is_superseded());
+        assert!(!error.is_timeout());
+    }
+
+    #[test]
+    fn classify_superseded() {
+        let error = ConnectionError::superseded();
+
+        assert!(error.is_superseded());
+        assert!(!error.is_closed());
+    }
+
+    #[test]
+    fn classify_failure() {
+        let error = ConnectionError::from(ConnectionFailure::LostConnection);
+
+        assert!(!error.is_closed());
+        assert!(!error.is_superseded());
+        assert!(!error.is_timeout());
+        assert!(!error.is_adapter_error());
+    }
+
+    #[test]
+    fn classify_link_closed() {
+        let error = ConnectionError::from(link::Error::closed());
+
+        assert!(error.is_closed());
+        assert!(!error.is_superseded());
+    }
+
+    #[test]
+    fn classify_link_timeout() {
+        let error = ConnectionError::from(link::Error::from(super::super::Error::Timeout(
+            active::Timeout::Queue,
+        )));
+
+        assert!(error.is_timeout());
+        assert!(!error.is_adapter_error());
+        assert!(!error.is_closed());
+    }
+}
@@ -39,6 +39,26 @@ where
             kind: Kind::Canceled,
         }
     }
+
+    pub(crate) fn is_timeout(&self) -> bool {
+        matches!(&self.kind, Kind::Connection(error) if error.is_timeout())
+    }
+
+    pub(crate) fn is_adapter_error(&self) -> bool {
+        matches!(&self.kind, Kind::Connection(error) if error.is_adapter_error())
+    }
+
+    pub(crate) fn is_closed(&self) -> bool {
+        matches!(&self.kind, Kind::Connection(error) if error.is_closed())
+    }
+
+    pub(crate) fn is_superseded(&self) -> bool {
+        match &self.kind {
+            Kind::Superseded => true,
+            Kind::Connection(error) => error.is_superseded(),
+            Kind::NotFound | Kind::Canceled => false,
+        }
+    }
 }
 
 impl<Socket1, Socket2, Dns, Config> Debug for Error<Socket1, Socket2, Dns, Config>
@@ -184,3 +204,38 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Error, connection};
+    use crate::{config::NoConfig, dns::NoDns, socket::NoSocket};
+    use gba_test::test;
+
+    type DnsError = Error<NoSocket, NoSocket, NoDns, NoConfig>;
+
+    #[test]
+    fn classify_not_found() {
+        let error = DnsError::not_found();
+
+        assert!(!error.is_closed());
+        assert!(!error.is_superseded());
+        assert!(!error.is_timeout());
+        assert!(!error.is_adapter_error());
+    }
+
+    #[test]
+    fn classify_superseded() {
+        let error = DnsError::superseded();
+
+        assert!(error.is_superseded());
+        assert!(!error.is_closed());
+    }
+
+    #[test]
+    fn classify_connection_closed() {
+        let error = DnsError::from(connection::Error::closed());
+
+        assert!(error.is_closed());
+        assert!(!error.is_superseded());
+    }
+}
@@ -29,6 +29,40 @@ where
             kind: Kind::Superseded,
         }
     }
+
+    pub(in crate::driver) fn busy() -> Self {
+        Self { kind: Kind::Busy }
+    }
+
+    pub(in crate::driver) fn not_initialized() -> Self {
+        Self {
+            kind: Kind::NotInitialized,
+        }
+    }
+
+    pub(crate) fn is_timeout(&self) -> bool {
+        matches!(&self.kind, Kind::Driver(super::Error::Timeout(_)))
+    }
+
+    pub(crate) fn is_adapter_error(&self) -> bool {
+        matches!(&self.kind, Kind::Driver(error) if error.is_adapter_error())
+    }
+
+    pub(crate) fn is_closed(&self) -> bool {
+        matches!(self.kind, Kind::Closed)
+    }
+
+    pub(crate) fn is_superseded(&self) -> bool {
+        matches!(self.kind, Kind::Superseded)
+    }
+
+    pub(crate) fn is_timer_in_use(&self) -> bool {
+        matches!(&self.kind, Kind::Driver(super::Error::TimerInUse))
+    }
+
+    pub(crate) fn is_busy(&self) -> bool {
+        matches!(self.kind, Kind::Busy)
+    }
 }
 
 impl<Socket1, Socket2, Dns, Config> Debug for Error<Socket1, Socket2, Dns, Config>
@@ -92,6 +126,8 @@ where
     Driver(super::Error<Socket1, Socket2, Dns, Config>),
     Closed,
     Superseded,
+    Busy,
+    NotInitialized,
 }
 
 impl<Socket1, Socket2, Dns, Config> Debug for Kind<Socket1, Socket2, Dns, Config>
@@ -106,6 +142,8 @@ where
             Self::Driver(error) => formatter.debug_tuple("Driver").field(error).finish(),
             Self::Closed => formatter.write_str("Closed"),
             Self::Superseded => formatter.write_str("Superseded"),
+            Self::Busy => formatter.write_str("Busy"),
+            Self::NotInitialized => formatter.write_str("NotInitialized"),
         }
     }
 }
@@ -122,6 +160,10 @@ where
             Self::Driver(_) => formatter.write_str("the driver is in an error state"),
             Self::Closed => formatter.write_str("the link connection was closed"),
             Self::Superseded => formatter.write_str("the link connection was superseded"),
+            Self::Busy => formatter.write_str("the driver is in the middle of exchanging a packet"),
+            Self::NotInitialized => {
+                formatter.write_str("the adapter's configuration has not been initialized")
+            }
         }
     }
 }
@@ -138,6 +180,71 @@ where
             Self::Driver(error) => Some(error),
             Self::Closed => None,
             Self::Superseded => None,
+            Self::Busy => None,
+            Self::NotInitialized => None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{super::active, Error};
+    use crate::{config::NoConfig, dns::NoDns, socket::NoSocket};
+    use gba_test::test;
+
+    type LinkError = Error<NoSocket, NoSocket, NoDns, NoConfig>;
+
+    #[test]
+    fn classify_timeout() {
+        let error = LinkError::from(super::super::Error::Timeout(active::Timeout::Queue));
+
+        assert!(error.is_timeout());
+        assert!(!error.is_closed());
+        assert!(!error.is_superseded());
+    }
+
+    #[test]
+    fn classify_aborted() {
+        let error = LinkError::from(super::super::Error::Aborted);
+
+        assert!(!error.is_timeout());
+        assert!(!error.is_closed());
+        assert!(!error.is_superseded());
+        assert!(!error.is_timer_in_use());
+        assert!(!error.is_adapter_error());
+    }
+
+    #[test]
+    fn classify_timer_in_use() {
+        let error = LinkError::from(super::super::Error::TimerInUse);
+
+        assert!(error.is_timer_in_use());
+        assert!(!error.is_timeout());
+        assert!(!error.is_closed());
+    }
+
+    #[test]
+    fn classify_closed() {
+        let error = LinkError::closed();
+
+        assert!(error.is_closed());
+        assert!(!error.is_timeout());
+    }
+
+    #[test]
+    fn classify_superseded() {
+        let error = LinkError::superseded();
+
+        assert!(error.is_superseded());
+        assert!(!error.is_closed());
+    }
+
+    #[test]
+    fn classify_busy() {
+        let error = LinkError::busy();
+
+        assert!(error.is_busy());
+        assert!(!error.is_timer_in_use());
+        assert!(!error.is_closed());
+    }
+}
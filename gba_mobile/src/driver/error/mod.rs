@@ -22,6 +22,25 @@ where
 {
     Timeout(active::Timeout),
     Error(active::Error<Socket1, Socket2, Dns, Config>),
+    /// Communication was stopped by [`Driver::emergency_stop()`].
+    ///
+    /// [`Driver::emergency_stop()`]: crate::Driver::emergency_stop
+    Aborted,
+    /// The driver's timer was already running when the link was started.
+    TimerInUse,
+}
+
+impl<Socket1, Socket2, Dns, Config> Error<Socket1, Socket2, Dns, Config>
+where
+    Socket1: crate::socket::Slot,
+    Socket2: crate::socket::Slot,
+    Dns: crate::dns::Mode,
+    Config: crate::config::Mode,
+{
+    /// Whether the adapter itself reported the error that ended communication.
+    pub(in crate::driver) fn is_adapter_error(&self) -> bool {
+        matches!(self, Self::Error(error) if error.is_adapter_error())
+    }
 }
 
 impl<Socket1, Socket2, Dns, Config> Clone for Error<Socket1, Socket2, Dns, Config>
@@ -35,6 +54,8 @@ where
         match self {
             Self::Timeout(timeout) => Self::Timeout(timeout.clone()),
             Self::Error(error) => Self::Error(error.clone()),
+            Self::Aborted => Self::Aborted,
+            Self::TimerInUse => Self::TimerInUse,
         }
     }
 }
@@ -50,6 +71,8 @@ where
         match self {
             Self::Timeout(timeout) => formatter.debug_tuple("Timeout").field(timeout).finish(),
             Self::Error(error) => formatter.debug_tuple("Error").field(error).finish(),
+            Self::Aborted => formatter.write_str("Aborted"),
+            Self::TimerInUse => formatter.write_str("TimerInUse"),
         }
     }
 }
@@ -65,6 +88,8 @@ where
         match self {
             Self::Timeout(_) => formatter.write_str("communication timed out"),
             Self::Error(_) => formatter.write_str("communication failed"),
+            Self::Aborted => formatter.write_str("communication was aborted"),
+            Self::TimerInUse => formatter.write_str("the driver's timer is already in use"),
         }
     }
 }
@@ -80,6 +105,7 @@ where
         match self {
             Self::Timeout(timeout) => Some(timeout),
             Self::Error(error) => Some(error),
+            Self::Aborted | Self::TimerInUse => None,
         }
     }
 }
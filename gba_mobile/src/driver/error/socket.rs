@@ -43,6 +43,30 @@ where
             kind: Kind::ClosedRemotely,
         }
     }
+
+    pub(crate) fn is_timeout(&self) -> bool {
+        matches!(&self.kind, Kind::Connection(error) if error.is_timeout())
+    }
+
+    pub(crate) fn is_adapter_error(&self) -> bool {
+        matches!(&self.kind, Kind::Connection(error) if error.is_adapter_error())
+    }
+
+    pub(crate) fn is_closed(&self) -> bool {
+        match &self.kind {
+            Kind::Closed | Kind::ClosedRemotely => true,
+            Kind::Connection(error) => error.is_closed(),
+            Kind::Superseded | Kind::FailedToConnect => false,
+        }
+    }
+
+    pub(crate) fn is_superseded(&self) -> bool {
+        match &self.kind {
+            Kind::Superseded => true,
+            Kind::Connection(error) => error.is_superseded(),
+            Kind::Closed | Kind::ClosedRemotely | Kind::FailedToConnect => false,
+        }
+    }
 }
 
 impl<Socket1, Socket2, Dns, Config> Debug for Error<Socket1, Socket2, Dns, Config>
@@ -194,3 +218,38 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Error, connection};
+    use crate::{config::NoConfig, dns::NoDns, socket::NoSocket};
+    use gba_test::test;
+
+    type SocketError = Error<NoSocket, NoSocket, NoDns, NoConfig>;
+
+    #[test]
+    fn classify_closed_remotely() {
+        let error = SocketError::closed_remotely();
+
+        assert!(error.is_closed());
+        assert!(!error.is_superseded());
+    }
+
+    #[test]
+    fn classify_failed_to_connect() {
+        let error = SocketError::failed_to_connect();
+
+        assert!(!error.is_closed());
+        assert!(!error.is_superseded());
+        assert!(!error.is_timeout());
+        assert!(!error.is_adapter_error());
+    }
+
+    #[test]
+    fn classify_connection_superseded() {
+        let error = SocketError::from(connection::Error::superseded());
+
+        assert!(error.is_superseded());
+        assert!(!error.is_closed());
+    }
+}
@@ -4,21 +4,28 @@ pub(crate) mod error;
 mod adapter;
 mod command;
 mod frames;
+#[cfg(feature = "debug-overlay")]
+mod overlay;
 mod timers;
 
 pub use adapter::Adapter;
 
 use crate::{
-    ArrayVec, Config, Digit, Dns, Generation, Socket, Timer, config, dns,
+    ArrayVec, Config, Dns, Generation, Socket, Timer, config,
+    connection::{CallInfo, DisconnectReason},
+    digit, dns, link,
     mmio::{
         interrupt,
         serial::{self, RCNT, SIOCNT, TransferLength},
+        timer,
     },
     socket,
+    stats::{self, PersistentStats},
 };
 use active::Active;
 use command::Command;
 use core::net::{Ipv4Addr, SocketAddrV4};
+use core::num::NonZeroU8;
 use error::Error;
 
 #[derive(Debug)]
@@ -37,6 +44,17 @@ where
     Error(Error<Socket1, Socket2, Dns, Config>),
 }
 
+/// A driver for communicating with a Mobile Adapter.
+///
+/// The driver is shared between the game's main loop and its interrupt handlers, which update it
+/// through [`Driver::vblank()`], [`Driver::serial()`], and [`Driver::timer()`]. Every access to it
+/// must be exclusive, including read-only calls such as checking a [`Pending`] operation's status:
+/// a read made from the main loop while an interrupt handler holds the driver mutably is undefined
+/// behavior, however small the read. Disabling interrupts around each use of the driver from the
+/// main loop is sufficient; [`MobileCell`] does this for every access.
+///
+/// [`MobileCell`]: crate::MobileCell
+/// [`Pending`]: crate::Pending
 #[derive(Debug)]
 pub struct Driver<Socket1, Socket2, Dns, Config>
 where
@@ -53,6 +71,20 @@ where
     dns: Dns,
     config: Config,
 
+    persistent_stats: Option<&'static mut [u8; stats::LEN]>,
+    polled: bool,
+    strict: bool,
+    extended_ack: bool,
+    /// The serial registers as they were before the driver configured them, restored once a link
+    /// ends.
+    serial_registers: Option<(serial::Mode, serial::Control)>,
+    /// The control register of the driver's timer as it was before linking, restored once a link
+    /// ends.
+    timer_control: Option<timer::Control>,
+    /// The interrupts that were enabled before the driver enabled its own, so that only the ones
+    /// the driver added are disabled again once a link ends.
+    interrupts: Option<interrupt::Enable>,
+
     state: State<Socket1, Socket2, Dns, Config>,
 }
 
@@ -79,33 +111,129 @@ where
             dns,
             config,
 
+            persistent_stats: None,
+            polled: false,
+            strict: false,
+            extended_ack: false,
+            serial_registers: None,
+            timer_control: None,
+            interrupts: None,
+
             state: State::Inactive,
         }
     }
 
+    /// Drive serial communication by polling rather than through interrupts.
+    ///
+    /// The driver will no longer enable the serial and timer interrupts when linking. Instead,
+    /// [`Driver::poll()`] must be called regularly from the game's main loop. [`Driver::vblank()`]
+    /// must still be called once per frame.
+    pub const fn polled(mut self) -> Self {
+        self.polled = true;
+        self
+    }
+
+    /// Verify every field of the adapter's packets, rather than assuming the ones it is only
+    /// expected to leave empty are correct.
+    ///
+    /// This includes the empty byte of every received packet's header and the bytes following the
+    /// command in an SIO32 acknowledgement. A mismatch is handled like any other error in the
+    /// packet, so the packet is retried. An acknowledgement echoing another command also reports
+    /// both commands.
+    ///
+    /// This is meant for protocol development and conformance testing. By default, these fields
+    /// are not checked, for the sake of tolerating hardware that doesn't follow the protocol
+    /// exactly.
+    pub const fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Accept the extended acknowledgement used by some adapter reimplementations.
+    ///
+    /// These adapters report flow-control and status bits in the command byte of their half of
+    /// the acknowledgement signal, which is otherwise always `0x00`. With this enabled, a nonzero
+    /// byte is kept for [`Link::extended_acknowledgement()`] instead of failing the packet.
+    ///
+    /// [`Link::extended_acknowledgement()`]: crate::Link::extended_acknowledgement
+    pub const fn extended_ack(mut self, extended_ack: bool) -> Self {
+        self.extended_ack = extended_ack;
+        self
+    }
+
+    /// Bind a region of persistent memory, such as SRAM, for keeping session statistics.
+    ///
+    /// The statistics are updated whenever a link session ends, and can be read back using
+    /// [`PersistentStats::parse()`]. If the region does not contain a valid record, it is reset.
+    /// The region is only ever accessed one byte at a time.
+    pub fn bind_persistent_stats(&mut self, region: &'static mut [u8; stats::LEN]) {
+        PersistentStats::load(region);
+        self.persistent_stats = Some(region);
+    }
+
+    fn record_session_end(
+        &mut self,
+        reason: stats::Reason,
+        link_stats: stats::LinkStats,
+        command: Command,
+    ) {
+        if let Some(region) = &mut self.persistent_stats {
+            let mut stats = PersistentStats::load(region);
+            stats.record(reason, &link_stats, command as u8);
+            stats.store(region);
+        }
+    }
+
     /// Configures serial communication for a brand new link attempt.
-    fn enable_communication() {
+    fn enable_communication(&mut self) {
+        self.timer_control = Some(self.timer.control());
         unsafe {
+            self.serial_registers = Some((RCNT.read_volatile(), SIOCNT.read_volatile()));
             // Set transfer mode to 8-bit Normal.
             RCNT.write_volatile(serial::Mode::NORMAL);
             SIOCNT.write_volatile(serial::Control::new().transfer_length(TransferLength::_8Bit));
         }
     }
 
+    /// Undo the driver's hardware configuration after a link has ended, however it ended.
+    fn restore_hardware(&mut self) {
+        self.timer.stop();
+        if let Some(control) = self.timer_control.take() {
+            self.timer.set_control(control);
+        }
+        unsafe {
+            if let Some((mode, control)) = self.serial_registers.take() {
+                RCNT.write_volatile(mode);
+                SIOCNT.write_volatile(control);
+            }
+            if let Some(interrupts) = self.interrupts.take() {
+                interrupt::ENABLE.write_volatile(
+                    interrupt::ENABLE.read_volatile().without(
+                        (Self::timer_interrupt(self.timer) | interrupt::Enable::SERIAL)
+                            .without(interrupts),
+                    ),
+                );
+            }
+        }
+    }
+
+    fn timer_interrupt(timer: Timer) -> interrupt::Enable {
+        match timer {
+            Timer::_0 => interrupt::Enable::TIMER0,
+            Timer::_1 => interrupt::Enable::TIMER1,
+            Timer::_2 => interrupt::Enable::TIMER2,
+            Timer::_3 => interrupt::Enable::TIMER3,
+        }
+    }
+
     /// Enable interrupts required for the driver to function.
     fn enable_interrupts(timer: Timer) {
         unsafe {
             // Enable interrupts for vblank, timer, and serial.
-            let timer_enable = match timer {
-                Timer::_0 => interrupt::Enable::TIMER0,
-                Timer::_1 => interrupt::Enable::TIMER1,
-                Timer::_2 => interrupt::Enable::TIMER2,
-                Timer::_3 => interrupt::Enable::TIMER3,
-            };
             interrupt::ENABLE.write_volatile(
                 interrupt::ENABLE.read_volatile()
                     | interrupt::Enable::VBLANK
-                    | timer_enable
+                    | Self::timer_interrupt(timer)
                     | interrupt::Enable::SERIAL,
             );
         }
@@ -113,11 +241,20 @@ where
 
     pub(crate) fn link(&mut self) -> Generation {
         self.link_generation = self.link_generation.increment();
-        Self::enable_interrupts(self.timer);
+        if !matches!(self.state, State::Active(_)) && self.timer.is_running() {
+            // The driver only runs its timer while a transfer is scheduled, so a running timer
+            // belongs to someone else. Taking it over would corrupt their configuration.
+            self.state = State::Error(Error::TimerInUse);
+            return self.link_generation;
+        }
+        self.claim_hardware();
         match &mut self.state {
             State::Inactive | State::Error(_) => {
-                Self::enable_communication();
-                self.state = State::Active(Active::new(self.link_generation));
+                self.state = State::Active(
+                    Active::new(self.link_generation)
+                        .strict(self.strict)
+                        .extended_ack(self.extended_ack),
+                );
             }
             State::Active(active) => {
                 active.start_link();
@@ -126,6 +263,43 @@ where
         self.link_generation
     }
 
+    /// Enables the driver's interrupts and configures serial communication, unless a link is
+    /// already active.
+    fn claim_hardware(&mut self) {
+        if !self.polled {
+            if self.interrupts.is_none() {
+                self.interrupts = Some(unsafe { interrupt::ENABLE.read_volatile() });
+            }
+            Self::enable_interrupts(self.timer);
+        }
+        if !matches!(self.state, State::Active(_)) {
+            self.enable_communication();
+        }
+    }
+
+    /// Starts a link that has already been established with the adapter, without exchanging
+    /// anything, returning its generation.
+    #[cfg(test)]
+    pub(crate) fn linked(&mut self) -> Generation {
+        self.link_generation = self.link_generation.increment();
+        self.claim_hardware();
+        self.state = State::Active(Active::linked(self.link_generation));
+        self.link_generation
+    }
+
+    /// Whether nothing is being exchanged with the adapter, and nothing is waiting to be.
+    #[cfg(test)]
+    pub(crate) fn is_unscheduled(&mut self) -> bool {
+        match &mut self.state {
+            State::Active(active) => active.is_unscheduled(),
+            State::Inactive | State::Error(_) => true,
+        }
+    }
+
+    pub(crate) fn is_current_link(&self, link_generation: Generation) -> bool {
+        link_generation == self.link_generation
+    }
+
     pub(crate) fn as_active<'a>(
         &'a self,
         link_generation: Generation,
@@ -176,6 +350,58 @@ where
         }
     }
 
+    /// Switch the driver to a different timer without ending the link.
+    ///
+    /// This allows a timer to be lent to other code, such as a music engine, for a while. The timer
+    /// is only used while a packet is being exchanged with the adapter, so it can only be switched
+    /// in between packets. If a packet is being exchanged, an error for which
+    /// [`is_busy()`](crate::link::error::Error::is_busy) returns `true` is returned, and the switch
+    /// should be tried again on a later frame. A timer that is already running belongs to someone
+    /// else and is refused in the same way as when linking.
+    ///
+    /// Once switched, the old timer's interrupt is disabled again unless it was enabled before
+    /// linking, and [`Driver::timer()`] must be called for the new timer's interrupt instead.
+    pub fn migrate_timer(
+        &mut self,
+        timer: Timer,
+    ) -> Result<(), link::error::Error<Socket1, Socket2, Dns, Config>> {
+        if let State::Active(active) = &self.state
+            && !active.is_idle()
+        {
+            return Err(error::link::Error::busy().into());
+        }
+        if timer == self.timer {
+            return Ok(());
+        }
+        if timer.is_running() {
+            return Err(error::link::Error::from(Error::TimerInUse).into());
+        }
+
+        if let Some(interrupts) = &self.interrupts {
+            // The driver's interrupts are only enabled while it holds a saved copy of the game's.
+            let old_interrupt = Self::timer_interrupt(self.timer);
+            unsafe {
+                let mut enabled = interrupt::ENABLE.read_volatile();
+                if !interrupts.contains(&old_interrupt) {
+                    enabled = enabled.without(old_interrupt);
+                }
+                interrupt::ENABLE.write_volatile(enabled | Self::timer_interrupt(timer));
+            }
+        }
+        if let Some(control) = self.timer_control.take() {
+            // The old timer is handed back as it was before linking.
+            self.timer.set_control(control);
+            self.timer_control = Some(timer.control());
+        }
+        self.timer = timer;
+        Ok(())
+    }
+
+    /// Handles a timer interrupt.
+    ///
+    /// This should be called from the interrupt handler whenever the interrupt for the timer
+    /// provided in [`Driver::new()`] is raised.
+    #[cfg_attr(feature = "iwram", inline(never), unsafe(link_section = ".iwram"))]
     pub fn timer(&mut self) {
         match &mut self.state {
             State::Inactive => {}
@@ -184,6 +410,10 @@ where
         }
     }
 
+    /// Handles a serial interrupt.
+    ///
+    /// This should be called from the interrupt handler whenever the serial interrupt is raised.
+    #[cfg_attr(feature = "iwram", inline(never), unsafe(link_section = ".iwram"))]
     pub fn serial(&mut self) {
         match &mut self.state {
             State::Inactive => {}
@@ -196,13 +426,136 @@ where
                     &mut self.dns,
                     &mut self.config,
                 ) {
+                    let (link_stats, command) = active.session_summary();
                     self.state = State::Error(Error::Error(error));
+                    self.restore_hardware();
+                    self.record_session_end(stats::Reason::Error, link_stats, command);
                 }
             }
             State::Error(_) => {}
         }
     }
 
+    /// Handles any serial or timer events that have occurred since the last poll.
+    ///
+    /// This is used in place of [`Driver::serial()`] and [`Driver::timer()`] when the driver was
+    /// created with [`Driver::polled()`], for games that cannot afford the time spent handling
+    /// these interrupts. It should be called from the main loop as often as possible; each byte
+    /// exchanged with the adapter waits for a poll both when it is sent and when it completes, so
+    /// infrequent polling slows communication accordingly. Polling less than once per frame is
+    /// likely to cause requests to time out, which is reported in the same way as any other
+    /// timeout.
+    ///
+    /// Nothing is done unless the driver was created with [`Driver::polled()`] and is linked, so
+    /// interrupt flags belonging to the game are never acknowledged.
+    pub fn poll(&mut self) {
+        if !self.polled || !self.is_active() {
+            return;
+        }
+        let timer_interrupt = Self::timer_interrupt(self.timer);
+        let requests = unsafe { interrupt::REQUEST.read_volatile() };
+        if requests.contains(&interrupt::Enable::SERIAL) {
+            // Flags are acknowledged by writing them back.
+            unsafe { interrupt::REQUEST.write_volatile(interrupt::Enable::SERIAL) };
+            self.serial();
+        }
+        if requests.contains(&timer_interrupt) {
+            unsafe { interrupt::REQUEST.write_volatile(timer_interrupt) };
+            self.timer();
+        }
+    }
+
+    /// Immediately stops all communication with the adapter.
+    ///
+    /// This is intended for emergencies, such as imminent power loss, where waiting for the current
+    /// packet to finish is not an option. Any transfer in progress is stopped, the hardware is
+    /// restored in the same way as when a link is closed, and the link is abandoned without
+    /// notifying the adapter. No further hardware is accessed once this returns, and all handles to
+    /// the link will report that it was aborted. If the driver is not linked, the hardware is left
+    /// untouched.
+    ///
+    /// Calls to [`Driver::serial()`] or [`Driver::timer()`] for interrupts that were already raised
+    /// are ignored. A new link can be started afterward as normal.
+    pub fn emergency_stop(&mut self) {
+        if let State::Active(_) = self.state {
+            self.stop_transfer();
+            // Session statistics are deliberately not recorded, since writing to persistent memory
+            // is exactly what a game is likely to be doing in this situation.
+            self.state = State::Error(Error::Aborted);
+        }
+    }
+
+    /// Stops using the hardware, handing it back to the game.
+    ///
+    /// Any link is abandoned without notifying the adapter, and all handles to it will report that
+    /// it was closed. The serial registers and the timer's control register are restored to how
+    /// they were before linking, which stops the timer, and the timer and serial interrupts are
+    /// disabled unless they were already enabled before linking. Unlike
+    /// [`Driver::emergency_stop()`], the session's statistics are recorded.
+    ///
+    /// The hardware is restored whenever a link ends, so this does nothing if the driver is not
+    /// linked. A new link can be started afterward as normal.
+    pub fn release(&mut self) {
+        if let State::Active(active) = &self.state {
+            let (link_stats, command) = active.session_summary();
+            self.stop_transfer();
+            self.state = State::Inactive;
+            self.record_session_end(stats::Reason::Closed, link_stats, command);
+        }
+    }
+
+    /// Stops any transfer in progress and restores the hardware.
+    fn stop_transfer(&mut self) {
+        unsafe { SIOCNT.write_volatile(SIOCNT.read_volatile().start(false).interrupts(false)) };
+        self.restore_hardware();
+    }
+
+    /// Returns whether the driver is communicating with an adapter.
+    ///
+    /// After a link is closed using [`Link::close()`], this remains `true` until the adapter has
+    /// acknowledged the end of the session. At that point the serial registers and the timer's
+    /// control register are restored to how they were before linking, which stops the timer, and
+    /// the timer and serial interrupts are disabled unless they were already enabled before
+    /// linking, so the link port and the timer can then be used for something else. The same is
+    /// done when a link ends in an error.
+    ///
+    /// [`Link::close()`]: crate::Link::close
+    pub fn is_active(&self) -> bool {
+        matches!(self.state, State::Active(_))
+    }
+
+    /// Handles a vblank interrupt.
+    ///
+    /// This should be called from the interrupt handler once per frame. The driver never calls
+    /// back into user code while processing, so requests such as [`Link::accept()`] are free to be
+    /// made from a vblank-driven game loop, including one running within the interrupt handler
+    /// itself, once this method has returned. Any request made is only acted upon during a later
+    /// call to this method, so a request never interrupts work the driver is already doing.
+    ///
+    /// Requests are not necessarily acted upon in the order they were made. The driver works on one
+    /// request at a time, and when several are waiting it starts them in a fixed priority order:
+    ///
+    /// 1. Socket transfers, alternating between the two sockets when both have data waiting.
+    /// 2. Configuration writes.
+    /// 3. DNS queries.
+    /// 4. Restarting the session, when it is to be both ended and started again.
+    /// 5. Logging out of the ISP.
+    /// 6. Hanging up a call when the session is also ending, so the call is not left on the line.
+    /// 7. Ending the session.
+    /// 8. Starting the session.
+    /// 9. Hanging up a call.
+    /// 10. Connecting, whether by accepting a call, calling, or logging in.
+    /// 11. Closing sockets.
+    /// 12. Opening sockets.
+    /// 13. Checking the connection's status.
+    /// 14. Keeping the link alive while nothing else is waiting.
+    ///
+    /// A game that depends on one request completing before another should wait for the first
+    /// request's [`Pending`] handle to report its result before making the second.
+    ///
+    /// [`Pending`]: crate::Pending
+    ///
+    /// [`Link::accept()`]: crate::Link::accept
     pub fn vblank(&mut self) {
         match &mut self.state {
             State::Inactive => {}
@@ -216,8 +569,18 @@ where
                     &self.config,
                 ) {
                     Ok(active::StateChange::StillActive) => {}
-                    Ok(active::StateChange::Inactive) => self.state = State::Inactive,
-                    Err(timeout) => self.state = State::Error(Error::Timeout(timeout)),
+                    Ok(active::StateChange::Inactive) => {
+                        let (link_stats, command) = active.session_summary();
+                        self.state = State::Inactive;
+                        self.restore_hardware();
+                        self.record_session_end(stats::Reason::Closed, link_stats, command);
+                    }
+                    Err(timeout) => {
+                        let (link_stats, command) = active.session_summary();
+                        self.state = State::Error(Error::Timeout(timeout));
+                        self.restore_hardware();
+                        self.record_session_end(stats::Reason::Timeout, link_stats, command);
+                    }
                 }
             }
             State::Error(_) => {}
@@ -261,12 +624,63 @@ where
         self.active.connection_status(connection_generation)
     }
 
+    pub(crate) fn connection_info(
+        self,
+        connection_generation: Generation,
+    ) -> Result<&'a CallInfo, error::connection::Error<Socket1, Socket2, Dns, Config>> {
+        self.active.connection_info(connection_generation)
+    }
+
+    pub(crate) fn peer_quiet_frames(
+        self,
+        connection_generation: Generation,
+    ) -> Result<u16, error::connection::Error<Socket1, Socket2, Dns, Config>> {
+        self.active.peer_quiet_frames(connection_generation)
+    }
+
+    pub(crate) fn call_frames(
+        self,
+        connection_generation: Generation,
+    ) -> Result<u32, error::connection::Error<Socket1, Socket2, Dns, Config>> {
+        self.active.call_frames(connection_generation)
+    }
+
     pub(crate) fn adapter(
         self,
     ) -> Result<Adapter, error::link::Error<Socket1, Socket2, Dns, Config>> {
         self.active.adapter()
     }
 
+    pub(crate) fn session_frames(
+        self,
+    ) -> Result<u32, error::link::Error<Socket1, Socket2, Dns, Config>> {
+        self.active.session_frames()
+    }
+
+    pub(crate) fn disconnect_reason(
+        self,
+    ) -> Result<Option<DisconnectReason>, error::link::Error<Socket1, Socket2, Dns, Config>> {
+        self.active.disconnect_reason()
+    }
+
+    pub(crate) fn link_stats(
+        self,
+    ) -> Result<stats::LinkStats, error::link::Error<Socket1, Socket2, Dns, Config>> {
+        self.active.link_stats()
+    }
+
+    pub(crate) fn extended_acknowledgement(
+        self,
+    ) -> Result<Option<NonZeroU8>, error::link::Error<Socket1, Socket2, Dns, Config>> {
+        self.active.extended_acknowledgement()
+    }
+
+    pub(crate) fn needs_initialization(
+        self,
+    ) -> Result<bool, error::link::Error<Socket1, Socket2, Dns, Config>> {
+        self.active.needs_initialization(self.config)
+    }
+
     pub(crate) fn ip(
         self,
         connection_generation: Generation,
@@ -386,16 +800,28 @@ where
         self.active.close_link()
     }
 
+    pub(crate) fn reset_link_stats(
+        self,
+    ) -> Result<(), error::link::Error<Socket1, Socket2, Dns, Config>> {
+        self.active.reset_link_stats()
+    }
+
     pub(crate) fn login(
         &mut self,
-        phone_number: ArrayVec<Digit, 32>,
+        phone_number: digit::NumberBuf,
         id: ArrayVec<u8, 32>,
         password: ArrayVec<u8, 32>,
         primary_dns: Ipv4Addr,
         secondary_dns: Ipv4Addr,
     ) -> Result<Generation, error::link::Error<Socket1, Socket2, Dns, Config>> {
-        self.active
-            .login(phone_number, id, password, primary_dns, secondary_dns)
+        self.active.login(
+            phone_number,
+            id,
+            password,
+            primary_dns,
+            secondary_dns,
+            self.config,
+        )
     }
 
     pub(crate) fn disconnect(
@@ -421,7 +847,7 @@ where
 
     pub(crate) fn connect(
         self,
-        phone_number: ArrayVec<Digit, 32>,
+        phone_number: digit::NumberBuf,
     ) -> Result<Generation, error::link::Error<Socket<Buffer>, Socket2, Dns, Config>> {
         self.active.connect(phone_number)
     }
@@ -652,4 +1078,445 @@ where
     ) -> Result<(), error::link::Error<Socket1, Socket2, Dns, Config<Format>>> {
         self.active.write_config(self.config, format)
     }
+
+    pub(crate) fn initialize_config(
+        &mut self,
+        defaults: Format,
+    ) -> Result<bool, error::link::Error<Socket1, Socket2, Dns, Config<Format>>> {
+        self.active.initialize_config(self.config, defaults)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Active, Command, Driver, Error, State,
+        active::{self, script},
+        frames,
+    };
+    use crate::{
+        Config, Generation, Pending, Timer,
+        config::{NoConfig, mobile_system_gb},
+        dns::NoDns,
+        link::{Initialization, initialization},
+        mmio::{
+            interrupt,
+            serial::{RCNT, SIOCNT, TransferLength},
+            timer::{Control, TM0CNT, TM1CNT, TM2CNT, TM3CNT},
+        },
+        socket::NoSocket,
+    };
+    use claims::{assert_err, assert_none, assert_ok, assert_some};
+    use core::net::Ipv4Addr;
+    use gba_test::test;
+
+    type TestDriver = Driver<NoSocket, NoSocket, NoDns, NoConfig>;
+
+    /// Writes sentinel values to RCNT and SIOCNT, as a game using the link port would.
+    fn write_serial_sentinels() {
+        unsafe {
+            (RCNT as *mut u16).write_volatile(0x8000);
+            (SIOCNT as *mut u16).write_volatile(0x1003);
+        }
+    }
+
+    /// Asserts that the values written by [`write_serial_sentinels()`] are back.
+    fn assert_serial_sentinels() {
+        unsafe {
+            assert_eq!((RCNT as *mut u16).read_volatile(), 0x8000);
+            assert_eq!((SIOCNT as *mut u16).read_volatile(), 0x1003);
+        }
+    }
+
+    /// The control register of `timer`, as a plain value.
+    fn timer_control(timer: Timer) -> *mut u16 {
+        match timer {
+            Timer::_0 => TM0CNT as *mut u16,
+            Timer::_1 => TM1CNT as *mut u16,
+            Timer::_2 => TM2CNT as *mut u16,
+            Timer::_3 => TM3CNT as *mut u16,
+        }
+    }
+
+    /// Asserts that linking refuses `timer` while the game is running it, leaving it untouched.
+    fn assert_link_refuses_running_timer(timer: Timer) {
+        let mut driver = TestDriver::new(timer, NoSocket, NoSocket, NoDns, NoConfig);
+        let control = timer_control(timer);
+        // Running, and interrupting every 1024 cycles.
+        unsafe { control.write_volatile(0x00c3) };
+
+        let link_generation = driver.link();
+
+        let value = unsafe { control.read_volatile() };
+        unsafe { control.write_volatile(0x0000) };
+        assert_eq!(value, 0x00c3);
+        assert!(assert_err!(driver.as_active(link_generation)).is_timer_in_use());
+    }
+
+    /// Asserts that linking leaves `timer` alone, and that it is stopped again once the driver is
+    /// idle after exchanging an idle pulse.
+    fn assert_timer_stopped_when_idle(timer: Timer) {
+        let mut driver = TestDriver::new(timer, NoSocket, NoSocket, NoDns, NoConfig);
+        let control = timer_control(timer);
+        // Stopped, but configured by the game to interrupt every 1024 cycles.
+        unsafe { control.write_volatile(0x0043) };
+
+        driver.linked();
+        assert_eq!(unsafe { control.read_volatile() }, 0x0043);
+        // The timer paces the idle pulse sent after a second without requests.
+        script::run_driver(&mut driver, [0xd2]);
+
+        assert!(matches!(&driver.state, State::Active(active) if active.is_idle()));
+        assert_eq!(unsafe { control.read_volatile() } & 0x0080, 0x0000);
+    }
+
+    /// Runs `f` after raising `requests` in the interrupt request register, returning whether the
+    /// register was left unchanged.
+    fn requests_unchanged(requests: interrupt::Enable, f: impl FnOnce()) -> bool {
+        unsafe {
+            interrupt::REQUEST.write_volatile(requests);
+            let before = interrupt::REQUEST.read_volatile();
+            f();
+            before == interrupt::REQUEST.read_volatile()
+        }
+    }
+
+    /// Runs `f` with the interrupt enable register set to `enabled`, returning its value afterward.
+    fn with_enabled(enabled: interrupt::Enable, f: impl FnOnce()) -> interrupt::Enable {
+        unsafe {
+            let old_enabled = interrupt::ENABLE.read_volatile();
+            interrupt::ENABLE.write_volatile(enabled);
+            f();
+            let enabled = interrupt::ENABLE.read_volatile();
+            interrupt::ENABLE.write_volatile(old_enabled);
+            enabled
+        }
+    }
+
+    #[test]
+    fn emergency_stop_inactive_leaves_hardware() {
+        let mut driver = TestDriver::new(Timer::_3, NoSocket, NoSocket, NoDns, NoConfig);
+        let siocnt = SIOCNT as *mut u16;
+        let (old_enable, old_control) =
+            unsafe { (interrupt::ENABLE.read_volatile(), siocnt.read_volatile()) };
+        unsafe {
+            interrupt::ENABLE.write_volatile(
+                interrupt::Enable::VBLANK | interrupt::Enable::TIMER3 | interrupt::Enable::SERIAL,
+            );
+            siocnt.write_volatile(0x1001);
+        }
+
+        driver.emergency_stop();
+
+        let (enable, control) =
+            unsafe { (interrupt::ENABLE.read_volatile(), siocnt.read_volatile()) };
+        unsafe {
+            interrupt::ENABLE.write_volatile(old_enable);
+            siocnt.write_volatile(old_control);
+        }
+        assert_eq!(
+            enable,
+            interrupt::Enable::VBLANK | interrupt::Enable::TIMER3 | interrupt::Enable::SERIAL
+        );
+        assert_eq!(control, 0x1001);
+        assert!(matches!(driver.state, State::Inactive));
+    }
+
+    #[test]
+    fn emergency_stop_keeps_game_interrupts() {
+        let mut driver = TestDriver::new(Timer::_3, NoSocket, NoSocket, NoDns, NoConfig);
+        driver.state = State::Active(Active::new(Generation::new()));
+        // The timer's interrupt was already enabled by the game before linking.
+        driver.interrupts = Some(interrupt::Enable::VBLANK | interrupt::Enable::TIMER3);
+        let enabled = with_enabled(
+            interrupt::Enable::VBLANK | interrupt::Enable::TIMER3 | interrupt::Enable::SERIAL,
+            || driver.emergency_stop(),
+        );
+        assert_eq!(
+            enabled,
+            interrupt::Enable::VBLANK | interrupt::Enable::TIMER3
+        );
+        assert!(matches!(driver.state, State::Error(Error::Aborted)));
+    }
+
+    #[test]
+    fn end_session_restores_serial_registers() {
+        let mut driver = TestDriver::new(Timer::_3, NoSocket, NoSocket, NoDns, NoConfig);
+        write_serial_sentinels();
+        let link_generation = driver.linked();
+        assert_ok!(assert_ok!(driver.as_active_mut(link_generation)).close_link());
+
+        script::run_driver(
+            &mut driver,
+            script::acknowledge(TransferLength::_8Bit, Command::EndSession, 0).chain(
+                script::response(TransferLength::_8Bit, Command::EndSession, &[]),
+            ),
+        );
+        // The session ends once the adapter has had time to return to SIO8.
+        for _ in 0..=frames::ONE_HUNDRED_MILLISECONDS {
+            assert!(driver.is_active());
+            driver.vblank();
+        }
+
+        assert!(!driver.is_active());
+        assert_serial_sentinels();
+        assert!(assert_err!(driver.as_active(link_generation)).is_closed());
+    }
+
+    #[test]
+    fn timeout_restores_serial_registers() {
+        let mut driver = TestDriver::new(Timer::_3, NoSocket, NoSocket, NoDns, NoConfig);
+        write_serial_sentinels();
+        let link_generation = driver.linked();
+
+        // The adapter never answers the idle pulse sent to keep the link alive.
+        let mut frames = 0;
+        while driver.is_active() {
+            assert!(
+                frames < 3 * frames::THREE_SECONDS as u16,
+                "the link never timed out"
+            );
+            if driver.timer.is_running() {
+                driver.timer();
+            } else {
+                driver.vblank();
+                frames += 1;
+            }
+        }
+
+        assert_serial_sentinels();
+        assert!(assert_err!(driver.as_active(link_generation)).is_timeout());
+    }
+
+    #[test]
+    fn link_refuses_running_timer_0() {
+        assert_link_refuses_running_timer(Timer::_0);
+    }
+
+    #[test]
+    fn link_refuses_running_timer_1() {
+        assert_link_refuses_running_timer(Timer::_1);
+    }
+
+    #[test]
+    fn link_refuses_running_timer_2() {
+        assert_link_refuses_running_timer(Timer::_2);
+    }
+
+    #[test]
+    fn link_refuses_running_timer_3() {
+        assert_link_refuses_running_timer(Timer::_3);
+    }
+
+    #[test]
+    fn timer_stopped_when_idle_0() {
+        assert_timer_stopped_when_idle(Timer::_0);
+    }
+
+    #[test]
+    fn timer_stopped_when_idle_1() {
+        assert_timer_stopped_when_idle(Timer::_1);
+    }
+
+    #[test]
+    fn timer_stopped_when_idle_2() {
+        assert_timer_stopped_when_idle(Timer::_2);
+    }
+
+    #[test]
+    fn timer_stopped_when_idle_3() {
+        assert_timer_stopped_when_idle(Timer::_3);
+    }
+
+    #[test]
+    fn release_restores_hardware() {
+        let mut driver = TestDriver::new(Timer::_3, NoSocket, NoSocket, NoDns, NoConfig);
+        let (rcnt, siocnt, tm3cnt) = (RCNT as *mut u16, SIOCNT as *mut u16, TM3CNT as *mut u16);
+        unsafe {
+            rcnt.write_volatile(0x8000);
+            siocnt.write_volatile(0x1003);
+            // A stopped timer, configured by the game to interrupt every 1024 cycles.
+            tm3cnt.write_volatile(0x0043);
+        }
+        let link_generation = driver.linked();
+
+        driver.release();
+
+        unsafe {
+            assert_eq!(rcnt.read_volatile(), 0x8000);
+            assert_eq!(siocnt.read_volatile(), 0x1003);
+            assert_eq!(tm3cnt.read_volatile(), 0x0043);
+        }
+        assert!(assert_err!(driver.as_active(link_generation)).is_closed());
+    }
+
+    #[test]
+    fn migrate_timer_busy() {
+        let mut driver = TestDriver::new(Timer::_3, NoSocket, NoSocket, NoDns, NoConfig);
+        driver.state = State::Active(Active::new(Generation::new()));
+
+        let error = assert_err!(driver.migrate_timer(Timer::_1));
+        assert!(error.is_busy());
+        assert_eq!(driver.timer, Timer::_3);
+    }
+
+    #[test]
+    fn migrate_timer_running() {
+        let mut driver = TestDriver::new(Timer::_3, NoSocket, NoSocket, NoDns, NoConfig);
+        unsafe { TM2CNT.write_volatile(Control::new().start(true)) };
+
+        let result = driver.migrate_timer(Timer::_2);
+        unsafe { TM2CNT.write_volatile(Control::new()) };
+        let error = assert_err!(result);
+        assert!(error.is_timer_in_use());
+        assert_eq!(driver.timer, Timer::_3);
+    }
+
+    #[test]
+    fn migrate_timer_releases_driver_interrupt() {
+        let mut driver = TestDriver::new(Timer::_3, NoSocket, NoSocket, NoDns, NoConfig);
+        driver.state = State::Error(Error::Timeout(active::Timeout::Queue));
+        driver.interrupts = Some(interrupt::Enable::VBLANK);
+
+        let enabled = with_enabled(
+            interrupt::Enable::VBLANK | interrupt::Enable::TIMER3 | interrupt::Enable::SERIAL,
+            || assert_ok!(driver.migrate_timer(Timer::_1)),
+        );
+        assert_eq!(
+            enabled,
+            interrupt::Enable::VBLANK | interrupt::Enable::TIMER1 | interrupt::Enable::SERIAL
+        );
+        assert_eq!(driver.timer, Timer::_1);
+    }
+
+    #[test]
+    fn migrate_timer_keeps_game_interrupt() {
+        let mut driver = TestDriver::new(Timer::_3, NoSocket, NoSocket, NoDns, NoConfig);
+        driver.state = State::Error(Error::Timeout(active::Timeout::Queue));
+        driver.interrupts = Some(interrupt::Enable::VBLANK | interrupt::Enable::TIMER3);
+
+        let enabled = with_enabled(
+            interrupt::Enable::VBLANK | interrupt::Enable::TIMER3 | interrupt::Enable::SERIAL,
+            || assert_ok!(driver.migrate_timer(Timer::_1)),
+        );
+        assert_eq!(
+            enabled,
+            interrupt::Enable::VBLANK
+                | interrupt::Enable::TIMER1
+                | interrupt::Enable::TIMER3
+                | interrupt::Enable::SERIAL
+        );
+    }
+
+    #[test]
+    fn poll_not_polled() {
+        let mut driver = TestDriver::new(Timer::_3, NoSocket, NoSocket, NoDns, NoConfig);
+        driver.state = State::Active(Active::new(Generation::new()));
+
+        assert!(requests_unchanged(
+            interrupt::Enable::TIMER3 | interrupt::Enable::SERIAL,
+            || driver.poll()
+        ));
+    }
+
+    #[test]
+    fn poll_inactive() {
+        let mut driver = TestDriver::new(Timer::_3, NoSocket, NoSocket, NoDns, NoConfig).polled();
+
+        assert!(requests_unchanged(
+            interrupt::Enable::TIMER3 | interrupt::Enable::SERIAL,
+            || driver.poll()
+        ));
+    }
+
+    #[test]
+    fn poll_error() {
+        let mut driver = TestDriver::new(Timer::_3, NoSocket, NoSocket, NoDns, NoConfig).polled();
+        driver.state = State::Error(Error::Aborted);
+
+        assert!(requests_unchanged(
+            interrupt::Enable::TIMER3 | interrupt::Enable::SERIAL,
+            || driver.poll()
+        ));
+    }
+
+    #[test]
+    fn initialize_config_completes_once_written() {
+        let mut driver = Driver::new(
+            Timer::_3,
+            NoSocket,
+            NoSocket,
+            NoDns,
+            Config::new(mobile_system_gb::Config::new()),
+        );
+        let link_generation = driver.linked();
+        // The adapter's configuration block was read as blank.
+        driver
+            .config
+            .read(Err(mobile_system_gb::Error::HeaderM(0xff)));
+        let defaults = mobile_system_gb::Config {
+            registration: mobile_system_gb::Registration::Complete,
+            primary_dns: Ipv4Addr::UNSPECIFIED,
+            secondary_dns: Ipv4Addr::UNSPECIFIED,
+            login_id: [0; 10],
+            email: [0; 24],
+            smtp_server: [0; 20],
+            pop_server: [0; 19],
+            configuration_slots: Default::default(),
+        };
+
+        let written = assert_ok!(
+            assert_ok!(driver.as_active_mut(link_generation)).initialize_config(defaults.clone())
+        );
+        let pending = Pending::<Initialization, _, _, _, _>::new(initialization::Pending {
+            link_generation,
+            written,
+        });
+        assert_none!(pending.status(&driver));
+        assert!(assert_ok!(
+            assert_ok!(driver.as_active(link_generation)).needs_initialization()
+        ));
+
+        script::run_driver(
+            &mut driver,
+            script::acknowledge(TransferLength::_8Bit, Command::WriteConfigurationData, 0x72)
+                .chain(script::response(
+                    TransferLength::_8Bit,
+                    Command::WriteConfigurationData,
+                    &[0x00, 0x71],
+                ))
+                .chain(script::acknowledge(
+                    TransferLength::_8Bit,
+                    Command::WriteConfigurationData,
+                    0x50,
+                ))
+                .chain(script::response(
+                    TransferLength::_8Bit,
+                    Command::WriteConfigurationData,
+                    &[0x71, 0x4f],
+                )),
+        );
+
+        assert_eq!(
+            assert_ok!(assert_some!(pending.status(&driver))),
+            Initialization::Written
+        );
+        assert!(!assert_ok!(
+            assert_ok!(driver.as_active(link_generation)).needs_initialization()
+        ));
+
+        // Now that the adapter is initialized, nothing more is written.
+        let written = assert_ok!(
+            assert_ok!(driver.as_active_mut(link_generation)).initialize_config(defaults)
+        );
+        let pending = Pending::<Initialization, _, _, _, _>::new(initialization::Pending {
+            link_generation,
+            written,
+        });
+        assert_eq!(
+            assert_ok!(assert_some!(pending.status(&driver))),
+            Initialization::AlreadyInitialized
+        );
+        assert!(driver.is_unscheduled());
+    }
 }
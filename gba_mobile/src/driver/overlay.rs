@@ -0,0 +1,214 @@
+use super::{Driver, Error, State};
+use crate::{
+    config, dns,
+    overlay::{LastError, Lines},
+    socket,
+    stats::PersistentStats,
+};
+use core::error::Error as _;
+
+impl<Socket1, Socket2, Dns, Config> Driver<Socket1, Socket2, Dns, Config>
+where
+    Socket1: socket::Slot,
+    Socket2: socket::Slot,
+    Dns: dns::Mode,
+    Config: config::Mode,
+{
+    pub(crate) fn render_overlay(&self, lines: &mut Lines) {
+        let last_error = LastError(
+            self.persistent_stats
+                .as_deref()
+                .and_then(PersistentStats::read)
+                .and_then(|stats| stats.last_errors[0]),
+        );
+        match &self.state {
+            State::Inactive => {
+                lines.line(format_args!("driver: inactive"));
+                lines.line(format_args!("last error: {last_error}"));
+            }
+            State::Active(active) => {
+                lines.line(format_args!("driver: active"));
+                active.render_overlay(lines, last_error);
+            }
+            State::Error(error) => {
+                lines.line(format_args!("driver: error"));
+                lines.line(format_args!("last error: {last_error}"));
+                lines.line(format_args!("{error}"));
+                match error {
+                    Error::Timeout(timeout) => {
+                        // Walk down to the timeout that actually occurred.
+                        let mut source = timeout.source();
+                        lines.line(format_args!("{timeout}"));
+                        while let Some(timeout) = source {
+                            lines.line(format_args!("{timeout}"));
+                            source = timeout.source();
+                        }
+                    }
+                    Error::Error(error) => lines.line(format_args!("{error}")),
+                    Error::Aborted | Error::TimerInUse => {}
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{Active, Command, Driver, Error, State, active, active::script};
+    use crate::{
+        ArrayVec, Digit, Generation, Socket, Timer, config,
+        config::NoConfig,
+        dns,
+        dns::NoDns,
+        mmio::serial::TransferLength,
+        overlay::{MAX_LINES, MAX_WIDTH, render},
+        socket,
+        socket::NoSocket,
+        stats,
+        stats::PersistentStats,
+    };
+    use alloc::{boxed::Box, format, string::String, vec::Vec};
+    use claims::{assert_ok, assert_some};
+    use core::iter;
+    use gba_test::test;
+
+    fn rendered<Socket1, Socket2, Dns, Config>(
+        driver: &Driver<Socket1, Socket2, Dns, Config>,
+    ) -> Vec<String>
+    where
+        Socket1: socket::Slot,
+        Socket2: socket::Slot,
+        Dns: dns::Mode,
+        Config: config::Mode,
+    {
+        let mut lines = Vec::new();
+        render(driver, &mut |index, line| {
+            assert_eq!(index, lines.len());
+            assert!(line.len() <= MAX_WIDTH, "line too long: {line}");
+            assert!(line.is_ascii());
+            lines.push(String::from(line));
+        });
+        assert!(lines.len() <= MAX_LINES);
+        lines
+    }
+
+    #[test]
+    fn inactive() {
+        let driver = Driver::new(Timer::_0, NoSocket, NoSocket, NoDns, NoConfig);
+
+        assert_eq!(rendered(&driver), ["driver: inactive", "last error: none"]);
+    }
+
+    #[test]
+    fn active_idle() {
+        let mut driver = Driver::new(Timer::_0, NoSocket, NoSocket, NoDns, NoConfig);
+        driver.state = State::Active(Active::linked(Generation::new()));
+
+        let lines = rendered(&driver);
+        assert_eq!(lines.len(), MAX_LINES);
+        assert_eq!(lines[2], "flow: none");
+        assert_eq!(lines[3], "command: none");
+    }
+
+    #[test]
+    fn active_mid_packet() {
+        let mut driver = Driver::new(Timer::_0, Socket::new([]), NoSocket, NoDns, NoConfig);
+        let region = Box::leak(Box::new([0; stats::LEN]));
+        PersistentStats {
+            last_errors: [Some(Command::TransferData as u8), None, None, None],
+            ..PersistentStats::default()
+        }
+        .store(region);
+        driver.bind_persistent_stats(region);
+        let mut active = Active::linked(Generation::new());
+        let mut digits = ArrayVec::new();
+        digits.write(&['1', '2', '3'].map(|c| assert_ok!(Digit::try_from(c))));
+        assert_ok!(active.connect(digits));
+        // The adapter rejects the dial packet, and the driver is interrupted partway through
+        // sending it again.
+        let adapter = script::acknowledge(TransferLength::_8Bit, Command::MalformedError, 4)
+            .chain(iter::repeat_n(0xd2, 3));
+        assert_ok!(script::run(
+            &mut active,
+            &mut driver.socket_1,
+            &mut NoSocket,
+            &mut NoDns,
+            &mut NoConfig,
+            adapter,
+        ));
+        driver.state = State::Active(active);
+
+        let lines = rendered(&driver);
+        assert_eq!(lines.len(), MAX_LINES);
+        assert_eq!(lines[0], "driver: active");
+        assert_eq!(lines[1], "phase: connecting");
+        assert_eq!(lines[2], "flow: connection");
+        assert_eq!(lines[3], "command: DialTelephone");
+        assert_eq!(lines[4], "sio8 Blue attempt: 1");
+        assert_eq!(lines[5], "sent: 0 received: 0");
+        assert_eq!(lines[6], "errors: 0 retries: 0");
+        assert_some!(lines[7].strip_suffix(" last error: 0x15"));
+        assert_some!(lines[8].strip_suffix(" call: none"));
+    }
+
+    #[test]
+    fn active_connected() {
+        let mut driver = Driver::new(Timer::_0, Socket::new([]), NoSocket, NoDns, NoConfig);
+        let mut active = Active::linked(Generation::new());
+        let mut digits = ArrayVec::new();
+        digits.write(&['1', '2', '3'].map(|c| assert_ok!(Digit::try_from(c))));
+        assert_ok!(active.connect(digits));
+        let adapter = script::acknowledge(TransferLength::_8Bit, Command::DialTelephone, 4).chain(
+            script::response(TransferLength::_8Bit, Command::DialTelephone, &[]),
+        );
+        assert_ok!(script::run(
+            &mut active,
+            &mut driver.socket_1,
+            &mut NoSocket,
+            &mut NoDns,
+            &mut NoConfig,
+            adapter,
+        ));
+        let session_frames = assert_ok!(active.session_frames());
+        assert_ok!(script::run_frames(
+            &mut active,
+            &mut driver.socket_1,
+            &mut NoSocket,
+            &mut NoDns,
+            &mut NoConfig,
+            30,
+            iter::empty(),
+        ));
+        driver.state = State::Active(active);
+
+        let lines = rendered(&driver);
+        assert_eq!(lines[1], "phase: connected");
+        assert_eq!(
+            lines[8],
+            format!("session: {} call: 30", session_frames + 30)
+        );
+    }
+
+    #[test]
+    fn error_queue_timeout() {
+        let mut driver = Driver::new(Timer::_0, NoSocket, NoSocket, NoDns, NoConfig);
+        driver.state = State::Error(Error::Timeout(active::Timeout::Queue));
+
+        assert_eq!(rendered(&driver).len(), 4);
+    }
+
+    #[test]
+    fn error_aborted() {
+        let mut driver = Driver::new(Timer::_0, NoSocket, NoSocket, NoDns, NoConfig);
+        driver.state = State::Error(Error::Aborted);
+
+        assert_eq!(
+            rendered(&driver),
+            [
+                "driver: error",
+                "last error: none",
+                "communication was aborted"
+            ]
+        );
+    }
+}
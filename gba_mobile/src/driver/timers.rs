@@ -1,3 +1,4 @@
 // These are at a rate of ~60us per tick.
 pub(in crate::driver) const MICROSECONDS_200: u16 = u16::MIN.wrapping_sub(4);
 pub(in crate::driver) const MICROSECONDS_400: u16 = u16::MIN.wrapping_sub(7);
+pub(in crate::driver) const MICROSECONDS_800: u16 = u16::MIN.wrapping_sub(14);
@@ -0,0 +1,89 @@
+//! Guardrails against the driver unintentionally growing.
+//!
+//! Games using this crate usually have little memory to spare, so the limits are declared here in
+//! one place. Raising one of them should be a deliberate change.
+//!
+//! The size of the crate's code is not guarded here. A test binary links the test harness and
+//! every test alongside the driver, so no linker symbol isolates the crate's contribution to
+//! `.text`. Check it against a release build of the example instead, using `nm --size-sort`.
+
+use crate::{
+    Config, Driver, Socket,
+    config::{NoConfig, mobile_system_gb},
+    dns::{Dns, NoDns},
+    mmio::timer::{Control, Frequency, TM3CNT, TM3VAL},
+    socket::NoSocket,
+};
+use core::mem::size_of;
+use gba_test::test;
+
+/// The maximum size of a driver using none of the optional components, in bytes.
+#[cfg(not(feature = "phone-digits-16"))]
+const MAX_MINIMAL_DRIVER_SIZE: usize = 528;
+/// The maximum size of a driver using none of the optional components, in bytes.
+///
+/// This is below the 520 bytes measured for the default of 32 digits, so that the
+/// `phone-digits-16` feature is known to actually shrink the driver.
+#[cfg(feature = "phone-digits-16")]
+const MAX_MINIMAL_DRIVER_SIZE: usize = 496;
+/// The maximum size of a driver using every optional component, in bytes.
+///
+/// The sockets' read buffers are excluded, since their size is chosen by the user.
+#[cfg(not(feature = "phone-digits-16"))]
+const MAX_FULL_DRIVER_SIZE: usize = 1536;
+/// The maximum size of a driver using every optional component, in bytes.
+///
+/// This is below the 1472 bytes measured for the default of 32 digits, so that the
+/// `phone-digits-16` feature is known to actually shrink the driver.
+#[cfg(feature = "phone-digits-16")]
+const MAX_FULL_DRIVER_SIZE: usize = 1440;
+/// The most cycles a single serial interrupt may spend in the driver.
+///
+/// In SIO32 mode, a new transfer is started every 400 microseconds, or about 6700 cycles, so this
+/// leaves the game well over a third of that time.
+const MAX_SERIAL_CYCLES: u16 = 4096;
+
+/// Measures the cycles spent running `f`, using timer 3.
+///
+/// `f` must take fewer than 65536 cycles, or the count wraps around.
+pub(crate) fn cycles<T>(f: impl FnOnce() -> T) -> (T, u16) {
+    unsafe {
+        TM3CNT.write_volatile(Control::new());
+        TM3VAL.write_volatile(0);
+        TM3CNT.write_volatile(Control::new().frequency(Frequency::_1).start(true));
+    }
+    let result = f();
+    let cycles = unsafe { TM3VAL.read_volatile() };
+    unsafe { TM3CNT.write_volatile(Control::new()) };
+    (result, cycles)
+}
+
+/// Asserts that the longest serial interrupt measured stays within [`MAX_SERIAL_CYCLES`].
+pub(crate) fn assert_serial_cycles(cycles: u16) {
+    assert!(
+        cycles <= MAX_SERIAL_CYCLES,
+        "serial interrupt took {cycles} cycles, but the limit is {MAX_SERIAL_CYCLES} cycles"
+    );
+}
+
+#[test]
+fn minimal_driver_size() {
+    let size = size_of::<Driver<NoSocket, NoSocket, NoDns, NoConfig>>();
+
+    assert!(
+        size <= MAX_MINIMAL_DRIVER_SIZE,
+        "minimal driver is {size} bytes, but the limit is {MAX_MINIMAL_DRIVER_SIZE} bytes"
+    );
+}
+
+#[test]
+fn full_driver_size() {
+    let size = size_of::<
+        Driver<Socket<[u8; 0]>, Socket<[u8; 0]>, Dns<255>, Config<mobile_system_gb::Config>>,
+    >();
+
+    assert!(
+        size <= MAX_FULL_DRIVER_SIZE,
+        "full driver is {size} bytes, but the limit is {MAX_FULL_DRIVER_SIZE} bytes"
+    );
+}
@@ -1,4 +1,4 @@
-use crate::{arrayvec, config, driver, internet, socket};
+use crate::{config, driver, internet, socket};
 use core::{
     fmt,
     fmt::{Debug, Display, Formatter},
@@ -85,7 +85,7 @@ where
     }
 }
 
-impl<Socket1, Socket2, Dns, Config, const MAX_LEN: usize> From<arrayvec::error::Capacity<MAX_LEN>>
+impl<Socket1, Socket2, Dns, Config, const MAX_LEN: usize> From<crate::dns::name::Error>
     for Error<Socket1, Socket2, Dns, Config, MAX_LEN>
 where
     Socket1: socket::Slot,
@@ -93,9 +93,9 @@ where
     Dns: crate::dns::Mode,
     Config: config::Mode,
 {
-    fn from(error: arrayvec::error::Capacity<MAX_LEN>) -> Self {
+    fn from(error: crate::dns::name::Error) -> Self {
         Self {
-            kind: Kind::Capacity(error),
+            kind: Kind::Name(error),
         }
     }
 }
@@ -107,7 +107,7 @@ where
     Dns: crate::dns::Mode,
     Config: config::Mode,
 {
-    Capacity(arrayvec::error::Capacity<MAX_LEN>),
+    Name(crate::dns::name::Error),
     Connection(internet::Error<Socket1, Socket2, Dns, Config>),
 }
 
@@ -121,7 +121,7 @@ where
 {
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
         match self {
-            Self::Capacity(error) => formatter.debug_tuple("Capacity").field(error).finish(),
+            Self::Name(error) => formatter.debug_tuple("Name").field(error).finish(),
             Self::Connection(error) => formatter.debug_tuple("Connection").field(error).finish(),
         }
     }
@@ -137,7 +137,7 @@ where
 {
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
         match self {
-            Self::Capacity(_) => formatter.write_str("could not create domain name lookup request"),
+            Self::Name(_) => formatter.write_str("domain name error"),
             Self::Connection(_) => formatter.write_str("Mobile Adapter connection error"),
         }
     }
@@ -153,7 +153,7 @@ where
 {
     fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
         match self {
-            Self::Capacity(error) => Some(error),
+            Self::Name(error) => Some(error),
             Self::Connection(error) => Some(error),
         }
     }
@@ -16,6 +16,37 @@ where
     internal: driver::error::connection::Error<Socket1, Socket2, Dns, Config>,
 }
 
+impl<Socket1, Socket2, Dns, Config> Error<Socket1, Socket2, Dns, Config>
+where
+    Socket1: socket::Slot,
+    Socket2: socket::Slot,
+    Dns: crate::dns::Mode,
+    Config: config::Mode,
+{
+    /// Whether the connection failed because the adapter stopped responding in time.
+    pub fn is_timeout(&self) -> bool {
+        self.internal.is_timeout()
+    }
+
+    /// Whether the connection failed because the adapter itself reported an error.
+    ///
+    /// This includes the adapter responding to a request with an error, as well as it refusing a
+    /// packet every time it was sent.
+    pub fn is_adapter_error(&self) -> bool {
+        self.internal.is_adapter_error()
+    }
+
+    /// Whether the connection has already ended, or the link it was made through has.
+    pub fn is_closed(&self) -> bool {
+        self.internal.is_closed()
+    }
+
+    /// Whether the connection was replaced by a newer one, or its link by a newer link.
+    pub fn is_superseded(&self) -> bool {
+        self.internal.is_superseded()
+    }
+}
+
 impl<Socket1, Socket2, Dns, Config> Debug for Error<Socket1, Socket2, Dns, Config>
 where
     Socket1: socket::Slot,
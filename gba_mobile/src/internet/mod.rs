@@ -3,7 +3,7 @@ pub mod error;
 pub use error::Error;
 
 use crate::{
-    ArrayVec, Connection, Dns, Driver, Generation, Pending, Socket, config, connection, dns,
+    Connection, Dns, Driver, Generation, Pending, Socket, config, connection, dns,
     pending::{self, Pendable, PendableError},
     socket,
 };
@@ -213,12 +213,10 @@ where
     where
         Name: dns::ToName,
     {
+        let name = dns::name::collect(name.to_name())?;
         driver
             .as_active_mut(self.link_generation)?
-            .dns(
-                self.connection_generation,
-                ArrayVec::try_from_iter(name.to_name().into_iter().copied())?,
-            )
+            .dns(self.connection_generation, name)
             .map(|dns_generation| {
                 Pending::new(dns::Pending {
                     link_generation: self.link_generation,
@@ -293,3 +291,45 @@ where
     Config: config::Mode,
 {
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Internet;
+    use crate::{Dns, Driver, Generation, Timer, config::NoConfig, socket::NoSocket};
+    use alloc::format;
+    use claims::assert_err;
+    use core::marker::PhantomData;
+    use gba_test::test;
+
+    type TestDriver = Driver<NoSocket, NoSocket, Dns<4>, NoConfig>;
+
+    fn logged_in() -> (TestDriver, Internet<TestDriver>) {
+        let mut driver = Driver::new(Timer::_0, NoSocket, NoSocket, Dns::new(), NoConfig);
+        let internet = Internet {
+            link_generation: driver.linked(),
+            connection_generation: Generation::new(),
+            driver: PhantomData,
+        };
+        (driver, internet)
+    }
+
+    #[test]
+    fn dns_empty_name() {
+        let (mut driver, internet) = logged_in();
+
+        let error = assert_err!(internet.dns(&mut driver, ""));
+
+        assert_eq!(format!("{error:?}"), "Name(Empty)");
+        assert!(driver.is_unscheduled());
+    }
+
+    #[test]
+    fn dns_name_too_long() {
+        let (mut driver, internet) = logged_in();
+
+        let error = assert_err!(internet.dns(&mut driver, "a.com"));
+
+        assert_eq!(format!("{error:?}"), "Name(TooLong { length: 5, max: 4 })");
+        assert!(driver.is_unscheduled());
+    }
+}
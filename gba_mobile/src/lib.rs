@@ -13,15 +13,22 @@ pub mod digit;
 pub mod dns;
 pub mod internet;
 pub mod link;
+#[cfg(feature = "debug-overlay")]
+pub mod overlay;
 pub mod pending;
 pub mod socket;
+pub mod stats;
 
 mod arrayvec;
+mod cell;
 mod driver;
 mod generation;
+#[cfg(test)]
+mod guardrails;
 mod mmio;
 mod timer;
 
+pub use cell::MobileCell;
 #[doc(inline)]
 pub use config::Config;
 #[doc(inline)]
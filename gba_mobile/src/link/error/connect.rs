@@ -1,4 +1,4 @@
-use crate::{arrayvec, config, dns, driver, link, socket};
+use crate::{config, digit, dns, driver, link, socket};
 use core::{
     fmt,
     fmt::{Debug, Display, Formatter},
@@ -65,7 +65,7 @@ where
     }
 }
 
-impl<Socket1, Socket2, Dns, Config> From<arrayvec::error::Capacity<32>>
+impl<Socket1, Socket2, Dns, Config> From<digit::phone_number::Error>
     for Error<Socket1, Socket2, Dns, Config>
 where
     Socket1: socket::Slot,
@@ -73,7 +73,7 @@ where
     Dns: dns::Mode,
     Config: config::Mode,
 {
-    fn from(error: arrayvec::error::Capacity<32>) -> Self {
+    fn from(error: digit::phone_number::Error) -> Self {
         Self {
             kind: Kind::PhoneNumber(error),
         }
@@ -88,7 +88,7 @@ where
     Config: config::Mode,
 {
     Link(link::Error<Socket1, Socket2, Dns, Config>),
-    PhoneNumber(arrayvec::error::Capacity<32>),
+    PhoneNumber(digit::phone_number::Error),
 }
 
 impl<Socket1, Socket2, Dns, Config> Debug for Kind<Socket1, Socket2, Dns, Config>
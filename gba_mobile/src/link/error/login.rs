@@ -1,5 +1,5 @@
 use super::connect;
-use crate::{arrayvec, config, dns, driver, socket};
+use crate::{arrayvec, config, digit, dns, driver, socket};
 use core::{
     fmt,
     fmt::{Debug, Display, Formatter},
@@ -86,7 +86,7 @@ where
     }
 }
 
-impl<Socket1, Socket2, Dns, Config> From<arrayvec::error::Capacity<32>>
+impl<Socket1, Socket2, Dns, Config> From<digit::phone_number::Error>
     for Error<Socket1, Socket2, Dns, Config>
 where
     Socket1: socket::Slot,
@@ -94,7 +94,7 @@ where
     Dns: dns::Mode,
     Config: config::Mode,
 {
-    fn from(error: arrayvec::error::Capacity<32>) -> Self {
+    fn from(error: digit::phone_number::Error) -> Self {
         Self {
             kind: Kind::Connect(error.into()),
         }
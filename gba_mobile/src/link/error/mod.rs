@@ -1,6 +1,7 @@
 pub mod config;
 pub mod connect;
 pub mod login;
+pub mod write_config;
 
 use crate::{dns, driver, socket};
 use core::{
@@ -18,6 +19,54 @@ where
     internal: driver::error::link::Error<Socket1, Socket2, Dns, Config>,
 }
 
+impl<Socket1, Socket2, Dns, Config> Error<Socket1, Socket2, Dns, Config>
+where
+    Socket1: socket::Slot,
+    Socket2: socket::Slot,
+    Dns: dns::Mode,
+    Config: crate::config::Mode,
+{
+    /// Whether the link was lost because the adapter stopped responding in time.
+    pub fn is_timeout(&self) -> bool {
+        self.internal.is_timeout()
+    }
+
+    /// Whether the link was lost because the adapter itself reported an error.
+    ///
+    /// This includes the adapter responding to a request with an error, as well as it refusing a
+    /// packet every time it was sent.
+    pub fn is_adapter_error(&self) -> bool {
+        self.internal.is_adapter_error()
+    }
+
+    /// Whether the link has already ended, such as through [`Link::close()`](crate::Link::close).
+    pub fn is_closed(&self) -> bool {
+        self.internal.is_closed()
+    }
+
+    /// Whether the link was replaced by a newer one.
+    pub fn is_superseded(&self) -> bool {
+        self.internal.is_superseded()
+    }
+
+    /// Whether the link could not start because the driver's timer was already running.
+    ///
+    /// This usually means something else, such as a music engine, is using the same timer. Stop
+    /// that use of the timer, or give the driver a different one using
+    /// [`Driver::migrate_timer()`](crate::Driver::migrate_timer), before starting a new link.
+    pub fn is_timer_in_use(&self) -> bool {
+        self.internal.is_timer_in_use()
+    }
+
+    /// Whether the driver could not switch timers because a packet was being exchanged.
+    ///
+    /// See [`Driver::migrate_timer()`](crate::Driver::migrate_timer). Trying again on a later
+    /// frame will usually succeed.
+    pub fn is_busy(&self) -> bool {
+        self.internal.is_busy()
+    }
+}
+
 impl<Socket1, Socket2, Dns, Config> Debug for Error<Socket1, Socket2, Dns, Config>
 where
     Socket1: socket::Slot,
@@ -0,0 +1,136 @@
+use crate::{config, config::format::LocationError, dns, driver, link, socket};
+use core::{
+    fmt,
+    fmt::{Debug, Display, Formatter},
+};
+
+pub struct Error<Socket1, Socket2, Dns, Config>
+where
+    Socket1: socket::Slot,
+    Socket2: socket::Slot,
+    Dns: dns::Mode,
+    Config: config::Mode,
+{
+    kind: Kind<Socket1, Socket2, Dns, Config>,
+}
+
+impl<Socket1, Socket2, Dns, Config> Debug for Error<Socket1, Socket2, Dns, Config>
+where
+    Socket1: socket::Slot,
+    Socket2: socket::Slot,
+    Dns: dns::Mode,
+    Config: config::Mode,
+{
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        Debug::fmt(&self.kind, formatter)
+    }
+}
+
+impl<Socket1, Socket2, Dns, Config> Display for Error<Socket1, Socket2, Dns, Config>
+where
+    Socket1: socket::Slot,
+    Socket2: socket::Slot,
+    Dns: dns::Mode,
+    Config: config::Mode,
+{
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        Display::fmt(&self.kind, formatter)
+    }
+}
+
+impl<Socket1, Socket2, Dns, Config> core::error::Error for Error<Socket1, Socket2, Dns, Config>
+where
+    Socket1: socket::Slot + 'static,
+    Socket2: socket::Slot + 'static,
+    Dns: dns::Mode + 'static,
+    Config: config::Mode + 'static,
+{
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        self.kind.source()
+    }
+}
+
+impl<Socket1, Socket2, Dns, Config> From<driver::error::link::Error<Socket1, Socket2, Dns, Config>>
+    for Error<Socket1, Socket2, Dns, Config>
+where
+    Socket1: socket::Slot,
+    Socket2: socket::Slot,
+    Dns: dns::Mode,
+    Config: config::Mode,
+{
+    fn from(error: driver::error::link::Error<Socket1, Socket2, Dns, Config>) -> Self {
+        Self {
+            kind: Kind::Link(error.into()),
+        }
+    }
+}
+
+impl<Socket1, Socket2, Dns, Config> From<LocationError> for Error<Socket1, Socket2, Dns, Config>
+where
+    Socket1: socket::Slot,
+    Socket2: socket::Slot,
+    Dns: dns::Mode,
+    Config: config::Mode,
+{
+    fn from(error: LocationError) -> Self {
+        Self {
+            kind: Kind::Location(error),
+        }
+    }
+}
+
+enum Kind<Socket1, Socket2, Dns, Config>
+where
+    Socket1: socket::Slot,
+    Socket2: socket::Slot,
+    Dns: dns::Mode,
+    Config: config::Mode,
+{
+    Link(link::Error<Socket1, Socket2, Dns, Config>),
+    Location(LocationError),
+}
+
+impl<Socket1, Socket2, Dns, Config> Debug for Kind<Socket1, Socket2, Dns, Config>
+where
+    Socket1: socket::Slot,
+    Socket2: socket::Slot,
+    Dns: dns::Mode,
+    Config: config::Mode,
+{
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Link(error) => formatter.debug_tuple("Link").field(error).finish(),
+            Self::Location(error) => formatter.debug_tuple("Location").field(error).finish(),
+        }
+    }
+}
+
+impl<Socket1, Socket2, Dns, Config> Display for Kind<Socket1, Socket2, Dns, Config>
+where
+    Socket1: socket::Slot,
+    Socket2: socket::Slot,
+    Dns: dns::Mode,
+    Config: config::Mode,
+{
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Link(_) => formatter.write_str("Mobile Adapter link connection error"),
+            Self::Location(_) => formatter.write_str("configuration location error"),
+        }
+    }
+}
+
+impl<Socket1, Socket2, Dns, Config> core::error::Error for Kind<Socket1, Socket2, Dns, Config>
+where
+    Socket1: socket::Slot + 'static,
+    Socket2: socket::Slot + 'static,
+    Dns: dns::Mode + 'static,
+    Config: config::Mode + 'static,
+{
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Link(error) => Some(error),
+            Self::Location(error) => Some(error),
+        }
+    }
+}
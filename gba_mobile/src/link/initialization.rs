@@ -0,0 +1,86 @@
+use super::Error;
+use crate::{
+    Config, Driver, Generation, config, dns,
+    pending::{self, Pendable, PendableError},
+    socket,
+};
+
+/// The outcome of initializing the adapter's configuration.
+///
+/// Returned by the handle from [`Link::initialize_config()`](super::Link::initialize_config) once
+/// the adapter's configuration is known to be initialized.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Initialization {
+    /// The adapter was already initialized, so nothing was written.
+    AlreadyInitialized,
+    /// The defaults were written, and the adapter has accepted them.
+    Written,
+}
+
+#[derive(Debug)]
+pub(crate) struct Pending {
+    pub(crate) link_generation: Generation,
+    pub(crate) written: bool,
+}
+
+impl<Socket1, Socket2, Dns, Format> PendableError<Socket1, Socket2, Dns, Config<Format>>
+    for Initialization
+where
+    Socket1: socket::Slot,
+    Socket2: socket::Slot,
+    Dns: dns::Mode,
+    Format: config::Format,
+{
+    type Error = Error<Socket1, Socket2, Dns, Config<Format>>;
+}
+
+impl<Socket1, Socket2, Dns, Format> pending::Sealed<Socket1, Socket2, Dns, Config<Format>>
+    for Initialization
+where
+    Socket1: socket::Slot,
+    Socket2: socket::Slot,
+    Dns: dns::Mode,
+    Format: config::Format,
+{
+    type State = Pending;
+
+    fn status(
+        state: &Self::State,
+        driver: &Driver<Socket1, Socket2, Dns, Config<Format>>,
+    ) -> Option<Result<Self, Self::Error>> {
+        driver
+            .as_active(state.link_generation)
+            .and_then(|active| active.needs_initialization())
+            .map(|needs_initialization| {
+                (!needs_initialization).then_some(if state.written {
+                    Self::Written
+                } else {
+                    Self::AlreadyInitialized
+                })
+            })
+            .map_err(Into::into)
+            .transpose()
+    }
+
+    fn cancel(
+        state: Self::State,
+        driver: &mut Driver<Socket1, Socket2, Dns, Config<Format>>,
+    ) -> Result<(), Self::Error> {
+        // A write that has been requested is not withdrawn, since the driver's copy of the
+        // configuration has already been replaced. Cancelling only stops waiting for it.
+        driver
+            .as_active_mut(state.link_generation)
+            .map(|_| ())
+            .map_err(Into::into)
+    }
+}
+
+impl<Socket1, Socket2, Dns, Format> Pendable<Socket1, Socket2, Dns, Config<Format>>
+    for Initialization
+where
+    Socket1: socket::Slot,
+    Socket2: socket::Slot,
+    Dns: dns::Mode,
+    Format: config::Format,
+{
+}
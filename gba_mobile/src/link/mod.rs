@@ -1,17 +1,52 @@
 pub mod error;
 
+pub(crate) mod initialization;
+
 pub use error::Error;
+pub use initialization::Initialization;
 
 use crate::{
     Adapter, ArrayVec, Config, Connection, Driver, Generation, Internet, Pending, Socket, config,
     connection,
-    digit::IntoDigits,
+    digit::{self, IntoDigits},
     dns,
     pending::{self, Pendable, PendableError},
     socket,
+    stats::LinkStats,
 };
-use core::{marker::PhantomData, net::Ipv4Addr};
+use core::{marker::PhantomData, net::Ipv4Addr, num::NonZeroU8};
+
+/// A pending peer-to-peer connection made through a link.
+type PendingP2p<Buffer, Socket2, Dns, Config> = Pending<
+    Connection<Driver<Socket<Buffer>, Socket2, Dns, Config>, connection::P2p>,
+    Socket<Buffer>,
+    Socket2,
+    Dns,
+    Config,
+>;
+
+/// The result of requesting that a link accept a call.
+type AcceptResult<Buffer, Socket2, Dns, Config> =
+    Result<PendingP2p<Buffer, Socket2, Dns, Config>, Error<Socket<Buffer>, Socket2, Dns, Config>>;
+
+/// The result of requesting that a link call another adapter.
+type ConnectResult<Buffer, Socket2, Dns, Config> = Result<
+    PendingP2p<Buffer, Socket2, Dns, Config>,
+    error::connect::Error<Socket<Buffer>, Socket2, Dns, Config>,
+>;
 
+/// The result of requesting that a link initialize the adapter's configuration.
+type InitializeConfigResult<Socket1, Socket2, Dns, Format> = Result<
+    Pending<Initialization, Socket1, Socket2, Dns, Config<Format>>,
+    error::write_config::Error<Socket1, Socket2, Dns, Config<Format>>,
+>;
+
+/// A link with a Mobile Adapter.
+///
+/// If communication with the adapter fails, including when the adapter ends the session itself
+/// by responding to a request with an error, the link is not closed silently. Every operation on
+/// the link, and on any connection made through it, instead reports that same error until a new
+/// link is started with [`Link::new()`].
 #[derive(Debug)]
 pub struct Link<Driver> {
     link_generation: Generation,
@@ -34,6 +69,19 @@ where
         })
     }
 
+    /// Returns whether this is the driver's most recent link.
+    ///
+    /// A link is only ever superseded by starting a new one with [`Link::new()`]; once that has
+    /// happened, every operation on this link reports that it was superseded. Unlike those
+    /// operations, this check does not report whether the link has otherwise ended.
+    pub fn is_current(&self, driver: &Driver<Socket1, Socket2, Dns, Config>) -> bool {
+        driver.is_current_link(self.link_generation)
+    }
+
+    /// Ends the session with the adapter.
+    ///
+    /// Any call in progress is hung up first. The session is ended gracefully in the background;
+    /// use [`Driver::is_active()`] to tell when the adapter has acknowledged it.
     pub fn close(
         &self,
         driver: &mut Driver<Socket1, Socket2, Dns, Config>,
@@ -44,10 +92,10 @@ where
             .map_err(Into::into)
     }
 
-    pub fn login<PhoneNumber, Id, Password>(
+    pub fn login<Number, Id, Password>(
         &self,
         driver: &mut Driver<Socket1, Socket2, Dns, Config>,
-        phone_number: PhoneNumber,
+        phone_number: Number,
         id: Id,
         password: Password,
         primary_dns: Ipv4Addr,
@@ -57,11 +105,11 @@ where
         error::login::Error<Socket1, Socket2, Dns, Config>,
     >
     where
-        PhoneNumber: IntoDigits,
+        Number: IntoDigits,
         Id: IntoIterator<Item = u8>,
         Password: IntoIterator<Item = u8>,
     {
-        ArrayVec::try_from_iter(phone_number.into_digits())
+        digit::phone_number::collect(phone_number)
             .map_err(Into::into)
             .and_then(|digits| {
                 ArrayVec::try_from_iter(id)
@@ -86,6 +134,10 @@ where
             })
     }
 
+    /// Returns the type of adapter that was linked with.
+    ///
+    /// The adapter reports its type in every packet it sends, so this is known as soon as the link
+    /// is established.
     pub fn adapter(
         &self,
         driver: &Driver<Socket1, Socket2, Dns, Config>,
@@ -95,6 +147,92 @@ where
             .adapter()
             .map_err(Into::into)
     }
+
+    /// Returns the number of frames since the link was established.
+    ///
+    /// This is counted by the driver on every vblank, whether or not any requests are being made,
+    /// so it keeps advancing while the game itself is paused.
+    pub fn session_frames(
+        &self,
+        driver: &Driver<Socket1, Socket2, Dns, Config>,
+    ) -> Result<u32, Error<Socket1, Socket2, Dns, Config>> {
+        driver
+            .as_active(self.link_generation)?
+            .session_frames()
+            .map_err(Into::into)
+    }
+
+    /// Returns how the most recent call hung up during this link ended.
+    ///
+    /// Hanging up happens in the background, so this is `None` until the hang-up has completed, as
+    /// well as when no call has been hung up yet. A call that the other side hung up first, or at
+    /// the same time, is still hung up successfully, and is reported as
+    /// [`DisconnectReason::MutualOrRemoteFirst`](connection::DisconnectReason::MutualOrRemoteFirst).
+    pub fn disconnect_reason(
+        &self,
+        driver: &Driver<Socket1, Socket2, Dns, Config>,
+    ) -> Result<Option<connection::DisconnectReason>, Error<Socket1, Socket2, Dns, Config>> {
+        driver
+            .as_active(self.link_generation)?
+            .disconnect_reason()
+            .map_err(Into::into)
+    }
+
+    /// Returns counters describing how reliably packets have been exchanged during this link.
+    ///
+    /// A rising number of receive errors and retries means the connection to the adapter is
+    /// unreliable, which can be used to show a connection quality indicator.
+    pub fn link_stats(
+        &self,
+        driver: &Driver<Socket1, Socket2, Dns, Config>,
+    ) -> Result<LinkStats, Error<Socket1, Socket2, Dns, Config>> {
+        driver
+            .as_active(self.link_generation)?
+            .link_stats()
+            .map_err(Into::into)
+    }
+
+    /// Sets all counters returned by [`Link::link_stats()`] back to zero.
+    pub fn reset_link_stats(
+        &self,
+        driver: &mut Driver<Socket1, Socket2, Dns, Config>,
+    ) -> Result<(), Error<Socket1, Socket2, Dns, Config>> {
+        driver
+            .as_active_mut(self.link_generation)?
+            .reset_link_stats()
+            .map_err(Into::into)
+    }
+
+    /// Returns the status most recently reported in the adapter's extended acknowledgement.
+    ///
+    /// Some adapter reimplementations use the otherwise unused second byte of the acknowledgement
+    /// signal to report flow-control and status bits. This is `None` if the last packet received
+    /// from the adapter left that byte as `0x00`, and always `None` unless the driver was created
+    /// with [`Driver::extended_ack()`].
+    pub fn extended_acknowledgement(
+        &self,
+        driver: &Driver<Socket1, Socket2, Dns, Config>,
+    ) -> Result<Option<NonZeroU8>, Error<Socket1, Socket2, Dns, Config>> {
+        driver
+            .as_active(self.link_generation)?
+            .extended_acknowledgement()
+            .map_err(Into::into)
+    }
+
+    /// Returns whether the adapter's configuration was found to be uninitialized while linking.
+    ///
+    /// Operations depending on the configuration, such as logging in, will fail until a valid
+    /// configuration has been written to the adapter. This remains `true` until the adapter has
+    /// accepted such a write, not just until one is requested.
+    pub fn needs_initialization(
+        &self,
+        driver: &Driver<Socket1, Socket2, Dns, Config>,
+    ) -> Result<bool, Error<Socket1, Socket2, Dns, Config>> {
+        driver
+            .as_active(self.link_generation)?
+            .needs_initialization()
+            .map_err(Into::into)
+    }
 }
 
 impl<Buffer, Socket2, Dns, Config> Link<Driver<Socket<Buffer>, Socket2, Dns, Config>>
@@ -107,16 +245,7 @@ where
     pub fn accept(
         &self,
         driver: &mut Driver<Socket<Buffer>, Socket2, Dns, Config>,
-    ) -> Result<
-        Pending<
-            Connection<Driver<Socket<Buffer>, Socket2, Dns, Config>, connection::P2p>,
-            Socket<Buffer>,
-            Socket2,
-            Dns,
-            Config,
-        >,
-        Error<Socket<Buffer>, Socket2, Dns, Config>,
-    > {
+    ) -> AcceptResult<Buffer, Socket2, Dns, Config> {
         driver
             .as_active_mut(self.link_generation)?
             .accept()
@@ -131,24 +260,15 @@ where
             .map_err(Into::into)
     }
 
-    pub fn connect<PhoneNumber>(
+    pub fn connect<Number>(
         &self,
         driver: &mut Driver<Socket<Buffer>, Socket2, Dns, Config>,
-        phone_number: PhoneNumber,
-    ) -> Result<
-        Pending<
-            Connection<Driver<Socket<Buffer>, Socket2, Dns, Config>, connection::P2p>,
-            Socket<Buffer>,
-            Socket2,
-            Dns,
-            Config,
-        >,
-        error::connect::Error<Socket<Buffer>, Socket2, Dns, Config>,
-    >
+        phone_number: Number,
+    ) -> ConnectResult<Buffer, Socket2, Dns, Config>
     where
-        PhoneNumber: IntoDigits,
+        Number: IntoDigits,
     {
-        ArrayVec::try_from_iter(phone_number.into_digits())
+        digit::phone_number::collect(phone_number)
             .map_err(Into::into)
             .and_then(|digits| {
                 driver
@@ -174,6 +294,13 @@ where
     Dns: dns::Mode,
     Format: config::Format,
 {
+    /// Returns the adapter's configuration.
+    ///
+    /// The configuration is read from the adapter while linking, before the link is reported as
+    /// established, so it is available immediately without any further communication. Together
+    /// with [`adapter()`](Self::adapter) and
+    /// [`needs_initialization()`](Self::needs_initialization), this covers what is learned about
+    /// the adapter during linking.
     pub fn config(
         &self,
         driver: &Driver<Socket1, Socket2, Dns, Config<Format>>,
@@ -188,16 +315,59 @@ where
             .map_err(Into::into)
     }
 
+    /// Writes a new configuration to the adapter.
+    ///
+    /// The driver keeps its own copy of the configuration, so [`config()`](Self::config) returns
+    /// the new value immediately; there is no separately cached value that could become stale. The
+    /// write to the adapter itself happens in the background.
+    ///
+    /// The value is only read from the driver's copy once the write actually begins, so writes
+    /// made while an earlier one is still waiting to be sent replace it rather than being sent one
+    /// after another. A menu that writes its changes and then reverts them before the driver gets
+    /// to them will therefore only cause a single write.
+    ///
+    /// Every write of the configuration must lie within the adapter's
+    /// [`BLOCK_LEN`](config::format::BLOCK_LEN)-byte configuration block. Otherwise, an error is
+    /// returned and nothing is written.
     pub fn write_config(
         &self,
         driver: &mut Driver<Socket1, Socket2, Dns, Config<Format>>,
         format: Format,
-    ) -> Result<(), Error<Socket1, Socket2, Dns, Config<Format>>> {
+    ) -> Result<(), error::write_config::Error<Socket1, Socket2, Dns, Config<Format>>> {
+        config::format::validate_writes(&format)?;
         driver
             .as_active_mut(self.link_generation)?
             .write_config(format)
             .map_err(Into::into)
     }
+
+    /// Writes `defaults` to the adapter if its configuration was found to be uninitialized.
+    ///
+    /// The returned handle completes once the adapter has accepted the write, reporting whether
+    /// the defaults were [`Written`](Initialization::Written). An adapter that is already
+    /// initialized is left untouched, and the handle completes immediately. Until the handle
+    /// completes, [`needs_initialization()`](Self::needs_initialization) continues to return
+    /// `true`.
+    ///
+    /// The defaults are validated in the same way as by [`write_config()`](Self::write_config),
+    /// whether or not they end up being written.
+    pub fn initialize_config(
+        &self,
+        driver: &mut Driver<Socket1, Socket2, Dns, Config<Format>>,
+        defaults: Format,
+    ) -> InitializeConfigResult<Socket1, Socket2, Dns, Format> {
+        config::format::validate_writes(&defaults)?;
+        driver
+            .as_active_mut(self.link_generation)?
+            .initialize_config(defaults)
+            .map(|written| {
+                Pending::new(initialization::Pending {
+                    link_generation: self.link_generation,
+                    written,
+                })
+            })
+            .map_err(Into::into)
+    }
 }
 
 impl<Socket1, Socket2, Dns, Config> PendableError<Socket1, Socket2, Dns, Config>
@@ -259,3 +429,162 @@ where
     Config: config::Mode,
 {
 }
+
+impl<Socket1, Socket2, Dns, Config>
+    Pending<Link<Driver<Socket1, Socket2, Dns, Config>>, Socket1, Socket2, Dns, Config>
+where
+    Socket1: socket::Slot,
+    Socket2: socket::Slot,
+    Dns: dns::Mode,
+    Config: config::Mode,
+{
+    /// Returns whether the pending link is the driver's most recent link.
+    ///
+    /// See [`Link::is_current()`].
+    pub fn is_current(&self, driver: &Driver<Socket1, Socket2, Dns, Config>) -> bool {
+        self.state().is_current(driver)
+    }
+}
+
+impl<Buffer, Socket2, Dns, Config>
+    Pending<
+        Link<Driver<Socket<Buffer>, Socket2, Dns, Config>>,
+        Socket<Buffer>,
+        Socket2,
+        Dns,
+        Config,
+    >
+where
+    Buffer: socket::Buffer,
+    Socket2: socket::Slot,
+    Dns: dns::Mode,
+    Config: config::Mode,
+{
+    /// Listen for an incoming call as soon as linking completes.
+    ///
+    /// This behaves like [`Link::accept()`], but can be requested before the link is established.
+    /// If linking fails, the returned connection reports the same error as the link.
+    pub fn then_accept(
+        &self,
+        driver: &mut Driver<Socket<Buffer>, Socket2, Dns, Config>,
+    ) -> AcceptResult<Buffer, Socket2, Dns, Config> {
+        self.state().accept(driver)
+    }
+
+    /// Call another adapter as soon as linking completes.
+    ///
+    /// This behaves like [`Link::connect()`], but can be requested before the link is established.
+    /// If linking fails, the returned connection reports the same error as the link.
+    pub fn then_connect<Number>(
+        &self,
+        driver: &mut Driver<Socket<Buffer>, Socket2, Dns, Config>,
+        phone_number: Number,
+    ) -> ConnectResult<Buffer, Socket2, Dns, Config>
+    where
+        Number: IntoDigits,
+    {
+        self.state().connect(driver, phone_number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Link;
+    use crate::{
+        Config, Driver, Timer,
+        config::{
+            self,
+            format::{Location, ReadResult},
+        },
+        dns::NoDns,
+        socket::NoSocket,
+    };
+    use alloc::format;
+    use claims::assert_err;
+    use core::{convert::Infallible, marker::PhantomData};
+    use deranged::RangedU8;
+    use gba_test::test;
+
+    /// A format whose only write extends one byte past the end of the configuration block.
+    #[derive(Clone, Debug)]
+    struct OutOfBlock;
+
+    #[derive(Debug)]
+    struct OutOfBlockSegments;
+
+    impl config::Format for OutOfBlock {
+        const WRITES: usize = 1;
+
+        type Segments = OutOfBlockSegments;
+        type Error = Infallible;
+
+        fn segments() -> Self::Segments {
+            OutOfBlockSegments
+        }
+
+        fn write(&self, _request: usize, _bytes: &mut [u8; 128]) -> Location {
+            Location {
+                offset: 0x80,
+                length: RangedU8::new_static::<0x41>(),
+            }
+        }
+    }
+
+    impl config::format::Segments for OutOfBlockSegments {
+        type Format = OutOfBlock;
+
+        fn location(&self) -> Location {
+            Location {
+                offset: 0,
+                length: RangedU8::new_static::<0x40>(),
+            }
+        }
+
+        fn read(self, _bytes: &[u8]) -> Result<ReadResult<OutOfBlock, Self>, Infallible> {
+            Ok(ReadResult::Success(OutOfBlock))
+        }
+    }
+
+    type TestDriver = Driver<NoSocket, NoSocket, NoDns, Config<OutOfBlock>>;
+
+    fn linked() -> (TestDriver, Link<TestDriver>) {
+        let mut driver = Driver::new(
+            Timer::_0,
+            NoSocket,
+            NoSocket,
+            NoDns,
+            Config::new(OutOfBlockSegments),
+        );
+        let link = Link {
+            link_generation: driver.linked(),
+            driver: PhantomData,
+        };
+        (driver, link)
+    }
+
+    #[test]
+    fn write_config_out_of_block() {
+        let (mut driver, link) = linked();
+
+        let error = assert_err!(link.write_config(&mut driver, OutOfBlock));
+
+        assert_eq!(
+            format!("{error:?}"),
+            "Location(LocationError { offset: 128, length: 65 })"
+        );
+        assert!(driver.is_unscheduled());
+    }
+
+    #[test]
+    fn initialize_config_out_of_block() {
+        let (mut driver, link) = linked();
+
+        let error = assert_err!(link.initialize_config(&mut driver, OutOfBlock));
+
+        assert_eq!(
+            format!("{error:?}"),
+            "Location(LocationError { offset: 128, length: 65 })"
+        );
+        assert!(driver.is_unscheduled());
+    }
+}
@@ -1,6 +1,15 @@
 use core::ops::BitOr;
 
 pub(crate) const ENABLE: *mut Enable = 0x0400_0200 as *mut Enable;
+/// Interrupt request flags.
+///
+/// These share their layout with the enable register. A flag is raised whether or not its
+/// interrupt is enabled, and is acknowledged by writing it back.
+pub(crate) const REQUEST: *mut Enable = 0x0400_0202 as *mut Enable;
+/// Interrupt master enable.
+///
+/// Only the lowest bit is used. No interrupts are raised while it is cleared.
+pub(crate) const MASTER_ENABLE: *mut u16 = 0x0400_0208 as *mut u16;
 
 #[derive(Debug, Eq, PartialEq)]
 pub(crate) struct Enable(u16);
@@ -12,6 +21,15 @@ impl Enable {
     pub(crate) const TIMER2: Self = Self(0b0000_0000_0010_0000);
     pub(crate) const TIMER3: Self = Self(0b0000_0000_0100_0000);
     pub(crate) const SERIAL: Self = Self(0b0000_0000_1000_0000);
+
+    pub(crate) fn contains(&self, other: &Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns these interrupts with all of `other`'s interrupts removed.
+    pub(crate) fn without(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
 }
 
 impl BitOr for Enable {
@@ -49,4 +67,28 @@ mod tests {
             Enable(0b0000_0000_0100_0001)
         );
     }
+
+    #[test]
+    fn contains() {
+        assert!((Enable::SERIAL | Enable::TIMER1).contains(&Enable::SERIAL));
+    }
+
+    #[test]
+    fn contains_missing() {
+        assert!(!(Enable::SERIAL | Enable::TIMER1).contains(&Enable::TIMER2));
+    }
+
+    #[test]
+    fn without() {
+        assert_eq!(
+            (Enable::VBLANK | Enable::SERIAL | Enable::TIMER1)
+                .without(Enable::SERIAL | Enable::TIMER1),
+            Enable::VBLANK
+        );
+    }
+
+    #[test]
+    fn without_missing() {
+        assert_eq!(Enable::VBLANK.without(Enable::SERIAL), Enable::VBLANK);
+    }
 }
@@ -38,6 +38,12 @@ impl Control {
         Self((self.0 & 0b1111_1111_0111_1111) | ((enabled as u16) << 7))
     }
 
+    /// Whether a transfer has been started.
+    #[cfg(test)]
+    pub(crate) fn is_started(&self) -> bool {
+        self.0 & 0b0000_0000_1000_0000 != 0
+    }
+
     /// Configures the transfer length.
     pub(crate) const fn transfer_length(self, transfer_length: TransferLength) -> Self {
         Self((self.0 & 0b1100_1111_1111_1111) | ((transfer_length as u16) << 12))
@@ -10,6 +10,9 @@ pub(crate) const TM3VAL: *mut u16 = 0x0400_010c as *mut u16;
 /// The frequency with which to increment the timer.
 #[derive(Debug)]
 pub(crate) enum Frequency {
+    /// A single period is one CPU cycle, approximately 60ns.
+    #[cfg(test)]
+    _1 = 0,
     /// A single period is approximately 61us.
     _1024 = 3,
 }
@@ -33,4 +36,8 @@ impl Control {
     pub(crate) fn start(self, start: bool) -> Self {
         Self((self.0 & 0b1111_1111_0111_1111) | ((start as u16) << 7))
     }
+
+    pub(crate) fn is_started(&self) -> bool {
+        self.0 & 0b1000_0000 != 0
+    }
 }
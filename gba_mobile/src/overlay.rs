@@ -0,0 +1,157 @@
+//! On-device debugging overlay.
+//!
+//! When testing on hardware without a debugger attached, [`render()`] provides a quick view of
+//! the driver's current state. The overlay is produced as a small block of plain text lines, which
+//! a game can draw using its own font.
+
+use crate::{Driver, config, dns, socket};
+use core::{
+    fmt,
+    fmt::{Arguments, Display, Formatter, Write},
+    str,
+};
+
+/// The maximum number of lines rendered by [`render()`].
+pub const MAX_LINES: usize = 9;
+/// The maximum number of characters in a single line rendered by [`render()`].
+pub const MAX_WIDTH: usize = 30;
+
+/// Render an overlay describing the current state of the driver.
+///
+/// Each line is passed to `target` along with its index. At most [`MAX_LINES`] lines are
+/// rendered, and each line consists of at most [`MAX_WIDTH`] ASCII characters. Rendering never
+/// allocates.
+pub fn render<Socket1, Socket2, Dns, Config>(
+    driver: &Driver<Socket1, Socket2, Dns, Config>,
+    target: &mut dyn FnMut(usize, &str),
+) where
+    Socket1: socket::Slot,
+    Socket2: socket::Slot,
+    Dns: dns::Mode,
+    Config: config::Mode,
+{
+    driver.render_overlay(&mut Lines::new(target));
+}
+
+/// The command that was being exchanged when the most recent failed session ended, as recorded in
+/// the driver's persistent statistics.
+pub(crate) struct LastError(pub(crate) Option<u8>);
+
+impl Display for LastError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self.0 {
+            Some(command) => write!(formatter, "{command:#04x}"),
+            None => formatter.write_str("none"),
+        }
+    }
+}
+
+/// Collects formatted lines, passing them on to the render target.
+pub(crate) struct Lines<'a> {
+    target: &'a mut dyn FnMut(usize, &str),
+    index: usize,
+}
+
+impl<'a> Lines<'a> {
+    fn new(target: &'a mut dyn FnMut(usize, &str)) -> Self {
+        Self { target, index: 0 }
+    }
+
+    /// Render a single line.
+    ///
+    /// Lines past [`MAX_LINES`] are dropped, and the line is truncated to [`MAX_WIDTH`].
+    pub(crate) fn line(&mut self, args: Arguments) {
+        if self.index >= MAX_LINES {
+            return;
+        }
+
+        let mut line = Line::new();
+        // Writing to a line never fails; it only truncates.
+        let _ = line.write_fmt(args);
+        (self.target)(self.index, line.as_str());
+        self.index += 1;
+    }
+}
+
+/// A fixed-width line of ASCII text.
+struct Line {
+    bytes: [u8; MAX_WIDTH],
+    len: usize,
+}
+
+impl Line {
+    fn new() -> Self {
+        Self {
+            bytes: [0; MAX_WIDTH],
+            len: 0,
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        str::from_utf8(self.bytes.get(..self.len).unwrap_or(&[])).unwrap_or("")
+    }
+}
+
+impl Write for Line {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for character in s.chars() {
+            let Some(byte) = self.bytes.get_mut(self.len) else {
+                break;
+            };
+            // Games are unlikely to have glyphs for anything outside of ASCII.
+            *byte = if character.is_ascii() {
+                character as u8
+            } else {
+                b'?'
+            };
+            self.len += 1;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Line, Lines, MAX_LINES, MAX_WIDTH};
+    use core::fmt::Write;
+    use gba_test::test;
+
+    #[test]
+    fn line_truncates() {
+        let mut line = Line::new();
+        assert!(
+            write!(
+                line,
+                "{}",
+                "a very long line that will never fit on the screen"
+            )
+            .is_ok()
+        );
+
+        assert_eq!(line.as_str().len(), MAX_WIDTH);
+        assert_eq!(line.as_str(), "a very long line that will nev");
+    }
+
+    #[test]
+    fn line_replaces_non_ascii() {
+        let mut line = Line::new();
+        assert!(write!(line, "caf\u{e9}").is_ok());
+
+        assert_eq!(line.as_str(), "caf?");
+    }
+
+    #[test]
+    fn lines_limited() {
+        let mut count = 0;
+        let mut target = |index: usize, _: &str| {
+            assert_eq!(index, count);
+            count += 1;
+        };
+        let mut lines = Lines::new(&mut target);
+        for index in 0..(MAX_LINES * 2) {
+            lines.line(format_args!("line {index}"));
+        }
+
+        assert_eq!(count, MAX_LINES);
+    }
+}
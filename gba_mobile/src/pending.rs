@@ -37,6 +37,11 @@ where
 {
 }
 
+/// An operation that has been requested but may not have completed yet.
+///
+/// Every request returns its own handle, which is checked using [`status()`](Self::status) until
+/// the operation completes. Games starting several operations of the same kind can tell their
+/// results apart by storing any context they need next to the corresponding handle.
 #[derive(Debug)]
 pub struct Pending<T, Socket1, Socket2, Dns, Config>
 where
@@ -69,6 +74,10 @@ where
         }
     }
 
+    pub(crate) fn state(&self) -> &T::State {
+        &self.state
+    }
+
     pub fn status(
         &self,
         driver: &Driver<Socket1, Socket2, Dns, Config>,
@@ -0,0 +1,406 @@
+//! Session statistics persisted across play sessions.
+//!
+//! A game can bind a small region of persistent memory (usually SRAM) to the driver using
+//! [`Driver::bind_persistent_stats()`]. The driver then keeps a compact record of how its link
+//! sessions ended, which can be read back using [`PersistentStats::parse()`] to help diagnose
+//! problems players run into.
+//!
+//! [`Driver::bind_persistent_stats()`]: crate::Driver::bind_persistent_stats
+
+use core::ptr;
+
+/// The number of bytes used by a persisted record.
+pub const LEN: usize = 64;
+
+const MAGIC: [u8; 2] = *b"MS";
+const CHECKSUM_OFFSET: usize = LEN - 2;
+
+/// The way in which a link session ended.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub enum Reason {
+    /// The link was closed normally.
+    Closed = 0x01,
+    /// Communication with the adapter timed out.
+    Timeout = 0x02,
+    /// Communication with the adapter failed.
+    Error = 0x03,
+}
+
+impl Reason {
+    fn try_from(byte: u8) -> Option<Self> {
+        match byte {
+            0x01 => Some(Self::Closed),
+            0x02 => Some(Self::Timeout),
+            0x03 => Some(Self::Error),
+            _ => None,
+        }
+    }
+}
+
+/// Reads a persisted command ID, where `0x00` means no command was recorded.
+fn command_id(byte: u8) -> Option<u8> {
+    (byte != 0x00).then_some(byte)
+}
+
+/// Statistics accumulated over all link sessions since the record was created.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PersistentStats {
+    /// The total number of link sessions that have ended.
+    pub sessions: u16,
+    /// The number of sessions that ended due to a timeout.
+    pub timeouts: u16,
+    /// The number of sessions that ended due to a communication error.
+    pub errors: u16,
+    /// The reasons the most recent sessions ended, most recent first.
+    pub recent: [Option<Reason>; 4],
+    /// The most times the adapter had to be asked to send a single response again, over all
+    /// sessions.
+    pub worst_retry_streak: u8,
+    /// The most recent sessions that ended due to a timeout or an error, most recent first.
+    ///
+    /// Each is recorded as the ID of the command that was being exchanged with the adapter when
+    /// the session ended, as defined by the Mobile Adapter GB protocol.
+    pub last_errors: [Option<u8>; 4],
+}
+
+impl PersistentStats {
+    /// Parse a persisted record.
+    ///
+    /// Returns `None` if the record is corrupted or was never written.
+    pub fn parse(bytes: &[u8; LEN]) -> Option<Self> {
+        if bytes[..2] != MAGIC {
+            return None;
+        }
+        let checksum = u16::from_be_bytes([bytes[CHECKSUM_OFFSET], bytes[CHECKSUM_OFFSET + 1]]);
+        if checksum != Self::checksum(bytes) {
+            return None;
+        }
+
+        Some(Self {
+            sessions: u16::from_be_bytes([bytes[2], bytes[3]]),
+            timeouts: u16::from_be_bytes([bytes[4], bytes[5]]),
+            errors: u16::from_be_bytes([bytes[6], bytes[7]]),
+            recent: [
+                Reason::try_from(bytes[8]),
+                Reason::try_from(bytes[9]),
+                Reason::try_from(bytes[10]),
+                Reason::try_from(bytes[11]),
+            ],
+            worst_retry_streak: bytes[12],
+            last_errors: [
+                command_id(bytes[13]),
+                command_id(bytes[14]),
+                command_id(bytes[15]),
+                command_id(bytes[16]),
+            ],
+        })
+    }
+
+    fn checksum(bytes: &[u8; LEN]) -> u16 {
+        bytes[..CHECKSUM_OFFSET]
+            .iter()
+            .copied()
+            .fold(0u16, |sum, byte| sum.wrapping_add(byte as u16))
+    }
+
+    /// Records the end of a session, along with the command being exchanged when it ended.
+    pub(crate) fn record(&mut self, reason: Reason, link_stats: &LinkStats, command: u8) {
+        self.sessions = self.sessions.saturating_add(1);
+        match reason {
+            Reason::Closed => {}
+            Reason::Timeout => self.timeouts = self.timeouts.saturating_add(1),
+            Reason::Error => self.errors = self.errors.saturating_add(1),
+        }
+        self.recent.copy_within(..3, 1);
+        self.recent[0] = Some(reason);
+        self.worst_retry_streak = self.worst_retry_streak.max(link_stats.max_retry_streak);
+        if !matches!(reason, Reason::Closed) {
+            self.last_errors.copy_within(..3, 1);
+            self.last_errors[0] = command_id(command);
+        }
+    }
+
+    /// Serializes the record, passing each byte and its offset to `write`.
+    fn serialize<Write>(&self, mut write: Write)
+    where
+        Write: FnMut(usize, u8),
+    {
+        let mut bytes = [0; LEN];
+        bytes[..2].copy_from_slice(&MAGIC);
+        bytes[2..4].copy_from_slice(&self.sessions.to_be_bytes());
+        bytes[4..6].copy_from_slice(&self.timeouts.to_be_bytes());
+        bytes[6..8].copy_from_slice(&self.errors.to_be_bytes());
+        for (byte, reason) in bytes[8..12].iter_mut().zip(self.recent) {
+            *byte = reason.map_or(0x00, |reason| reason as u8);
+        }
+        bytes[12] = self.worst_retry_streak;
+        for (byte, command) in bytes[13..17].iter_mut().zip(self.last_errors) {
+            *byte = command.unwrap_or(0x00);
+        }
+        let checksum = Self::checksum(&bytes);
+        bytes[CHECKSUM_OFFSET..].copy_from_slice(&checksum.to_be_bytes());
+
+        for (offset, byte) in bytes.into_iter().enumerate() {
+            write(offset, byte);
+        }
+    }
+
+    /// Read a record from persistent memory, without resetting it if it is corrupted.
+    pub(crate) fn read(region: &[u8; LEN]) -> Option<Self> {
+        // SRAM only supports 8-bit accesses, so every byte is read individually.
+        let mut bytes = [0; LEN];
+        for (offset, byte) in bytes.iter_mut().enumerate() {
+            *byte = unsafe { ptr::read_volatile(region.as_ptr().add(offset)) };
+        }
+        Self::parse(&bytes)
+    }
+
+    /// Load a record from persistent memory, resetting it if it is corrupted.
+    pub(crate) fn load(region: &mut [u8; LEN]) -> Self {
+        Self::read(region).unwrap_or_else(|| {
+            let stats = Self::default();
+            stats.store(region);
+            stats
+        })
+    }
+
+    /// Store the record in persistent memory.
+    pub(crate) fn store(&self, region: &mut [u8; LEN]) {
+        // SRAM only supports 8-bit accesses, so every byte is written individually.
+        let region = region.as_mut_ptr();
+        self.serialize(|offset, byte| unsafe { ptr::write_volatile(region.add(offset), byte) });
+    }
+}
+
+/// Counters describing how reliably packets are being exchanged with the adapter.
+///
+/// These only cover the current link session, and start again from zero whenever a new link is
+/// started. Every counter saturates rather than wrapping.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct LinkStats {
+    /// The number of packets sent to the adapter, including packets sent again.
+    pub packets_sent: u16,
+    /// The number of responses received from the adapter without error.
+    pub packets_received: u16,
+    /// The number of responses that could not be received correctly.
+    pub receive_errors: u16,
+    /// The number of receive errors that were checksum mismatches.
+    pub checksum_errors: u16,
+    /// The number of times the adapter was asked to send a response again after a receive error.
+    pub retries: u16,
+    /// The number of times a serial transfer went at least a whole frame without completing, after
+    /// which the exchange carried on normally.
+    pub serial_timeouts_recovered: u16,
+    /// The longest time between two serial interrupts while a packet was being sent or received, in
+    /// frames.
+    ///
+    /// Time spent waiting for the adapter to begin its response is not included, since the adapter
+    /// is only polled every few frames then.
+    pub max_serial_gap: u8,
+    /// The most times the adapter had to be asked to send a single response again.
+    pub max_retry_streak: u8,
+}
+
+impl LinkStats {
+    pub(crate) fn record_sent(&mut self) {
+        self.packets_sent = self.packets_sent.saturating_add(1);
+    }
+
+    pub(crate) fn record_received(&mut self) {
+        self.packets_received = self.packets_received.saturating_add(1);
+    }
+
+    pub(crate) fn record_receive_error(&mut self, checksum: bool) {
+        self.receive_errors = self.receive_errors.saturating_add(1);
+        if checksum {
+            self.checksum_errors = self.checksum_errors.saturating_add(1);
+        }
+    }
+
+    /// Records that a response was asked for again, for the given time in a row.
+    pub(crate) fn record_retry(&mut self, attempt: u8) {
+        self.retries = self.retries.saturating_add(1);
+        self.max_retry_streak = self.max_retry_streak.max(attempt);
+    }
+
+    /// Records the number of frames that passed before a serial interrupt was raised.
+    pub(crate) fn record_serial_gap(&mut self, frames: u8) {
+        // A single vblank can fall between any two transfers, but a second one means a whole frame
+        // passed without the transfer completing.
+        if frames >= 2 {
+            self.serial_timeouts_recovered = self.serial_timeouts_recovered.saturating_add(1);
+        }
+        self.max_serial_gap = self.max_serial_gap.max(frames);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LEN, LinkStats, PersistentStats, Reason};
+    use claims::{assert_none, assert_some_eq};
+    use gba_test::test;
+
+    #[test]
+    fn parse_blank() {
+        assert_none!(PersistentStats::parse(&[0xff; LEN]));
+    }
+
+    #[test]
+    fn two_sessions_one_timeout() {
+        let mut region = [0xff; LEN];
+        let mut stats = PersistentStats::load(&mut region);
+        stats.record(Reason::Closed, &LinkStats::default(), 0x11);
+        stats.store(&mut region);
+        let mut stats = PersistentStats::load(&mut region);
+        stats.record(
+            Reason::Timeout,
+            &LinkStats {
+                max_retry_streak: 2,
+                ..LinkStats::default()
+            },
+            0x12,
+        );
+        stats.store(&mut region);
+
+        assert_some_eq!(
+            PersistentStats::parse(&region),
+            PersistentStats {
+                sessions: 2,
+                timeouts: 1,
+                errors: 0,
+                recent: [Some(Reason::Timeout), Some(Reason::Closed), None, None],
+                worst_retry_streak: 2,
+                last_errors: [Some(0x12), None, None, None],
+            }
+        );
+    }
+
+    #[test]
+    fn recent_keeps_last_four() {
+        let mut stats = PersistentStats::default();
+        stats.record(Reason::Error, &LinkStats::default(), 0x15);
+        stats.record(Reason::Closed, &LinkStats::default(), 0x15);
+        stats.record(Reason::Closed, &LinkStats::default(), 0x15);
+        stats.record(Reason::Timeout, &LinkStats::default(), 0x15);
+        stats.record(Reason::Closed, &LinkStats::default(), 0x15);
+
+        assert_eq!(
+            stats.recent,
+            [
+                Some(Reason::Closed),
+                Some(Reason::Timeout),
+                Some(Reason::Closed),
+                Some(Reason::Closed)
+            ]
+        );
+        assert_eq!(stats.sessions, 5);
+        assert_eq!(stats.errors, 1);
+    }
+
+    #[test]
+    fn last_errors_keep_last_four() {
+        let mut stats = PersistentStats::default();
+        stats.record(Reason::Error, &LinkStats::default(), 0x10);
+        stats.record(Reason::Timeout, &LinkStats::default(), 0x12);
+        stats.record(Reason::Closed, &LinkStats::default(), 0x11);
+        stats.record(Reason::Error, &LinkStats::default(), 0x15);
+        stats.record(Reason::Error, &LinkStats::default(), 0x17);
+        stats.record(Reason::Timeout, &LinkStats::default(), 0x23);
+
+        assert_eq!(
+            stats.last_errors,
+            [Some(0x23), Some(0x17), Some(0x15), Some(0x12)]
+        );
+    }
+
+    #[test]
+    fn worst_retry_streak_kept() {
+        let mut stats = PersistentStats::default();
+        stats.record(
+            Reason::Closed,
+            &LinkStats {
+                max_retry_streak: 3,
+                ..LinkStats::default()
+            },
+            0x11,
+        );
+        stats.record(
+            Reason::Closed,
+            &LinkStats {
+                max_retry_streak: 1,
+                ..LinkStats::default()
+            },
+            0x11,
+        );
+
+        assert_eq!(stats.worst_retry_streak, 3);
+    }
+
+    #[test]
+    fn corruption_resets() {
+        let mut region = [0xff; LEN];
+        let mut stats = PersistentStats::load(&mut region);
+        stats.record(Reason::Error, &LinkStats::default(), 0x15);
+        stats.store(&mut region);
+        region[3] ^= 0x01;
+
+        assert_none!(PersistentStats::parse(&region));
+        assert_eq!(
+            PersistentStats::load(&mut region),
+            PersistentStats::default()
+        );
+        assert_some_eq!(PersistentStats::parse(&region), PersistentStats::default());
+    }
+
+    #[test]
+    fn serialize_writes_each_byte_once() {
+        let mut stats = PersistentStats::default();
+        stats.record(Reason::Timeout, &LinkStats::default(), 0x15);
+
+        let mut written = [false; LEN];
+        let mut bytes = [0; LEN];
+        stats.serialize(|offset, byte| {
+            assert!(!written[offset]);
+            written[offset] = true;
+            bytes[offset] = byte;
+        });
+
+        assert!(written.iter().all(|&written| written));
+        assert_some_eq!(PersistentStats::parse(&bytes), stats);
+    }
+
+    #[test]
+    fn link_stats_serial_gap() {
+        let mut stats = LinkStats::default();
+        stats.record_serial_gap(0);
+        stats.record_serial_gap(1);
+        stats.record_serial_gap(3);
+        stats.record_serial_gap(2);
+
+        assert_eq!(stats.max_serial_gap, 3);
+        assert_eq!(stats.serial_timeouts_recovered, 2);
+    }
+
+    #[test]
+    fn link_stats_retry_streak() {
+        let mut stats = LinkStats::default();
+        stats.record_retry(1);
+        stats.record_retry(2);
+        stats.record_retry(1);
+
+        assert_eq!(stats.retries, 3);
+        assert_eq!(stats.max_retry_streak, 2);
+    }
+
+    #[test]
+    fn link_stats_saturate() {
+        let mut stats = LinkStats {
+            packets_sent: u16::MAX,
+            ..LinkStats::default()
+        };
+        stats.record_sent();
+
+        assert_eq!(stats.packets_sent, u16::MAX);
+    }
+}
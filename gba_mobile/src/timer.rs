@@ -1,6 +1,6 @@
-use crate::mmio::timer::{TM0CNT, TM1CNT, TM2CNT, TM3CNT};
+use crate::mmio::timer::{Control, TM0CNT, TM1CNT, TM2CNT, TM3CNT};
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Timer {
     _0,
     _1,
@@ -9,6 +9,39 @@ pub enum Timer {
 }
 
 impl Timer {
+    pub(crate) fn is_running(self) -> bool {
+        match self {
+            Self::_0 => unsafe { TM0CNT.read_volatile().is_started() },
+            Self::_1 => unsafe { TM1CNT.read_volatile().is_started() },
+            Self::_2 => unsafe { TM2CNT.read_volatile().is_started() },
+            Self::_3 => unsafe { TM3CNT.read_volatile().is_started() },
+        }
+    }
+
+    /// Reads the timer's control register.
+    pub(crate) fn control(self) -> Control {
+        unsafe {
+            match self {
+                Self::_0 => TM0CNT.read_volatile(),
+                Self::_1 => TM1CNT.read_volatile(),
+                Self::_2 => TM2CNT.read_volatile(),
+                Self::_3 => TM3CNT.read_volatile(),
+            }
+        }
+    }
+
+    /// Overwrites the timer's control register.
+    pub(crate) fn set_control(self, control: Control) {
+        unsafe {
+            match self {
+                Self::_0 => TM0CNT.write_volatile(control),
+                Self::_1 => TM1CNT.write_volatile(control),
+                Self::_2 => TM2CNT.write_volatile(control),
+                Self::_3 => TM3CNT.write_volatile(control),
+            }
+        }
+    }
+
     pub(crate) fn stop(self) {
         match self {
             Self::_0 => unsafe { TM0CNT.write_volatile(TM0CNT.read_volatile().start(false)) },